@@ -0,0 +1,194 @@
+use crate::conversation::{self, ChatMessage, PendingAttachment};
+use crate::documents;
+use crate::knowledge::{self, CollectionSettings};
+use crate::i18n::{t, tf};
+use crate::lm_studio_client::{CompletionStats, LMStudioClient, Message as LMMessage};
+use crate::memory::{self, MemoryStore};
+
+// Liczba fragmentów wiedzy i wspomnień dołączanych do pytania
+const CONTEXT_LIMIT: u64 = 5;
+
+// Limity dla generowania tytułu: długość przekazanej rozmowy i samego tytułu
+const TITLE_SOURCE_CHARS: usize = 2000;
+const TITLE_MAX_CHARS: usize = 60;
+
+// Źródła kontekstu dołączane do pytania przed wysłaniem go do modelu
+#[derive(Debug, Clone, Default)]
+pub struct ContextOptions {
+    // Przeszukiwane kolekcje wiedzy; pusta lista wyłącza bazę wiedzy
+    pub collections: Vec<CollectionSettings>,
+    pub use_memory: bool,
+    pub embedding_model: String,
+}
+
+// Rozmowa z wybranym modelem: odpowiedzi z kontekstem, tytuł i zapamiętywanie faktów.
+// Wspólna dla okna aplikacji, trybu wiersza poleceń i serwera API
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub client: LMStudioClient,
+    pub model: String,
+    pub options: ContextOptions,
+    pub temperature: f32,
+}
+
+impl Session {
+    pub fn new(client: LMStudioClient, model: &str, options: ContextOptions, temperature: f32) -> Self {
+        Session {
+            client,
+            model: model.to_string(),
+            options,
+            temperature,
+        }
+    }
+
+    /// Historia poprzedzona kontekstem z pamięci i bazy wiedzy
+    pub async fn context(&self, query: &str, history: Vec<LMMessage>) -> Result<Vec<LMMessage>, String> {
+        with_context(&self.client, &self.model, query, history, &self.options).await
+    }
+
+    /// Odpowiedź modelu na historię uzupełnioną o kontekst, razem ze statystykami
+    pub async fn reply(&self, query: &str, history: Vec<LMMessage>) -> Result<(String, CompletionStats), String> {
+        let history = self.context(query, history).await?;
+
+        self.client
+            .send_message_with_stats(&self.model, history, self.temperature)
+            .await
+            .map_err(|e| tf("chat.error", &[&e]))
+    }
+
+    pub async fn title(&self, messages: &[ChatMessage]) -> Result<String, String> {
+        generate_title(&self.client, &self.model, messages).await
+    }
+
+    /// Wyodrębnia fakty z wymiany (pytanie + odpowiedź) i zapisuje je w pamięci
+    pub async fn remember(&self, exchange: &[LMMessage], source: &str) -> Result<usize, String> {
+        let store = MemoryStore::new(self.client.clone(), &self.options.embedding_model)?;
+        let facts = store.extract_facts(&self.model, exchange).await?;
+        store.remember(facts, source).await
+    }
+}
+
+/// Historia rozmowy w formacie API; treść załączników trafia tylko do ostatniej wiadomości
+pub fn build_history(messages: &[ChatMessage], attachments: &[PendingAttachment]) -> Vec<LMMessage> {
+    let mut history = conversation::to_lm_history(messages);
+
+    if let Some(last) = history.last_mut()
+        && !attachments.is_empty()
+    {
+        let context: Vec<String> = attachments
+            .iter()
+            .map(|a| documents::attachment_context(&a.attachment.name, &a.content, &last.content))
+            .collect();
+        last.content = format!("{}\n\n{}", context.join("\n\n"), last.content);
+    }
+
+    // LM Studio przycina zbyt długą historię od początku, a wiadomość systemowa zostaje,
+    // więc przypięte wiadomości są powtarzane właśnie w niej
+    let pinned: Vec<&ChatMessage> = messages.iter().filter(|m| m.pinned).collect();
+    if !pinned.is_empty() {
+        history.insert(0, LMMessage {
+            role: "system".to_string(),
+            content: format_pinned(&pinned),
+        });
+    }
+
+    history
+}
+
+fn format_pinned(messages: &[&ChatMessage]) -> String {
+    let quoted: Vec<String> = messages
+        .iter()
+        .map(|m| format!("{}: {}", if m.is_user { "Użytkownik" } else { "Asystent" }, m.content.trim()))
+        .collect();
+    format!(
+        "Użytkownik przypiął poniższe wiadomości z tej rozmowy. Zawsze bierz je pod uwagę:\n\n{}",
+        quoted.join("\n\n")
+    )
+}
+
+/// Poprzedza historię wiadomościami systemowymi z pamięci i bazy wiedzy
+pub async fn with_context(
+    client: &LMStudioClient,
+    model: &str,
+    query: &str,
+    mut history: Vec<LMMessage>,
+    options: &ContextOptions,
+) -> Result<Vec<LMMessage>, String> {
+    // Przypomnij fakty z wcześniejszych rozmów
+    if options.use_memory {
+        let store = MemoryStore::new(client.clone(), &options.embedding_model)?;
+        let memories = store.recall(query, CONTEXT_LIMIT).await?;
+        if !memories.is_empty() {
+            history.insert(0, LMMessage {
+                role: "system".to_string(),
+                content: memory::format_memories(&memories),
+            });
+        }
+    }
+
+    // Dołącz fragmenty z bazy wiedzy jako wiadomość systemową
+    if !options.collections.is_empty() {
+        let chunks = knowledge::search_collections(client, &options.collections, query, CONTEXT_LIMIT, model).await?;
+        if !chunks.is_empty() {
+            history.insert(0, LMMessage {
+                role: "system".to_string(),
+                content: knowledge::format_context(&chunks),
+            });
+        }
+    }
+
+    Ok(history)
+}
+
+/// Prosi model o krótki tytuł na podstawie początku rozmowy
+pub async fn generate_title(client: &LMStudioClient, model: &str, messages: &[ChatMessage]) -> Result<String, String> {
+    let transcript: String = messages
+        .iter()
+        .map(|m| format!("{}: {}", if m.is_user { "Użytkownik" } else { "Asystent" }, m.content))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .chars()
+        .take(TITLE_SOURCE_CHARS)
+        .collect();
+
+    let history = vec![
+        LMMessage {
+            role: "system".to_string(),
+            content: "Nadaj rozmowie krótki tytuł (najwyżej 6 słów) w języku rozmowy. \
+                      Odpowiedz samym tytułem, bez cudzysłowów i kropki na końcu."
+                .to_string(),
+        },
+        LMMessage {
+            role: "user".to_string(),
+            content: transcript,
+        },
+    ];
+
+    let response = client
+        .send_message(model, history)
+        .await
+        .map_err(|e| tf("chat.title_failed", &[&e]))?;
+
+    let title = clean_title(&response);
+    if title.is_empty() {
+        Err(t("chat.title_empty").to_string())
+    } else {
+        Ok(title)
+    }
+}
+
+// Modele lubią dodawać prefiksy, cudzysłowy i formatowanie Markdown
+fn clean_title(response: &str) -> String {
+    let line = response.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or_default();
+    let line = line
+        .strip_prefix("Tytuł:")
+        .or_else(|| line.strip_prefix("Title:"))
+        .unwrap_or(line);
+    let title = line
+        .trim()
+        .trim_matches(|c: char| matches!(c, '"' | '\'' | '„' | '”' | '*' | '#' | '`'))
+        .trim_end_matches('.')
+        .trim();
+
+    title.chars().take(TITLE_MAX_CHARS).collect::<String>().trim().to_string()
+}
@@ -0,0 +1,589 @@
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+
+use clap::{Parser, Subcommand};
+
+use knowledge_app::chat::{ContextOptions, Session};
+use knowledge_app::conversation::{self, ChatMessage, SavedConversation};
+use knowledge_app::export::{self, ExportFormat};
+use knowledge_app::import;
+use knowledge_app::knowledge::{self, KnowledgeBase};
+use knowledge_app::lm_studio_client::{self, LMStudioClient, Message as LMMessage};
+use knowledge_app::server::ApiServer;
+use knowledge_app::storage;
+
+#[derive(Parser)]
+#[command(
+    name = "KnowladgeAppV2",
+    about = "Czat z LM Studio i baza wiedzy bez otwierania okna aplikacji"
+)]
+struct Cli {
+    /// Adres serwera LM Studio
+    #[arg(long, global = true, env = "KNOWLEDGE_APP_LM_STUDIO_URL", default_value = lm_studio_client::DEFAULT_BASE_URL)]
+    lm_studio_url: String,
+    /// Adres gRPC bazy Qdrant
+    #[arg(long, global = true, env = "KNOWLEDGE_APP_QDRANT_URL", default_value = knowledge::DEFAULT_QDRANT_URL)]
+    qdrant_url: String,
+    #[command(subcommand)]
+    command: CliCommand,
+}
+
+#[derive(Subcommand)]
+enum CliCommand {
+    /// Zadaje jedno pytanie; treść ze standardowego wejścia jest dołączana do pytania
+    Ask {
+        prompt: Vec<String>,
+        #[arg(short, long)]
+        model: Option<String>,
+        /// Kolekcja wiedzy, z której pobrać kontekst (można podać kilka razy)
+        #[arg(short, long)]
+        collection: Vec<String>,
+        /// Wypisz odpowiedź dopiero po jej zakończeniu
+        #[arg(long)]
+        no_stream: bool,
+    },
+    /// Interaktywna rozmowa; z --conversation kontynuuje i zapisuje zapisaną rozmowę
+    Chat {
+        #[arg(short, long)]
+        conversation: Option<String>,
+        #[arg(short, long)]
+        model: Option<String>,
+    },
+    /// Lista zapisanych rozmów
+    List,
+    /// Wypisuje wiadomości zapisanej rozmowy
+    Show { name: String },
+    /// Eksportuje rozmowę (lub wszystkie z --all) do pliku albo na standardowe wyjście
+    Export {
+        name: Option<String>,
+        #[arg(long)]
+        all: bool,
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// json, markdown (md), html lub jsonl; domyślnie według rozszerzenia pliku wyjściowego
+        #[arg(short, long)]
+        format: Option<String>,
+    },
+    /// Importuje rozmowy z eksportów ChatGPT, Open WebUI i LM Studio, pomijając duplikaty
+    Import {
+        paths: Vec<PathBuf>,
+        /// Tylko pokaż, co zostałoby zaimportowane
+        #[arg(long)]
+        dry_run: bool,
+        /// Importuj także boczne gałęzie rozmów, nie tylko aktywną
+        #[arg(long)]
+        all_branches: bool,
+    },
+    /// Dodaje pliki lub całe foldery do kolekcji wiedzy
+    Ingest {
+        paths: Vec<PathBuf>,
+        #[arg(short, long, default_value = knowledge::DEFAULT_COLLECTION)]
+        collection: String,
+    },
+    /// Wyszukuje fragmenty w kolekcji wiedzy
+    Search {
+        query: Vec<String>,
+        #[arg(short, long, default_value = knowledge::DEFAULT_COLLECTION)]
+        collection: String,
+        #[arg(short, long, default_value_t = 5)]
+        limit: u64,
+    },
+    /// Uruchamia lokalny serwer HTTP API (czat z kontekstem, rozmowy, kolekcje)
+    Serve {
+        #[arg(short, long)]
+        port: Option<u16>,
+    },
+}
+
+/// Uruchamia tryb wiersza poleceń i zwraca kod wyjścia procesu
+pub fn run() -> i32 {
+    let cli = Cli::parse();
+    let client = LMStudioClient::with_base_url(&cli.lm_studio_url);
+    knowledge::set_qdrant_url(&cli.qdrant_url);
+
+    let result = match cli.command {
+        CliCommand::Ask { prompt, model, collection, no_stream } => ask(&client, prompt, model, collection, no_stream),
+        CliCommand::Chat { conversation, model } => chat(&client, conversation, model),
+        CliCommand::List => list(),
+        CliCommand::Show { name } => show(&name),
+        CliCommand::Export { name, all, output, format } => export(name, all, output, format),
+        CliCommand::Import { paths, dry_run, all_branches } => import_conversations(paths, dry_run, all_branches),
+        CliCommand::Ingest { paths, collection } => ingest(&client, paths, &collection),
+        CliCommand::Search { query, collection, limit } => {
+            search(&client, &query.join(" "), &collection, limit, &mut io::stdout())
+        }
+        CliCommand::Serve { port } => serve(&client, port),
+    };
+
+    match result {
+        Ok(()) => 0,
+        Err(error) => {
+            eprintln!("Błąd: {}", error);
+            1
+        }
+    }
+}
+
+fn ask(client: &LMStudioClient, prompt: Vec<String>, model: Option<String>, collections: Vec<String>, no_stream: bool) -> Result<(), String> {
+    let mut prompt = prompt.join(" ");
+
+    // Dane przekazane potokiem, np. `cat raport.txt | KnowladgeAppV2 ask "Podsumuj"`
+    if !io::stdin().is_terminal() {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|e| format!("Nie udało się odczytać standardowego wejścia: {}", e))?;
+        prompt = if prompt.trim().is_empty() {
+            input
+        } else {
+            format!("{}\n\n{}", prompt, input)
+        };
+    }
+    if prompt.trim().is_empty() {
+        return Err("Brak treści pytania".to_string());
+    }
+
+    let model = model.unwrap_or_else(|| lm_studio_client::DEFAULT_MODEL.to_string());
+    let history = vec![LMMessage {
+        role: "user".to_string(),
+        content: prompt.clone(),
+    }];
+
+    let settings = storage::load_settings();
+    let options = ContextOptions {
+        collections: collections.iter().map(|name| settings.collection(name)).collect(),
+        use_memory: false,
+        embedding_model: knowledge::DEFAULT_EMBEDDING_MODEL.to_string(),
+    };
+    let session = Session::new(client.clone(), &model, options, lm_studio_client::DEFAULT_TEMPERATURE);
+
+    if no_stream {
+        let (response, _) = runtime()?.block_on(session.reply(&prompt, history))?;
+        println!("{}", response);
+    } else {
+        let history = runtime()?.block_on(session.context(&prompt, history))?;
+        stream_reply(client, &model, history)?;
+    }
+
+    Ok(())
+}
+
+fn chat(client: &LMStudioClient, conversation: Option<String>, model: Option<String>) -> Result<(), String> {
+    let model = model.unwrap_or_else(|| lm_studio_client::DEFAULT_MODEL.to_string());
+    let mut conversations = storage::load_conversations();
+
+    let mut messages: Vec<ChatMessage> = conversation
+        .as_ref()
+        .and_then(|name| conversations.iter().find(|c| &c.name == name))
+        .map(|c| c.messages.clone())
+        .unwrap_or_default();
+
+    let interactive = io::stdin().is_terminal();
+    if interactive {
+        eprintln!("Rozmowa z {} (wpisz /exit, aby zakończyć)", model);
+    }
+
+    let stdin = io::stdin();
+    loop {
+        if interactive {
+            print!("> ");
+            let _ = io::stdout().flush();
+        }
+
+        let mut line = String::new();
+        let read = stdin
+            .lock()
+            .read_line(&mut line)
+            .map_err(|e| format!("Nie udało się odczytać wejścia: {}", e))?;
+        let line = line.trim();
+        if read == 0 || line == "/exit" {
+            break;
+        }
+        if line.is_empty() {
+            continue;
+        }
+
+        messages.push(ChatMessage {
+            content: line.to_string(),
+            is_user: true,
+            timestamp: conversation::now(),
+            attachments: Vec::new(),
+            pinned: false,
+            stats: None,
+        });
+
+        let history = conversation::to_lm_history(&messages);
+        let response = stream_reply(client, &model, history)?;
+
+        messages.push(ChatMessage {
+            content: response,
+            is_user: false,
+            timestamp: conversation::now(),
+            attachments: Vec::new(),
+            pinned: false,
+            stats: None,
+        });
+    }
+
+    if let Some(name) = conversation
+        && !messages.is_empty()
+    {
+        match conversations.iter_mut().find(|c| c.name == name) {
+            Some(existing) => {
+                existing.messages = messages;
+                existing.touch();
+            }
+            None => conversations.push(SavedConversation::new(
+                name,
+                messages,
+                conversation::default_collections(),
+            )),
+        }
+        storage::save_conversations(&conversations);
+    }
+
+    Ok(())
+}
+
+fn list() -> Result<(), String> {
+    let mut conversations = storage::load_conversations();
+    conversations.sort_by(|a, b| b.pinned.cmp(&a.pinned).then(b.updated_at.cmp(&a.updated_at)));
+
+    for conversation in conversations {
+        let mut details = format!("{} wiadomości", conversation.messages.len());
+        if let Some(folder) = &conversation.folder {
+            details.push_str(&format!(", folder: {}", folder));
+        }
+        if !conversation.tags.is_empty() {
+            details.push_str(&format!(", tagi: {}", conversation.tags.join(", ")));
+        }
+        println!("{}{}\t({})", if conversation.pinned { "📌 " } else { "" }, conversation.name, details);
+    }
+    Ok(())
+}
+
+fn show(name: &str) -> Result<(), String> {
+    let conversation = find_conversation(name)?;
+    for message in &conversation.messages {
+        let role = if message.is_user { "Użytkownik" } else { "Asystent" };
+        println!("[{}] {}:\n{}\n", message.timestamp, role, message.content);
+    }
+    Ok(())
+}
+
+fn export(name: Option<String>, all: bool, output: Option<PathBuf>, format: Option<String>) -> Result<(), String> {
+    let format = match &format {
+        Some(value) => ExportFormat::parse(value).ok_or_else(|| format!("Nieznany format eksportu: {}", value))?,
+        None => output
+            .as_deref()
+            .and_then(|path| path.extension())
+            .and_then(|extension| ExportFormat::parse(&extension.to_string_lossy()))
+            .unwrap_or(ExportFormat::Json),
+    };
+
+    let conversations = if all {
+        storage::load_conversations()
+    } else {
+        let name = name.ok_or_else(|| "Podaj nazwę rozmowy albo użyj --all".to_string())?;
+        vec![find_conversation(&name)?]
+    };
+
+    let content = export::export(&conversations, format)?;
+    write_output(&content, output.as_deref())
+}
+
+fn import_conversations(paths: Vec<PathBuf>, dry_run: bool, all_branches: bool) -> Result<(), String> {
+    let mut imported = Vec::new();
+    for path in &paths {
+        let (source, conversations) = import::read_file(path)?;
+        imported.extend(conversations.into_iter().map(|c| (source, c)));
+    }
+
+    let mut conversations = storage::load_conversations();
+    let mut candidates = import::preview(imported, &conversations);
+    for candidate in &mut candidates {
+        if all_branches && !candidate.duplicate {
+            candidate.selected = true;
+        }
+        // "=" - już zapisana, "-" - boczna gałąź pominięta bez --all-branches
+        println!(
+            "{} {} ({}, {} wiadomości)",
+            if candidate.duplicate { "=" } else if candidate.selected { "+" } else { "-" },
+            candidate.conversation.name,
+            candidate.source.label(),
+            candidate.conversation.messages.len()
+        );
+    }
+
+    if !dry_run {
+        let added = import::merge(&mut conversations, candidates);
+        storage::save_conversations(&conversations);
+        println!("Zaimportowano rozmów: {}", added);
+    }
+    Ok(())
+}
+
+fn ingest(client: &LMStudioClient, paths: Vec<PathBuf>, collection: &str) -> Result<(), String> {
+    let settings = storage::load_settings();
+    let kb = KnowledgeBase::new(client.clone(), &settings.collection(collection).embedding_model)?;
+    let files = knowledge::expand_paths(&paths);
+
+    for (file, result) in runtime()?.block_on(kb.ingest_files(collection, &files))? {
+        match result {
+            Ok(count) => println!("{}: {} fragmentów", file.display(), count),
+            Err(error) => eprintln!("{}: {}", file.display(), error),
+        }
+    }
+
+    Ok(())
+}
+
+fn search(client: &LMStudioClient, query: &str, collection: &str, limit: u64, out: &mut impl Write) -> Result<(), String> {
+    let settings = storage::load_settings();
+    let collection = settings.collection(collection);
+    let kb = KnowledgeBase::new(client.clone(), &collection.embedding_model)?;
+    let chunks = runtime()?.block_on(kb.hybrid_search(&collection, query, limit))?;
+
+    for (index, chunk) in chunks.iter().enumerate() {
+        writeln!(out, "[{}] {} ({:.3})", index + 1, chunk.citation(), chunk.score)
+            .and_then(|_| writeln!(out, "{}\n", chunk.payload.text.trim()))
+            .map_err(|e| format!("Nie udało się wypisać wyników: {}", e))?;
+    }
+    Ok(())
+}
+
+fn serve(client: &LMStudioClient, port: Option<u16>) -> Result<(), String> {
+    let port = port.unwrap_or(storage::load_settings().api_server_port);
+    let server = ApiServer::start(port, client.base_url())?;
+    eprintln!("Serwer API nasłuchuje na http://{} (Ctrl+C kończy)", server.address);
+    server.wait();
+    Ok(())
+}
+
+// Wypisuje odpowiedź na bieżąco, fragment po fragmencie
+fn stream_reply(client: &LMStudioClient, model: &str, history: Vec<LMMessage>) -> Result<String, String> {
+    let mut stdout = io::stdout();
+    let response = runtime()?
+        .block_on(client.send_message_stream(model, history, |token| {
+            let _ = stdout.write_all(token.as_bytes());
+            let _ = stdout.flush();
+        }))
+        .map_err(|e| format!("Błąd komunikacji z LM Studio: {}", e))?;
+    println!();
+    Ok(response)
+}
+
+fn find_conversation(name: &str) -> Result<SavedConversation, String> {
+    storage::load_conversations()
+        .into_iter()
+        .find(|c| c.name == name)
+        .ok_or_else(|| format!("Nie znaleziono rozmowy: {}", name))
+}
+
+fn write_output(content: &str, output: Option<&Path>) -> Result<(), String> {
+    match output {
+        Some(path) => fs::write(path, content)
+            .map_err(|e| format!("Nie udało się zapisać {}: {}", path.display(), e)),
+        None => {
+            println!("{}", content);
+            Ok(())
+        }
+    }
+}
+
+fn runtime() -> Result<tokio::runtime::Runtime, String> {
+    tokio::runtime::Runtime::new()
+        .map_err(|e| format!("Nie udało się uruchomić środowiska asynchronicznego: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::convert::Infallible;
+    use std::future::{ready, Ready};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::task::{Context, Poll};
+    use std::thread;
+
+    use knowledge_app::knowledge::bm25::Bm25Index;
+    use knowledge_app::knowledge::ChunkPayload;
+    use qdrant_client::qdrant::{
+        CollectionExists, CollectionExistsRequest, CollectionExistsResponse, HealthCheckReply,
+        HealthCheckRequest, RetrievedPoint, ScoredPoint, ScrollPoints, ScrollResponse, SearchPoints,
+        SearchResponse, Value,
+    };
+    use tonic::body::Body;
+    use tonic::codegen::{http, BoxFuture, Service};
+    use tonic::server::{Grpc, NamedService};
+    use tonic::transport::server::TcpIncoming;
+    use tonic::transport::Server;
+    use tonic_prost::ProstCodec;
+
+    // Jedyny fragment w kolekcji udawanej przez serwer Qdrant
+    fn stored_payload() -> HashMap<String, Value> {
+        let payload = ChunkPayload {
+            path: "/docs/raport.pdf".to_string(),
+            file_name: "raport.pdf".to_string(),
+            chunk_index: 0,
+            text: "  Przychody wzrosły o 12%.  ".to_string(),
+            page: Some(3),
+            heading: None,
+            content_hash: String::new(),
+        };
+        knowledge::to_payload(&payload).unwrap().into()
+    }
+
+    // Odpowiedź gRPC wyliczana z samej treści zapytania
+    struct Reply<F>(F);
+
+    impl<Req, Res, F: Fn(Req) -> Res> Service<tonic::Request<Req>> for Reply<F> {
+        type Response = tonic::Response<Res>;
+        type Error = tonic::Status;
+        type Future = Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: tonic::Request<Req>) -> Self::Future {
+            ready(Ok(tonic::Response::new((self.0)(request.into_inner()))))
+        }
+    }
+
+    // Usługi Qdrant potrzebne wyszukiwaniu: kontrola wersji, istnienie kolekcji, wyszukiwanie i przeglądanie
+    #[derive(Clone)]
+    struct QdrantStub<const SERVICE: u8>;
+
+    impl NamedService for QdrantStub<0> {
+        const NAME: &'static str = "qdrant.Qdrant";
+    }
+
+    impl NamedService for QdrantStub<1> {
+        const NAME: &'static str = "qdrant.Collections";
+    }
+
+    impl NamedService for QdrantStub<2> {
+        const NAME: &'static str = "qdrant.Points";
+    }
+
+    impl<const SERVICE: u8> Service<http::Request<Body>> for QdrantStub<SERVICE> {
+        type Response = http::Response<Body>;
+        type Error = Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: http::Request<Body>) -> Self::Future {
+            Box::pin(async move {
+                let response = match request.uri().path() {
+                    "/qdrant.Qdrant/HealthCheck" => {
+                        let reply = Reply(|_: HealthCheckRequest| HealthCheckReply {
+                            version: "1.19.0".to_string(),
+                            ..Default::default()
+                        });
+                        Grpc::new(ProstCodec::default()).unary(reply, request).await
+                    }
+                    "/qdrant.Collections/CollectionExists" => {
+                        let reply = Reply(|_: CollectionExistsRequest| CollectionExistsResponse {
+                            result: Some(CollectionExists { exists: true }),
+                            ..Default::default()
+                        });
+                        Grpc::new(ProstCodec::default()).unary(reply, request).await
+                    }
+                    "/qdrant.Points/Search" => {
+                        let reply = Reply(|search: SearchPoints| {
+                            assert_eq!(search.vector, vec![0.5, 0.25]);
+                            SearchResponse {
+                                result: vec![ScoredPoint {
+                                    payload: stored_payload(),
+                                    score: 0.9,
+                                    ..Default::default()
+                                }],
+                                ..Default::default()
+                            }
+                        });
+                        Grpc::new(ProstCodec::default()).unary(reply, request).await
+                    }
+                    "/qdrant.Points/Scroll" => {
+                        let reply = Reply(|_: ScrollPoints| ScrollResponse {
+                            result: vec![RetrievedPoint {
+                                payload: stored_payload(),
+                                ..Default::default()
+                            }],
+                            ..Default::default()
+                        });
+                        Grpc::new(ProstCodec::default()).unary(reply, request).await
+                    }
+                    path => tonic::Status::unimplemented(path.to_string()).into_http(),
+                };
+                Ok(response)
+            })
+        }
+    }
+
+    fn mock_qdrant() -> String {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            runtime().unwrap().block_on(async {
+                let incoming = TcpIncoming::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+                sender.send(incoming.local_addr().unwrap()).unwrap();
+                Server::builder()
+                    .add_service(QdrantStub::<0>)
+                    .add_service(QdrantStub::<1>)
+                    .add_service(QdrantStub::<2>)
+                    .serve_with_incoming(incoming)
+                    .await
+                    .unwrap();
+            });
+        });
+        format!("http://{}", receiver.recv().unwrap())
+    }
+
+    // Serwer udający LM Studio, który zwraca stałe osadzenie
+    fn mock_lm_studio() -> String {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", server.server_addr());
+        thread::spawn(move || {
+            for request in server.incoming_requests() {
+                assert_eq!(request.url(), "/v1/embeddings");
+                let body = r#"{"data":[{"embedding":[0.5,0.25],"index":0}]}"#;
+                let _ = request.respond(tiny_http::Response::from_string(body));
+            }
+        });
+        address
+    }
+
+    #[test]
+    fn search_prints_numbered_results() {
+        knowledge::set_qdrant_url(&mock_qdrant());
+        let collection = format!("cli-search-{}", std::process::id());
+        let mut out = Vec::new();
+
+        let result = search(&LMStudioClient::with_base_url(&mock_lm_studio()), "przychody", &collection, 5, &mut out);
+
+        // Wyszukiwanie hybrydowe zapisuje indeks BM25 zbudowany z fragmentów kolekcji
+        Bm25Index::remove(&collection);
+        let _ = fs::remove_dir("knowledge_index");
+        result.unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("[1] raport.pdf"), "{}", out);
+        assert!(out.contains("(0.033)\nPrzychody wzrosły o 12%.\n"), "{}", out);
+        assert!(!out.contains("[2]"), "{}", out);
+    }
+
+    #[test]
+    fn search_reports_lm_studio_errors() {
+        // Adres Qdrant jest wspólny dla procesu; oba testy ustawiają serwer o tym samym zachowaniu
+        knowledge::set_qdrant_url(&mock_qdrant());
+        // Port zwolniony zaraz po zajęciu - nikt na nim nie nasłuchuje
+        let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let client = LMStudioClient::with_base_url(&format!("http://127.0.0.1:{}", port));
+
+        assert!(search(&client, "przychody", "knowledge", 5, &mut Vec::new()).is_err());
+    }
+}
@@ -0,0 +1,240 @@
+use crate::export::ExportFormat;
+use crate::i18n::{t, tf};
+
+// Zakres temperatury akceptowany przez LM Studio
+pub const MAX_TEMPERATURE: f32 = 2.0;
+
+// Liczba podpowiedzi wyświetlanych nad polem wiadomości
+const SUGGESTION_LIMIT: usize = 8;
+
+// Polecenie wpisane w polu wiadomości zamiast pytania do modelu
+#[derive(Debug, Clone, PartialEq)]
+pub enum SlashCommand {
+    Model(String),
+    // None usuwa instrukcję systemową rozmowy
+    System(Option<String>),
+    Temperature(f32),
+    // Bez nazwy otwiera okno zapisu
+    Save(Option<String>),
+    Clear,
+    Search(String),
+    Ingest(String),
+    Export(ExportFormat),
+}
+
+// Opis polecenia do podpowiedzi: nazwa oraz klucze tłumaczeń składni argumentu i opisu
+pub struct CommandInfo {
+    pub name: &'static str,
+    pub usage: &'static str,
+    pub description: &'static str,
+}
+
+pub const COMMANDS: &[CommandInfo] = &[
+    CommandInfo { name: "model", usage: "commands.model_usage", description: "commands.model" },
+    CommandInfo { name: "system", usage: "commands.system_usage", description: "commands.system" },
+    CommandInfo { name: "temp", usage: "commands.temp_usage", description: "commands.temp" },
+    CommandInfo { name: "save", usage: "commands.save_usage", description: "commands.save" },
+    CommandInfo { name: "clear", usage: "", description: "commands.clear" },
+    CommandInfo { name: "search", usage: "commands.search_usage", description: "commands.search" },
+    CommandInfo { name: "ingest", usage: "commands.ingest_usage", description: "commands.ingest" },
+    CommandInfo { name: "export", usage: "commands.export_usage", description: "commands.export" },
+];
+
+// Podpowiedź: tekst wstawiany do pola po wybraniu, etykieta i opis
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub completion: String,
+    pub label: String,
+    pub description: String,
+}
+
+/// Rozpoznaje polecenie; None, gdy tekst jest zwykłą wiadomością.
+/// Tekst zaczynający się od `//` też jest wiadomością (patrz `unescape`)
+pub fn parse(input: &str) -> Option<Result<SlashCommand, String>> {
+    let input = input.trim();
+    let rest = input.strip_prefix('/')?;
+    if rest.starts_with('/') {
+        return None;
+    }
+
+    let (name, argument) = match rest.split_once(char::is_whitespace) {
+        Some((name, argument)) => (name, argument.trim()),
+        None => (rest, ""),
+    };
+    let required = |usage: &'static str| {
+        if argument.is_empty() {
+            Err(tf("commands.missing_argument", &[&format!("/{}", name), &t(usage)]))
+        } else {
+            Ok(argument.to_string())
+        }
+    };
+
+    let command = match name.to_lowercase().as_str() {
+        "model" => required("commands.model_usage").map(SlashCommand::Model),
+        "system" => Ok(SlashCommand::System((!argument.is_empty()).then(|| argument.to_string()))),
+        "temp" => required("commands.temp_usage").and_then(|value| {
+            value
+                .replace(',', ".")
+                .parse::<f32>()
+                .ok()
+                .filter(|temperature| (0.0..=MAX_TEMPERATURE).contains(temperature))
+                .map(SlashCommand::Temperature)
+                .ok_or_else(|| tf("commands.invalid_temperature", &[&value, &MAX_TEMPERATURE]))
+        }),
+        "save" => Ok(SlashCommand::Save((!argument.is_empty()).then(|| argument.to_string()))),
+        "clear" => Ok(SlashCommand::Clear),
+        "search" => Ok(SlashCommand::Search(argument.to_string())),
+        "ingest" => required("commands.ingest_usage").map(SlashCommand::Ingest),
+        "export" => required("commands.export_usage").and_then(|value| {
+            ExportFormat::parse(&value).map(SlashCommand::Export).ok_or_else(|| tf("commands.unknown_format", &[&value]))
+        }),
+        _ => Err(tf("commands.unknown", &[&format!("/{}", name)])),
+    };
+    Some(command)
+}
+
+/// Wiadomość zaczynająca się od `//` trafia do modelu z jednym ukośnikiem
+pub fn unescape(input: &str) -> &str {
+    if input.trim_start().starts_with("//") {
+        &input.trim_start()[1..]
+    } else {
+        input
+    }
+}
+
+/// Podpowiedzi dla wpisywanego polecenia: nazwy poleceń, a po `/model` i `/export` ich argumenty
+pub fn suggestions(input: &str, models: &[String]) -> Vec<Suggestion> {
+    // Edytor zawsze kończy tekst znakiem nowej linii
+    let input = input.strip_suffix('\n').unwrap_or(input);
+    let Some(rest) = input.trim_start().strip_prefix('/') else {
+        return Vec::new();
+    };
+    if rest.contains('\n') || rest.starts_with('/') {
+        return Vec::new();
+    }
+
+    let suggestions: Vec<Suggestion> = match rest.split_once(' ') {
+        None => {
+            let typed = rest.to_lowercase();
+            COMMANDS
+                .iter()
+                .filter(|command| command.name.starts_with(&typed))
+                .map(|command| Suggestion {
+                    completion: if command.usage.is_empty() {
+                        format!("/{}", command.name)
+                    } else {
+                        format!("/{} ", command.name)
+                    },
+                    label: if command.usage.is_empty() {
+                        format!("/{}", command.name)
+                    } else {
+                        format!("/{} {}", command.name, t(command.usage))
+                    },
+                    description: t(command.description).to_string(),
+                })
+                .collect()
+        }
+        Some(("model", typed)) => {
+            let typed = typed.trim().to_lowercase();
+            models
+                .iter()
+                .filter(|model| model.to_lowercase().contains(&typed))
+                .map(|model| Suggestion {
+                    completion: format!("/model {}", model),
+                    label: model.clone(),
+                    description: String::new(),
+                })
+                .collect()
+        }
+        Some(("export", typed)) => {
+            let typed = typed.trim().to_lowercase();
+            ExportFormat::ALL
+                .into_iter()
+                .filter(|format| format.extension().starts_with(&typed))
+                .map(|format| Suggestion {
+                    completion: format!("/export {}", format.extension()),
+                    label: format.extension().to_string(),
+                    description: format.label().to_string(),
+                })
+                .collect()
+        }
+        Some(_) => Vec::new(),
+    };
+
+    // Dokładnie wpisane polecenie nie potrzebuje podpowiedzi
+    if suggestions.len() == 1 && suggestions[0].completion.trim_end() == input.trim_end() {
+        return Vec::new();
+    }
+    suggestions.into_iter().take(SUGGESTION_LIMIT).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn completions(input: &str, models: &[String]) -> Vec<String> {
+        suggestions(input, models).into_iter().map(|s| s.completion).collect()
+    }
+
+    #[test]
+    fn plain_text_is_not_a_command() {
+        assert_eq!(parse("Jak działa BM25?"), None);
+        assert_eq!(parse("//etc/hosts to plik"), None);
+        assert_eq!(unescape("//etc/hosts to plik"), "/etc/hosts to plik");
+        assert_eq!(unescape("bez ukośnika"), "bez ukośnika");
+    }
+
+    #[test]
+    fn parses_commands_and_arguments() {
+        assert_eq!(parse("/model qwen2.5-7b"), Some(Ok(SlashCommand::Model("qwen2.5-7b".to_string()))));
+        assert_eq!(parse("  /CLEAR  "), Some(Ok(SlashCommand::Clear)));
+        assert_eq!(parse("/temp 0,7"), Some(Ok(SlashCommand::Temperature(0.7))));
+        assert_eq!(parse("/system"), Some(Ok(SlashCommand::System(None))));
+        assert_eq!(parse("/save"), Some(Ok(SlashCommand::Save(None))));
+        assert_eq!(parse("/search"), Some(Ok(SlashCommand::Search(String::new()))));
+        assert_eq!(parse("/export MD"), Some(Ok(SlashCommand::Export(ExportFormat::Markdown))));
+    }
+
+    #[test]
+    fn keeps_trailing_arguments_together() {
+        assert_eq!(
+            parse("/system  Odpowiadaj krótko,\npo polsku.  "),
+            Some(Ok(SlashCommand::System(Some("Odpowiadaj krótko,\npo polsku.".to_string()))))
+        );
+        assert_eq!(parse("/save Plan  na\tjutro"), Some(Ok(SlashCommand::Save(Some("Plan  na\tjutro".to_string())))));
+        assert!(matches!(parse("/temp 0.5 1"), Some(Err(_))));
+        assert!(matches!(parse("/export md json"), Some(Err(_))));
+    }
+
+    #[test]
+    fn rejects_unknown_commands_and_bad_arguments() {
+        assert!(matches!(parse("/foo bar"), Some(Err(e)) if e.contains("/foo")));
+        assert!(matches!(parse("/model"), Some(Err(e)) if e.contains("/model")));
+        assert!(matches!(parse("/ingest   "), Some(Err(_))));
+        assert!(matches!(parse("/temp 2.5"), Some(Err(_))));
+        assert!(matches!(parse("/temp ciepło"), Some(Err(_))));
+        assert!(matches!(parse("/export pdf"), Some(Err(e)) if e.contains("pdf")));
+    }
+
+    #[test]
+    fn suggests_command_names() {
+        assert_eq!(completions("/s\n", &[]), vec!["/system ", "/save ", "/search "]);
+        assert_eq!(completions("/cl", &[]), vec!["/clear"]);
+        assert_eq!(completions("/", &[]).len(), COMMANDS.len());
+        assert!(completions("/clear", &[]).is_empty());
+        assert!(completions("/xyz", &[]).is_empty());
+        assert!(completions("pytanie", &[]).is_empty());
+        assert!(completions("//s", &[]).is_empty());
+    }
+
+    #[test]
+    fn suggests_arguments_of_model_and_export() {
+        let models = vec!["Qwen2.5-7B".to_string(), "llama-3.1-8b".to_string()];
+
+        assert_eq!(completions("/model qwen", &models), vec!["/model Qwen2.5-7B"]);
+        assert_eq!(completions("/model ", &models).len(), 2);
+        assert_eq!(completions("/export j", &[]), vec!["/export json", "/export jsonl"]);
+        assert!(completions("/export jsonl", &[]).is_empty());
+        assert!(completions("/temp 1", &models).is_empty());
+    }
+}
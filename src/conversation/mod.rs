@@ -0,0 +1,147 @@
+use chrono::{Duration, Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use crate::knowledge;
+use crate::lm_studio_client::{CompletionStats, Message as LMMessage};
+
+// Nazwa rozmowy, która nie dostała jeszcze tytułu
+pub const DEFAULT_CONVERSATION_NAME: &str = "Nowa rozmowa";
+
+// Struktura dla wiadomości w czacie
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub content: String,
+    pub is_user: bool,
+    pub timestamp: String,
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    // Przypięta wiadomość jest zawsze przypominana modelowi na początku kontekstu
+    #[serde(default)]
+    pub pinned: bool,
+    // Statystyki generowania odpowiedzi modelu
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<CompletionStats>,
+}
+
+// Odniesienie do pliku załączonego do wiadomości
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attachment {
+    pub name: String,
+    pub path: String,
+}
+
+// Załącznik wybrany w polu wpisywania, jeszcze nie wysłany
+#[derive(Debug, Clone)]
+pub struct PendingAttachment {
+    pub attachment: Attachment,
+    pub content: String,
+}
+
+// Struktura dla zapisanych rozmów
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedConversation {
+    pub name: String,
+    pub messages: Vec<ChatMessage>,
+    // Kolekcje wiedzy, z których rozmowa pobiera kontekst
+    #[serde(default = "default_collections")]
+    pub collections: Vec<String>,
+    // Folder w panelu bocznym; None oznacza rozmowę poza folderami
+    #[serde(default)]
+    pub folder: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub pinned: bool,
+    // Czas ostatniej zmiany w formacie sortowalnym tekstowo
+    #[serde(default)]
+    pub updated_at: String,
+    // Instrukcja systemowa i temperatura ustawione poleceniami /system i /temp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+}
+
+impl SavedConversation {
+    pub fn new(name: String, messages: Vec<ChatMessage>, collections: Vec<String>) -> Self {
+        SavedConversation {
+            name,
+            messages,
+            collections,
+            folder: None,
+            tags: Vec::new(),
+            pinned: false,
+            updated_at: now(),
+            system_prompt: None,
+            temperature: None,
+        }
+    }
+
+    /// Oznacza rozmowę jako zmienioną teraz
+    pub fn touch(&mut self) {
+        self.updated_at = now();
+    }
+}
+
+// Rozmowa przeniesiona do kosza
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedConversation {
+    pub conversation: SavedConversation,
+    pub deleted_at: String,
+}
+
+impl TrashedConversation {
+    pub fn new(conversation: SavedConversation) -> Self {
+        TrashedConversation {
+            conversation,
+            deleted_at: now(),
+        }
+    }
+
+    /// Czy rozmowa leży w koszu dłużej niż okres przechowywania; 0 dni wyłącza usuwanie
+    pub fn is_expired(&self, retention_days: u32) -> bool {
+        if retention_days == 0 {
+            return false;
+        }
+        match NaiveDateTime::parse_from_str(&self.deleted_at, TIME_FORMAT) {
+            Ok(deleted_at) => {
+                Local::now().naive_local() - deleted_at > Duration::days(retention_days as i64)
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+pub fn now() -> String {
+    Local::now().format(TIME_FORMAT).to_string()
+}
+
+pub fn default_collections() -> Vec<String> {
+    vec![knowledge::DEFAULT_COLLECTION.to_string()]
+}
+
+/// Dokleja numer, jeśli inna zapisana rozmowa ma już taką nazwę; `current` to nazwa
+/// zmienianej rozmowy, która nie koliduje sama ze sobą
+pub fn unique_name(existing: &[SavedConversation], name: &str, current: &str) -> String {
+    let taken = |candidate: &str| candidate != current && existing.iter().any(|c| c.name == candidate);
+    if !taken(name) {
+        return name.to_string();
+    }
+    (2..)
+        .map(|n| format!("{} ({})", name, n))
+        .find(|candidate| !taken(candidate))
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// Zamienia wiadomości czatu na historię w formacie API LM Studio
+pub fn to_lm_history(messages: &[ChatMessage]) -> Vec<LMMessage> {
+    messages
+        .iter()
+        .map(|msg| LMMessage {
+            role: if msg.is_user { "user" } else { "assistant" }.to_string(),
+            content: msg.content.clone(),
+        })
+        .collect()
+}
@@ -0,0 +1,239 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use super::DocumentSection;
+use crate::i18n::tf;
+
+// Tekst dokumentu Word znajduje się w word/document.xml wewnątrz archiwum ZIP
+pub fn extract(path: &Path) -> Result<Vec<DocumentSection>, String> {
+    let file = File::open(path)
+        .map_err(|e| tf("documents.open_failed", &[&e]))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| tf("documents.invalid_docx", &[&e]))?;
+
+    let mut xml = String::new();
+    archive
+        .by_name("word/document.xml")
+        .map_err(|e| tf("documents.invalid_docx", &[&e]))?
+        .read_to_string(&mut xml)
+        .map_err(|e| tf("documents.docx_read_failed", &[&e]))?;
+
+    parse_document_xml(&xml)
+}
+
+fn parse_document_xml(xml: &str) -> Result<Vec<DocumentSection>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut sections = Vec::new();
+    let mut current = DocumentSection {
+        page: Some(1),
+        ..Default::default()
+    };
+    let mut page = 1;
+    let mut paragraph = String::new();
+    let mut is_heading = false;
+    let mut in_text = false;
+    // Word zapisuje miejsca podziału stron z ostatniego renderowania, również po ręcznych
+    // podziałach; liczymy tylko jeden rodzaj znacznika, żeby nie liczyć stron podwójnie
+    let rendered_breaks = xml.contains("lastRenderedPageBreak");
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                b"p" => {
+                    paragraph.clear();
+                    is_heading = false;
+                }
+                b"t" => in_text = true,
+                _ => {}
+            },
+            Ok(Event::Empty(e)) => match e.local_name().as_ref() {
+                // Style nagłówków: "Heading1" w angielskim Wordzie, "Nagwek1" w polskim
+                b"pStyle" => {
+                    for attr in e.attributes().flatten() {
+                        if attr.key.local_name().as_ref() == b"val" {
+                            let style = attr.unescape_value().unwrap_or_default().to_lowercase();
+                            is_heading = style.starts_with("heading")
+                                || style.starts_with("nagwek")
+                                || style == "title";
+                        }
+                    }
+                }
+                b"br" if !rendered_breaks => {
+                    let page_break = e.attributes().flatten().any(|attr| {
+                        attr.key.local_name().as_ref() == b"type"
+                            && attr.value.as_ref() == b"page"
+                    });
+                    if page_break {
+                        page += 1;
+                        next_page(&mut sections, &mut current, &mut paragraph, is_heading, page);
+                    }
+                }
+                b"lastRenderedPageBreak" if rendered_breaks => {
+                    page += 1;
+                    next_page(&mut sections, &mut current, &mut paragraph, is_heading, page);
+                }
+                b"tab" => paragraph.push('\t'),
+                _ => {}
+            },
+            Ok(Event::Text(e)) if in_text => {
+                paragraph.push_str(&e.unescape().unwrap_or_default());
+            }
+            Ok(Event::End(e)) => match e.local_name().as_ref() {
+                b"t" => in_text = false,
+                b"p" => {
+                    let line = paragraph.trim().to_string();
+                    if is_heading && !line.is_empty() {
+                        if !current.text.trim().is_empty() {
+                            sections.push(current.clone());
+                        }
+                        current = DocumentSection {
+                            text: String::new(),
+                            page: Some(page),
+                            heading: Some(line.clone()),
+                        };
+                    } else if current.text.trim().is_empty() {
+                        current.page = Some(page);
+                    }
+                    current.text.push_str(&line);
+                    current.text.push('\n');
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(tf("documents.docx_parse_failed", &[&e])),
+            _ => {}
+        }
+    }
+
+    if !current.text.trim().is_empty() {
+        sections.push(current);
+    }
+
+    Ok(sections)
+}
+
+// Tekst z nowej strony trafia do kontynuacji sekcji z tym samym nagłówkiem,
+// żeby cytat wskazywał stronę, na której tekst naprawdę się znajduje
+fn next_page(
+    sections: &mut Vec<DocumentSection>,
+    current: &mut DocumentSection,
+    paragraph: &mut String,
+    is_heading: bool,
+    page: u32,
+) {
+    // Nagłówek rozdzielony podziałem strony zostaje w całości
+    if is_heading {
+        return;
+    }
+
+    let before = paragraph.trim();
+    if !before.is_empty() {
+        current.text.push_str(before);
+        current.text.push('\n');
+    }
+    paragraph.clear();
+
+    if current.text.trim().is_empty() {
+        current.page = Some(page);
+        return;
+    }
+    let heading = current.heading.clone();
+    sections.push(std::mem::replace(
+        current,
+        DocumentSection {
+            text: String::new(),
+            page: Some(page),
+            heading,
+        },
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(body: &str) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>{}</w:body></w:document>"#,
+            body
+        )
+    }
+
+    fn heading(text: &str) -> String {
+        format!(r#"<w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t>{}</w:t></w:r></w:p>"#, text)
+    }
+
+    fn paragraph(text: &str) -> String {
+        format!("<w:p><w:r><w:t>{}</w:t></w:r></w:p>", text)
+    }
+
+    fn pages(sections: &[DocumentSection]) -> Vec<(Option<u32>, Option<&str>, &str)> {
+        sections
+            .iter()
+            .map(|s| (s.page, s.heading.as_deref(), s.text.trim()))
+            .collect()
+    }
+
+    #[test]
+    fn manual_page_breaks_split_sections_under_the_same_heading() {
+        let xml = document(&format!(
+            r#"{}{}<w:p><w:r><w:t>Koniec strony</w:t><w:br w:type="page"/><w:t>Początek drugiej</w:t></w:r></w:p><w:p><w:r><w:br/></w:r></w:p>{}{}"#,
+            heading("Wstęp"),
+            paragraph("Pierwsza strona"),
+            heading("Wyniki"),
+            paragraph("Tabela &amp; wykres"),
+        ));
+
+        let sections = parse_document_xml(&xml).unwrap();
+
+        assert_eq!(
+            pages(&sections),
+            vec![
+                (Some(1), Some("Wstęp"), "Wstęp\nPierwsza strona\nKoniec strony"),
+                (Some(2), Some("Wstęp"), "Początek drugiej"),
+                (Some(2), Some("Wyniki"), "Wyniki\nTabela & wykres"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rendered_breaks_are_not_counted_twice() {
+        // Word zapisuje po ręcznym podziale także znacznik z renderowania
+        let xml = document(&format!(
+            r#"{}<w:p><w:r><w:br w:type="page"/></w:r><w:r><w:lastRenderedPageBreak/><w:t>Druga strona</w:t></w:r></w:p><w:p><w:r><w:lastRenderedPageBreak/><w:t>Trzecia strona</w:t></w:r></w:p>"#,
+            paragraph("Pierwsza strona"),
+        ));
+
+        let sections = parse_document_xml(&xml).unwrap();
+
+        assert_eq!(
+            pages(&sections),
+            vec![
+                (Some(1), None, "Pierwsza strona"),
+                (Some(2), None, "Druga strona"),
+                (Some(3), None, "Trzecia strona"),
+            ]
+        );
+    }
+
+    #[test]
+    fn polish_heading_styles_and_leading_breaks() {
+        let xml = document(&format!(
+            r#"<w:p><w:r><w:br w:type="page"/></w:r></w:p><w:p><w:pPr><w:pStyle w:val="Nagwek2"/></w:pPr><w:r><w:t>Rozdział</w:t></w:r></w:p>{}"#,
+            paragraph("Treść"),
+        ));
+
+        let sections = parse_document_xml(&xml).unwrap();
+
+        assert_eq!(pages(&sections), vec![(Some(2), Some("Rozdział"), "Rozdział\nTreść")]);
+    }
+
+    #[test]
+    fn malformed_xml_is_an_error() {
+        assert!(parse_document_xml("<w:document><w:body></w:p>").is_err());
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use zip::ZipArchive;
+
+use super::{html, DocumentSection};
+use crate::i18n::{t, tf};
+
+// EPUB to archiwum ZIP z rozdziałami XHTML w kolejności opisanej w pliku OPF
+pub fn extract(path: &Path) -> Result<Vec<DocumentSection>, String> {
+    let file = File::open(path)
+        .map_err(|e| tf("documents.open_failed", &[&e]))?;
+    let mut archive = ZipArchive::new(file)
+        .map_err(|e| tf("documents.invalid_epub", &[&e]))?;
+
+    let container = read_entry(&mut archive, "META-INF/container.xml")?;
+    let opf_path = find_attribute(&container, b"rootfile", b"full-path")
+        .ok_or_else(|| t("documents.epub_no_opf").to_string())?;
+    let opf = read_entry(&mut archive, &opf_path)?;
+    let base_dir = match opf_path.rfind('/') {
+        Some(index) => opf_path[..=index].to_string(),
+        None => String::new(),
+    };
+
+    let mut sections = Vec::new();
+    for href in spine_documents(&opf)? {
+        let chapter = match read_entry(&mut archive, &format!("{}{}", base_dir, href)) {
+            Ok(chapter) => chapter,
+            Err(_) => continue,
+        };
+        sections.extend(html::extract(&chapter));
+    }
+
+    Ok(sections)
+}
+
+fn read_entry(archive: &mut ZipArchive<File>, name: &str) -> Result<String, String> {
+    let mut content = String::new();
+    archive
+        .by_name(name)
+        .map_err(|e| tf("documents.epub_missing_entry", &[&name, &e]))?
+        .read_to_string(&mut content)
+        .map_err(|e| tf("documents.entry_read_failed", &[&name, &e]))?;
+    Ok(content)
+}
+
+fn find_attribute(xml: &str, element: &[u8], attribute: &[u8]) -> Option<String> {
+    let mut reader = Reader::from_str(xml);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) if e.local_name().as_ref() == element => {
+                for attr in e.attributes().flatten() {
+                    if attr.key.local_name().as_ref() == attribute {
+                        return attr.unescape_value().ok().map(|v| v.to_string());
+                    }
+                }
+            }
+            Ok(Event::Eof) | Err(_) => return None,
+            _ => {}
+        }
+    }
+}
+
+// Zwraca ścieżki rozdziałów w kolejności czytania (manifest + spine)
+fn spine_documents(opf: &str) -> Result<Vec<String>, String> {
+    let mut reader = Reader::from_str(opf);
+    let mut manifest = HashMap::new();
+    let mut spine = Vec::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                let mut attributes = HashMap::new();
+                for attr in e.attributes().flatten() {
+                    let key = String::from_utf8_lossy(attr.key.local_name().as_ref()).to_string();
+                    let value = attr.unescape_value().unwrap_or_default().to_string();
+                    attributes.insert(key, value);
+                }
+                match e.local_name().as_ref() {
+                    b"item" => {
+                        if let (Some(id), Some(href)) = (attributes.get("id"), attributes.get("href")) {
+                            manifest.insert(id.clone(), href.clone());
+                        }
+                    }
+                    b"itemref" => {
+                        if let Some(idref) = attributes.get("idref") {
+                            spine.push(idref.clone());
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(tf("documents.epub_parse_failed", &[&e])),
+            _ => {}
+        }
+    }
+
+    Ok(spine
+        .iter()
+        .filter_map(|id| manifest.get(id).cloned())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::FileOptions;
+    use zip::ZipWriter;
+
+    fn write_epub(path: &Path, entries: &[(&str, &str)]) {
+        let mut zip = ZipWriter::new(File::create(path).unwrap());
+        for (name, content) in entries {
+            zip.start_file(*name, FileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn reads_chapters_in_spine_order() {
+        let path = std::env::temp_dir().join(format!("knowledge_app_test_{}.epub", std::process::id()));
+        write_epub(&path, &[
+            (
+                "META-INF/container.xml",
+                r#"<container><rootfiles><rootfile full-path="OEBPS/content.opf"/></rootfiles></container>"#,
+            ),
+            (
+                "OEBPS/content.opf",
+                r#"<package><manifest>
+                    <item id="c1" href="text/one.xhtml"/><item id="c2" href="text/two.xhtml"/><item id="gone" href="missing.xhtml"/>
+                </manifest><spine><itemref idref="c2"/><itemref idref="gone"/><itemref idref="c1"/></spine></package>"#,
+            ),
+            ("OEBPS/text/one.xhtml", "<html><body><h1>Pierwszy</h1><p>Jeden</p></body></html>"),
+            ("OEBPS/text/two.xhtml", "<html><body><h1>Drugi</h1><p>Dwa</p></body></html>"),
+        ]);
+
+        let sections = extract(&path);
+        let _ = std::fs::remove_file(&path);
+
+        let headings: Vec<Option<String>> = sections.unwrap().into_iter().map(|s| s.heading).collect();
+        assert_eq!(headings, vec![Some("Drugi".to_string()), Some("Pierwszy".to_string())]);
+    }
+
+    #[test]
+    fn spine_skips_unknown_ids() {
+        let opf = r#"<package><manifest><item id="a" href="a.xhtml"/></manifest><spine><itemref idref="b"/><itemref idref="a"/></spine></package>"#;
+
+        assert_eq!(spine_documents(opf).unwrap(), vec!["a.xhtml"]);
+    }
+}
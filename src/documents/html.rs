@@ -0,0 +1,147 @@
+use super::DocumentSection;
+
+// Znaczniki, po których zaczyna się nowa linia tekstu
+const BLOCK_TAGS: &[&str] = &[
+    "p", "div", "br", "li", "tr", "section", "article", "blockquote", "pre", "table",
+];
+
+/// Zamienia HTML na tekst podzielony na sekcje według nagłówków h1-h6
+pub fn extract(html: &str) -> Vec<DocumentSection> {
+    let mut sections = Vec::new();
+    let mut current = DocumentSection::default();
+    let mut heading: Option<String> = None;
+    let mut skip_until: Option<&str> = None;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            push_text(&mut current, &mut heading, rest, skip_until.is_some());
+            break;
+        };
+        push_text(&mut current, &mut heading, &rest[..start], skip_until.is_some());
+
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or("")
+            .to_lowercase();
+
+        // Zawartość skryptów i styli pomijamy w całości
+        if let Some(skipped) = skip_until {
+            if closing && name == skipped {
+                skip_until = None;
+            }
+            continue;
+        }
+        if !closing && (name == "script" || name == "style") {
+            skip_until = Some(if name == "script" { "script" } else { "style" });
+            continue;
+        }
+
+        if is_heading_tag(&name) {
+            if closing {
+                if let Some(title) = heading.take() {
+                    let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+                    current.text.push_str(&title);
+                    current.text.push('\n');
+                    current.heading = Some(title);
+                }
+            } else {
+                if !current.text.trim().is_empty() {
+                    sections.push(std::mem::take(&mut current));
+                } else {
+                    current = DocumentSection::default();
+                }
+                heading = Some(String::new());
+            }
+        } else if BLOCK_TAGS.contains(&name.as_str()) {
+            current.text.push('\n');
+        }
+    }
+
+    if !current.text.trim().is_empty() {
+        sections.push(current);
+    }
+
+    for section in &mut sections {
+        section.text = normalize_whitespace(&section.text);
+    }
+
+    sections
+}
+
+fn push_text(current: &mut DocumentSection, heading: &mut Option<String>, raw: &str, skipped: bool) {
+    if skipped || raw.is_empty() {
+        return;
+    }
+    let decoded = decode_entities(raw);
+    match heading {
+        Some(title) => title.push_str(&decoded),
+        None => current.text.push_str(&decoded),
+    }
+}
+
+fn is_heading_tag(name: &str) -> bool {
+    name.len() == 2 && name.starts_with('h') && matches!(name.as_bytes()[1], b'1'..=b'6')
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+// Usuwa puste linie i nadmiarowe spacje pozostałe po znacznikach
+fn normalize_whitespace(text: &str) -> String {
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_by_headings_and_skips_scripts() {
+        let html = r#"<html><head><title>T</title><style>p { color: red }</style></head><body>
+            <p>Wstęp&nbsp;do   tematu</p>
+            <h1 class="x">Rozdział <em>pierwszy</em></h1><p>Tom &amp; Jerry</p><script>var a = "<h2>";</script>
+            <h2>Rozdział 2</h2><ul><li>jeden</li><li>dwa</li></ul>
+        </body></html>"#;
+
+        let sections = extract(html);
+
+        let sections: Vec<(Option<&str>, &str)> =
+            sections.iter().map(|s| (s.heading.as_deref(), s.text.as_str())).collect();
+        assert_eq!(
+            sections,
+            vec![
+                (None, "T\nWstęp do tematu"),
+                (Some("Rozdział pierwszy"), "Rozdział pierwszy\nTom & Jerry"),
+                (Some("Rozdział 2"), "Rozdział 2\njeden\ndwa"),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_and_tag_only_documents_have_no_sections() {
+        assert!(extract("").is_empty());
+        assert!(extract("<div><br/></div>").is_empty());
+        assert_eq!(extract("zwykły tekst")[0].text, "zwykły tekst");
+    }
+}
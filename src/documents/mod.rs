@@ -0,0 +1,229 @@
+use std::fs;
+use std::path::Path;
+
+use crate::i18n::tf;
+
+// Ekstraktory tekstu dla poszczególnych formatów
+mod docx;
+mod epub;
+mod html;
+mod pdf;
+
+// Rozszerzenia plików, które można załączyć do wiadomości lub dodać do bazy wiedzy
+pub const SUPPORTED_EXTENSIONS: &[&str] = &[
+    "txt", "md", "rs", "csv", "pdf", "docx", "html", "htm", "epub",
+];
+
+// Powyżej tej liczby znaków załącznik jest dzielony na fragmenty
+// i do modelu trafiają tylko fragmenty najbardziej pasujące do pytania
+pub const MAX_INLINE_CHARS: usize = 12_000;
+
+const CHUNK_SIZE: usize = 1_500;
+const CHUNK_OVERLAP: usize = 200;
+const MAX_CHUNKS: usize = 6;
+
+// Fragment dokumentu wraz z miejscem, z którego pochodzi
+#[derive(Debug, Clone, Default)]
+pub struct DocumentSection {
+    pub text: String,
+    pub page: Option<u32>,
+    pub heading: Option<String>,
+}
+
+// Fragment gotowy do osadzenia i zapisania w bazie wiedzy
+#[derive(Debug, Clone)]
+pub struct DocumentChunk {
+    pub index: usize,
+    pub text: String,
+    pub page: Option<u32>,
+    pub heading: Option<String>,
+}
+
+/// Zwraca rozszerzenie pliku małymi literami
+pub fn file_extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default()
+}
+
+/// Czy plik ma format obsługiwany przez ekstraktory
+pub fn is_supported(path: &Path) -> bool {
+    SUPPORTED_EXTENSIONS.contains(&file_extension(path).as_str())
+}
+
+/// Wyodrębnia tekst z pliku z zachowaniem numerów stron i nagłówków
+pub fn extract_sections(path: &Path) -> Result<Vec<DocumentSection>, String> {
+    let extension = file_extension(path);
+
+    let sections = match extension.as_str() {
+        "pdf" => pdf::extract(path)?,
+        "docx" => docx::extract(path)?,
+        "epub" => epub::extract(path)?,
+        "html" | "htm" => html::extract(&read_text_file(path)?),
+        "md" => markdown_sections(&read_text_file(path)?),
+        "txt" | "rs" | "csv" => vec![DocumentSection {
+            text: read_text_file(path)?,
+            ..Default::default()
+        }],
+        _ => return Err(tf("documents.unsupported_type", &[&extension])),
+    };
+
+    Ok(sections
+        .into_iter()
+        .filter(|s| !s.text.trim().is_empty())
+        .collect())
+}
+
+/// Odczytuje cały tekst z obsługiwanego pliku
+pub fn read_document_text(path: &Path) -> Result<String, String> {
+    let sections = extract_sections(path)?;
+    Ok(sections
+        .iter()
+        .map(|s| s.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n"))
+}
+
+/// Dzieli sekcje dokumentu na fragmenty, przenosząc ich metadane
+pub fn chunk_sections(sections: &[DocumentSection]) -> Vec<DocumentChunk> {
+    let mut chunks = Vec::new();
+
+    for section in sections {
+        for text in chunk_text(&section.text, CHUNK_SIZE, CHUNK_OVERLAP) {
+            chunks.push(DocumentChunk {
+                index: chunks.len(),
+                text,
+                page: section.page,
+                heading: section.heading.clone(),
+            });
+        }
+    }
+
+    chunks
+}
+
+fn read_text_file(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path)
+        .map_err(|e| tf("documents.read_failed", &[&e]))
+}
+
+// Markdown dzielimy na sekcje według nagłówków "#"; "#" w blokach kodu to np. komentarze
+fn markdown_sections(content: &str) -> Vec<DocumentSection> {
+    let mut sections = Vec::new();
+    let mut current = DocumentSection::default();
+    let mut in_code_block = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+        }
+        if !in_code_block && trimmed.starts_with('#') {
+            if !current.text.trim().is_empty() {
+                sections.push(current.clone());
+            }
+            current = DocumentSection {
+                text: String::new(),
+                page: None,
+                heading: Some(trimmed.trim_start_matches('#').trim().to_string()),
+            };
+        }
+        current.text.push_str(line);
+        current.text.push('\n');
+    }
+
+    if !current.text.trim().is_empty() {
+        sections.push(current);
+    }
+
+    sections
+}
+
+/// Dzieli tekst na zachodzące na siebie fragmenty (liczone w znakach, nie bajtach)
+pub fn chunk_text(text: &str, chunk_size: usize, overlap: usize) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let step = chunk_size.saturating_sub(overlap).max(1);
+    let mut chunks = Vec::new();
+    let mut start = 0;
+
+    while start < chars.len() {
+        let end = (start + chunk_size).min(chars.len());
+        let chunk: String = chars[start..end].iter().collect();
+        if !chunk.trim().is_empty() {
+            chunks.push(chunk);
+        }
+        if end == chars.len() {
+            break;
+        }
+        start += step;
+    }
+
+    chunks
+}
+
+/// Wybiera fragmenty zawierające najwięcej słów z pytania, w kolejności z dokumentu
+pub fn select_relevant_chunks(chunks: &[String], query: &str, limit: usize) -> Vec<String> {
+    let terms = tokenize(query);
+
+    let mut scored: Vec<(usize, usize)> = chunks
+        .iter()
+        .enumerate()
+        .map(|(index, chunk)| {
+            let words = tokenize(chunk);
+            let score = words.iter().filter(|w| terms.contains(w)).count();
+            (index, score)
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    let mut selected: Vec<usize> = scored.into_iter().take(limit).map(|(i, _)| i).collect();
+    selected.sort();
+
+    selected.into_iter().map(|i| chunks[i].clone()).collect()
+}
+
+/// Przygotowuje treść załącznika do wstawienia w kontekst rozmowy
+pub fn attachment_context(name: &str, content: &str, query: &str) -> String {
+    if content.chars().count() <= MAX_INLINE_CHARS {
+        return format!("--- Załącznik: {} ---\n{}\n--- Koniec załącznika ---", name, content);
+    }
+
+    let chunks = chunk_text(content, CHUNK_SIZE, CHUNK_OVERLAP);
+    let relevant = select_relevant_chunks(&chunks, query, MAX_CHUNKS);
+    format!(
+        "--- Załącznik: {} (wybrane fragmenty) ---\n{}\n--- Koniec załącznika ---",
+        name,
+        relevant.join("\n[...]\n")
+    )
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() > 2)
+        .map(|w| w.to_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_headings_inside_code_fences_do_not_split_sections() {
+        let content = "Wstęp\n# Instalacja\n```bash\n# komentarz w skrypcie\ncargo build\n```\n## Użycie\nUruchom.\n";
+
+        let sections = markdown_sections(content);
+
+        let headings: Vec<Option<&str>> = sections.iter().map(|s| s.heading.as_deref()).collect();
+        assert_eq!(headings, vec![None, Some("Instalacja"), Some("Użycie")]);
+        assert!(sections[1].text.contains("# komentarz w skrypcie\ncargo build"));
+    }
+
+    #[test]
+    fn chunks_overlap_and_respect_characters() {
+        let chunks = chunk_text("ąęćłńóśźż", 4, 1);
+
+        assert_eq!(chunks, vec!["ąęćł", "łńóś", "śźż"]);
+    }
+}
@@ -0,0 +1,20 @@
+use std::path::Path;
+
+use super::DocumentSection;
+use crate::i18n::tf;
+
+// Każda strona PDF staje się osobną sekcją z numerem strony
+pub fn extract(path: &Path) -> Result<Vec<DocumentSection>, String> {
+    let pages = pdf_extract::extract_text_by_pages(path)
+        .map_err(|e| tf("documents.pdf_read_failed", &[&e]))?;
+
+    Ok(pages
+        .into_iter()
+        .enumerate()
+        .map(|(index, text)| DocumentSection {
+            text,
+            page: Some(index as u32 + 1),
+            heading: None,
+        })
+        .collect())
+}
@@ -0,0 +1,260 @@
+use serde::Serialize;
+
+use crate::conversation::{self, SavedConversation};
+use crate::i18n::{self, t};
+use crate::lm_studio_client::Message as LMMessage;
+
+// Formaty, do których można wyeksportować rozmowy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Markdown,
+    Html,
+    Jsonl,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 4] = [
+        ExportFormat::Json,
+        ExportFormat::Markdown,
+        ExportFormat::Html,
+        ExportFormat::Jsonl,
+    ];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+            ExportFormat::Jsonl => "jsonl",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportFormat::Json => "JSON",
+            ExportFormat::Markdown => "Markdown",
+            ExportFormat::Html => "HTML",
+            ExportFormat::Jsonl => "JSONL",
+        }
+    }
+
+    /// Rozpoznaje format po nazwie lub rozszerzeniu, np. "md" albo "markdown"
+    pub fn parse(value: &str) -> Option<ExportFormat> {
+        let value = value.trim().to_lowercase();
+        ExportFormat::ALL
+            .into_iter()
+            .find(|format| format.extension() == value || format.label().to_lowercase() == value)
+    }
+}
+
+// Jeden przykład treningowy w formacie fine-tuningu OpenAI
+#[derive(Serialize)]
+struct TrainingExample {
+    messages: Vec<LMMessage>,
+}
+
+/// Zamienia rozmowy na tekst w wybranym formacie
+pub fn export(conversations: &[SavedConversation], format: ExportFormat) -> Result<String, String> {
+    match format {
+        ExportFormat::Json => serde_json::to_string_pretty(conversations).map_err(|e| e.to_string()),
+        ExportFormat::Markdown => Ok(conversations
+            .iter()
+            .map(to_markdown)
+            .collect::<Vec<_>>()
+            .join("\n---\n\n")),
+        ExportFormat::Html => Ok(to_html(conversations)),
+        ExportFormat::Jsonl => to_jsonl(conversations),
+    }
+}
+
+/// Proponowana nazwa pliku dla eksportu jednej lub wielu rozmów
+pub fn file_name(conversations: &[SavedConversation], format: ExportFormat) -> String {
+    let stem = match conversations {
+        [conversation] => sanitize_file_name(&conversation.name),
+        _ => t("export.file_name").to_string(),
+    };
+    format!("{}.{}", stem, format.extension())
+}
+
+/// Treść wiadomości bez składni Markdown: nagłówków, cytatów, wyróżnień, kodu i linków
+pub fn plain_text(markdown: &str) -> String {
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            lines.push(line.to_string());
+            continue;
+        }
+
+        let line = match trimmed.trim_start_matches('#') {
+            heading if heading.len() < trimmed.len() && heading.starts_with(' ') => heading.trim_start(),
+            _ => trimmed.trim_start_matches("> "),
+        };
+        let line = match line.strip_prefix("* ").or_else(|| line.strip_prefix("- ")) {
+            Some(item) => format!("• {}", item),
+            None => line.to_string(),
+        };
+        lines.push(strip_inline_markup(&line));
+    }
+
+    lines.join("\n").trim().to_string()
+}
+
+// Usuwa `*` i `` ` `` wyróżnień i kodu oraz zamienia [tekst](adres) na sam tekst
+fn strip_inline_markup(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut rest = line;
+
+    while let Some(c) = rest.chars().next() {
+        if c == '['
+            && let Some((label, after)) = rest[1..].split_once("](")
+            && let Some(end) = after.find(')')
+        {
+            result.push_str(label);
+            rest = &after[end + 1..];
+            continue;
+        }
+        if !matches!(c, '*' | '`') {
+            result.push(c);
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+
+    result.trim().to_string()
+}
+
+fn role_label(is_user: bool) -> &'static str {
+    if is_user { t("export.user") } else { t("export.assistant") }
+}
+
+fn to_markdown(conversation: &SavedConversation) -> String {
+    let mut markdown = format!("# {}\n\n", conversation.name);
+
+    for message in &conversation.messages {
+        markdown.push_str(&format!("**{}** · {}\n\n", role_label(message.is_user), message.timestamp));
+        for attachment in &message.attachments {
+            markdown.push_str(&format!("📎 {}\n\n", attachment.name));
+        }
+        markdown.push_str(message.content.trim());
+        markdown.push_str("\n\n");
+    }
+
+    markdown
+}
+
+// Samodzielny dokument HTML z osadzonymi stylami, bez zewnętrznych zasobów
+fn to_html(conversations: &[SavedConversation]) -> String {
+    let title = match conversations {
+        [conversation] => conversation.name.as_str(),
+        _ => t("export.conversations"),
+    };
+
+    let mut body = String::new();
+    for conversation in conversations {
+        body.push_str(&format!("<section>\n<h1>{}</h1>\n", escape_html(&conversation.name)));
+        for message in &conversation.messages {
+            let class = if message.is_user { "user" } else { "assistant" };
+            body.push_str(&format!(
+                "<div class=\"message {}\">\n<div class=\"meta\">{} · {}</div>\n",
+                class,
+                role_label(message.is_user),
+                escape_html(&message.timestamp)
+            ));
+            for attachment in &message.attachments {
+                body.push_str(&format!("<div class=\"attachment\">📎 {}</div>\n", escape_html(&attachment.name)));
+            }
+            body.push_str(&format!("<div class=\"content\">{}</div>\n</div>\n", escape_html(message.content.trim())));
+        }
+        body.push_str("</section>\n");
+    }
+
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"{}\">
+<head>
+<meta charset=\"utf-8\">
+<title>{}</title>
+<style>
+body {{ font-family: sans-serif; background: #f2f2f2; margin: 0; padding: 20px; }}
+section {{ max-width: 800px; margin: 0 auto 40px; }}
+h1 {{ font-size: 20px; color: #333; }}
+.message {{ border-radius: 10px; padding: 10px 14px; margin: 10px 0; max-width: 80%; }}
+.user {{ background: #0080ff; color: #fff; margin-left: auto; }}
+.assistant {{ background: #fff; color: #222; }}
+.meta {{ font-size: 12px; opacity: 0.7; margin-bottom: 4px; }}
+.attachment {{ font-size: 12px; }}
+.content {{ white-space: pre-wrap; }}
+</style>
+</head>
+<body>
+{}</body>
+</html>
+",
+        i18n::language().code(),
+        escape_html(title),
+        body
+    )
+}
+
+// Każda rozmowa to jedna linia z tablicą `messages`
+fn to_jsonl(conversations: &[SavedConversation]) -> Result<String, String> {
+    let mut lines = Vec::new();
+    for conversation in conversations.iter().filter(|c| !c.messages.is_empty()) {
+        let example = TrainingExample {
+            messages: conversation::to_lm_history(&conversation.messages),
+        };
+        lines.push(serde_json::to_string(&example).map_err(|e| e.to_string())?);
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn sanitize_file_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' { c } else { '_' })
+        .collect();
+    let sanitized = sanitized.trim();
+    if sanitized.is_empty() { t("export.conversation").to_string() } else { sanitized.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_text_strips_markdown_but_keeps_code() {
+        let markdown = "## Wynik\n\n> **Uwaga:** użyj `cargo test`\n* pierwszy\n- [dokumentacja](https://docs.rs)\n#hashtag\n```rust\n    let x = *y; // [a](b)\n```\n";
+
+        assert_eq!(
+            plain_text(markdown),
+            "Wynik\n\nUwaga: użyj cargo test\n• pierwszy\n• dokumentacja\n#hashtag\n    let x = *y; // [a](b)"
+        );
+    }
+
+    #[test]
+    fn plain_text_keeps_unfinished_links() {
+        assert_eq!(plain_text("[nie link] i [(a)"), "[nie link] i [(a)");
+    }
+
+    #[test]
+    fn parses_formats_by_extension_or_label() {
+        assert_eq!(ExportFormat::parse(" JSONL "), Some(ExportFormat::Jsonl));
+        assert_eq!(ExportFormat::parse("html"), Some(ExportFormat::Html));
+        assert_eq!(ExportFormat::parse("Markdown"), Some(ExportFormat::Markdown));
+        assert_eq!(ExportFormat::parse("pdf"), None);
+    }
+}
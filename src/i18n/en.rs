@@ -0,0 +1,268 @@
+// Angielskie teksty interfejsu
+pub const MESSAGES: &[(&str, &str)] = &[
+    ("app.title", "Chat with LM Studio"),
+    ("conversation.default_name", "New conversation"),
+    ("sidebar.conversations", "Conversations"),
+    ("sidebar.new_conversation", "+ New conversation"),
+    ("sidebar.pinned", "📌 Pinned"),
+    ("sidebar.unfiled", "No folder"),
+    ("sidebar.unfiled_selected", "✓ No folder"),
+    ("sidebar.new_folder", "New folder..."),
+    ("sidebar.import", "Import conversations"),
+    ("sidebar.search", "Search conversations... (Ctrl+F)"),
+    ("sidebar.all_tags", "All"),
+    ("sidebar.rename_placeholder", "Conversation name..."),
+    ("sidebar.rename", "Rename"),
+    ("sidebar.pin", "Pin"),
+    ("sidebar.unpin", "Unpin"),
+    ("sidebar.new_tag", "New tag..."),
+    ("sidebar.folder", "Folder"),
+    ("sidebar.tags", "Tags"),
+    ("sidebar.export_conversation", "Export conversation"),
+    ("sidebar.export_all", "Export all"),
+    ("knowledge.title", "Knowledge base"),
+    ("knowledge.use_in_answers", "Use in answers"),
+    ("knowledge.add_documents", "+ Add documents"),
+    ("knowledge.manage_collections", "Manage collections"),
+    ("knowledge.watch_collection", "Collection:"),
+    ("knowledge.watch_folder", "+ Watch folder"),
+    ("knowledge.indexing_changes", "⏳ Indexing changes..."),
+    ("knowledge.documents_filter", "Documents"),
+    ("knowledge.indexing", "Indexing documents..."),
+    ("knowledge.indexed", "Indexed {0} files ({1} chunks)"),
+    ("knowledge.synced", "Synced {0} – {1}"),
+    ("memory.title", "Memory"),
+    ("memory.remember_facts", "Remember facts from conversations"),
+    ("memory.browse", "Browse memory"),
+    ("memory.panel_title", "Long-term memory"),
+    ("memory.empty", "No remembered facts"),
+    ("memory.fact_placeholder", "Fact..."),
+    ("memory.forget", "Forget"),
+    ("memory.remembered", "Facts remembered: {0}"),
+    ("models.button", "Models"),
+    ("models.title", "LM Studio models"),
+    ("models.chat_model", "Chat model: {0}"),
+    ("models.loaded", "loaded"),
+    ("models.refresh", "Refresh"),
+    ("models.reload", "Reload model"),
+    ("models.fetching", "Fetching model list..."),
+    ("models.reloading", "Reloading {0}..."),
+    ("models.reload_failed", "Failed to reload model: {0}"),
+    ("models.reloaded", "Model reloaded"),
+    ("settings.button", "⚙ Settings"),
+    ("settings.title", "Settings"),
+    ("settings.language", "Language"),
+    ("settings.theme", "Theme"),
+    ("settings.api_title", "Local API"),
+    ("settings.api_enabled", "Serve the API on localhost"),
+    ("settings.port", "Port:"),
+    ("settings.port_hint", "A new port takes effect after restarting the server"),
+    ("settings.api_running", "API server: http://{0}"),
+    ("theme.system", "System"),
+    ("theme.light", "Light"),
+    ("theme.dark", "Dark"),
+    ("theme.high_contrast", "High contrast"),
+    ("trash.title", "Trash"),
+    ("trash.retention", "Delete permanently after (days, 0 - never):"),
+    ("trash.empty_button", "Empty trash"),
+    ("trash.empty_confirm", "Permanently delete all conversations in the trash?"),
+    ("trash.is_empty", "Trash is empty"),
+    ("trash.deleted", "Deleted {0} · {1} messages"),
+    ("trash.restore", "Restore"),
+    ("trash.purge", "Delete permanently"),
+    ("trash.purge_confirm", "Permanently delete conversation “{0}”?"),
+    ("trash.moved", "Moved “{0}” to the trash"),
+    ("chat.regenerate_title", "Regenerate title"),
+    ("chat.save", "Save conversation"),
+    ("chat.clear", "Clear chat"),
+    ("chat.attach", "Attach file"),
+    ("chat.stop", "Stop"),
+    ("chat.send", "Send"),
+    ("chat.jump_to_latest", "↓ Jump to latest"),
+    ("chat.error", "LM Studio communication error: {0}"),
+    ("message.copy", "Copy"),
+    ("message.copy_text", "Copy text"),
+    ("message.quote", "Quote"),
+    ("message.pin", "Pin"),
+    ("message.unpin", "Unpin"),
+    ("chat.thinking", "Model is typing"),
+    ("chat.elapsed", "{0} s since sending"),
+    ("stats.speed", "{0} tok/s"),
+    ("stats.tokens", "{0} prompt tok, {1} completion tok"),
+    ("stats.first_token", "first token after {0} s"),
+    ("stats.duration", "{0} s total"),
+    ("compare.button", "⚖ Compare"),
+    ("compare.tooltip", "Send the prompt to several models and pick the best answer"),
+    ("compare.models", "Compared models:"),
+    ("compare.select_hint", "Select at least two models"),
+    ("compare.sequential", "One at a time (only one model fits in memory)"),
+    ("compare.keep", "Keep this answer"),
+    ("compare.stopped", "Stopped"),
+    ("templates.tooltip", "Prompt templates"),
+    ("templates.title", "Prompt templates"),
+    ("templates.empty", "No templates yet. Create one or import a file."),
+    ("templates.use", "Use"),
+    ("templates.new", "New template"),
+    ("templates.import", "Import"),
+    ("templates.export", "Export"),
+    ("templates.delete_confirm", "Delete template \"{0}\"?"),
+    ("templates.imported", "Templates added: {0}"),
+    ("templates.read_failed", "Could not read the file: {0}"),
+    ("templates.from_clipboard", "from clipboard"),
+    ("templates.today", "today's date"),
+    ("templates.from_file", "contents of the selected file"),
+    ("templates.pick_file", "Choose file"),
+    ("templates.no_file", "No file selected"),
+    ("templates.file_loaded", "Loaded {0} characters"),
+    ("templates.insert", "Insert"),
+    ("templates.edit_title", "Prompt template"),
+    ("templates.name", "Name"),
+    ("templates.description", "Description"),
+    ("templates.content", "Content"),
+    ("templates.variables_hint", "Write variables as {{name}}. {{clipboard}}, {{date}} and {{selected_file}} are filled in automatically."),
+    ("templates.missing_fields", "Enter a template name and content"),
+    ("templates.name_taken", "A template named \"{0}\" already exists"),
+    ("commands.model", "Switch the chat model"),
+    ("commands.system", "Set the conversation's system prompt (empty clears it)"),
+    ("commands.temp", "Set the reply temperature"),
+    ("commands.save", "Save the conversation under the given name"),
+    ("commands.clear", "Clear the current conversation"),
+    ("commands.search", "Filter the conversation list"),
+    ("commands.ingest", "Index a file or folder into the conversation's collection"),
+    ("commands.export", "Export the saved conversation"),
+    ("commands.model_usage", "<id>"),
+    ("commands.system_usage", "<text>"),
+    ("commands.temp_usage", "<0-2>"),
+    ("commands.save_usage", "[name]"),
+    ("commands.search_usage", "<query>"),
+    ("commands.ingest_usage", "<path>"),
+    ("commands.export_usage", "<md|json|html|jsonl>"),
+    ("commands.unknown", "Unknown command {0}. To send text starting with /, prefix it with a second slash"),
+    ("commands.missing_argument", "Command {0} needs an argument {1}"),
+    ("commands.invalid_temperature", "Invalid temperature \"{0}\" - enter a number from 0 to {1}"),
+    ("commands.unknown_format", "Unknown export format \"{0}\""),
+    ("commands.unknown_model", "Model {0} is not available in LM Studio"),
+    ("commands.model_set", "Chat model: {0}"),
+    ("commands.system_set", "Conversation system prompt set"),
+    ("commands.system_cleared", "Conversation system prompt removed"),
+    ("commands.temperature_set", "Temperature: {0}"),
+    ("commands.temperature_chip", "🌡 {0}"),
+    ("commands.nothing_to_save", "The conversation is empty"),
+    ("commands.saved", "Saved conversation \"{0}\""),
+    ("commands.export_unsaved", "Save the conversation with /save first"),
+    ("commands.ingesting", "Indexing {0} into collection {1}..."),
+    ("commands.no_documents", "No supported documents found"),
+    ("inspector.title", "Request inspector"),
+    ("inspector.log_directory", "The application log is written to the {0} folder"),
+    ("inspector.clear", "Clear"),
+    ("inspector.empty", "No requests have been sent to LM Studio yet"),
+    ("inspector.select_hint", "Select a request from the list to see its details"),
+    ("inspector.no_response", "no response"),
+    ("inspector.duration", "{0} ms"),
+    ("inspector.tokens", "tokens: {0} → {1}"),
+    ("inspector.copy_curl", "Copy as curl"),
+    ("inspector.copy_response", "Copy response"),
+    ("inspector.error", "Error: {0}"),
+    ("inspector.request_headers", "Request headers"),
+    ("inspector.request_body", "Request body"),
+    ("inspector.response_headers", "Response headers"),
+    ("inspector.response_body", "Response"),
+    ("inspector.no_body", "(none)"),
+    ("message.delete_confirm", "Delete this message from the conversation?"),
+    ("knowledge.sync_summary", "New: {0}, modified: {1}, removed: {2}, unchanged: {3}, errors: {4}"),
+    ("knowledge.page", "p. {0}"),
+    ("import.read_failed", "Could not read {0}: {1}"),
+    ("import.invalid_json", "{0} is not a valid JSON file: {1}"),
+    ("import.unknown_format", "Unknown export format: {0}"),
+    ("import.branch", "{0} (branch {1})"),
+    ("export.user", "User"),
+    ("export.assistant", "Assistant"),
+    ("export.conversations", "Conversations"),
+    ("export.file_name", "conversations"),
+    ("settings.log_bodies", "Write request and response bodies to the log"),
+    ("settings.log_bodies_hint", "Bodies contain prompts, document excerpts and memories; they are truncated in the log"),
+    ("documents.unsupported_type", "Unsupported file type: .{0}"),
+    ("documents.read_failed", "Could not read the file: {0}"),
+    ("documents.open_failed", "Could not open the file: {0}"),
+    ("documents.invalid_docx", "Invalid DOCX file: {0}"),
+    ("documents.docx_read_failed", "Could not read DOCX: {0}"),
+    ("documents.docx_parse_failed", "DOCX parsing error: {0}"),
+    ("documents.invalid_epub", "Invalid EPUB file: {0}"),
+    ("documents.epub_no_opf", "The EPUB has no OPF file"),
+    ("documents.epub_missing_entry", "The EPUB has no {0} file: {1}"),
+    ("documents.entry_read_failed", "Could not read {0}: {1}"),
+    ("documents.epub_parse_failed", "EPUB parsing error: {0}"),
+    ("documents.pdf_read_failed", "Could not read PDF: {0}"),
+    ("knowledge.upsert_failed", "Could not store chunks in Qdrant: {0}"),
+    ("knowledge.delete_points_failed", "Could not delete chunks from Qdrant: {0}"),
+    ("knowledge.list_failed", "Could not list collections: {0}"),
+    ("knowledge.collection_exists", "Collection {0} already exists"),
+    ("knowledge.delete_failed", "Could not delete collection {0}: {1}"),
+    ("knowledge.rename_incomplete", "Moved {0} of {1} chunks of collection {2}"),
+    ("knowledge.read_failed", "Error reading collection {0}: {1}"),
+    ("knowledge.point_without_vector", "A chunk of collection {0} has no vector to copy"),
+    ("knowledge.point_without_id", "A chunk of collection {0} has no id"),
+    ("knowledge.move_failed", "Could not move chunks: {0}"),
+    ("knowledge.no_info", "No information about collection {0}"),
+    ("knowledge.info_failed", "Could not get collection information: {0}"),
+    ("knowledge.search_failed", "Qdrant search error: {0}"),
+    ("knowledge.connect_failed", "Could not connect to Qdrant: {0}"),
+    ("knowledge.create_failed", "Could not create collection {0}: {1}"),
+    ("knowledge.embedding_failed", "LM Studio embedding error: {0}"),
+    ("knowledge.file_read_failed", "Could not read {0}: {1}"),
+    ("knowledge.index_dir_failed", "Could not create the index directory: {0}"),
+    ("knowledge.index_save_failed", "Could not save the keyword index: {0}"),
+    ("memory.extract_failed", "Fact extraction error: {0}"),
+    ("memory.read_failed", "Error reading memory: {0}"),
+    ("memory.delete_failed", "Could not delete the memory: {0}"),
+    ("memory.save_failed", "Could not save the memory: {0}"),
+    ("memory.search_failed", "Memory search error: {0}"),
+    ("memory.no_embedding", "LM Studio returned no embedding"),
+    ("memory.create_failed", "Could not create the memory collection: {0}"),
+    ("templates.export_failed", "Could not save templates: {0}"),
+    ("templates.invalid_json", "The file is not valid JSON: {0}"),
+    ("templates.no_templates", "The file contains no templates: {0}"),
+    ("chat.title_failed", "Could not generate a title: {0}"),
+    ("chat.title_empty", "The model returned no title"),
+    ("lm_studio.request_failed", "Could not prepare the request: {0}"),
+    ("lm_studio.invalid_response", "Invalid LM Studio response: {0}"),
+    ("lm_studio.no_model_list", "Invalid LM Studio response: no model list"),
+    ("lm_studio.stream_interrupted", "The response stream was interrupted: {0}"),
+    ("export.conversation", "conversation"),
+    ("settings.api_start_failed", "Could not start the API server on {0}: {1}"),
+    ("settings.api_runtime_failed", "Could not start the async runtime: {0}"),
+    ("save.name_label", "Conversation name:"),
+    ("save.name_placeholder", "Enter a name..."),
+    ("save.suggest_title", "Suggest a title"),
+    ("collections.title", "Knowledge collections"),
+    ("collections.new_name", "New collection name..."),
+    ("collections.embedding_model", "Embedding model"),
+    ("collections.create", "Create"),
+    ("collections.hint", "Checked collections are searched in the current conversation"),
+    ("collections.rename_placeholder", "New name..."),
+    ("collections.details", "Details"),
+    ("collections.delete_confirm", "Delete collection “{0}” together with all indexed documents?"),
+    ("collections.delete", "Delete collection"),
+    ("collections.loading", "Loading collection {0}..."),
+    ("collections.name", "Collection: {0}"),
+    ("collections.stats", "Documents: {0}   Chunks: {1}   Embedding model: {2}   Vector size: {3}"),
+    ("collections.hybrid", "Hybrid search (BM25 + vectors)"),
+    ("collections.rerank", "Rerank results with the chat model"),
+    ("collections.add_documents", "+ Add documents to collection"),
+    ("collections.chunks", "{0} chunks"),
+    ("collections.moving", "Moving collection {0}..."),
+    ("import.title", "Import conversations ({0} of {1} selected)"),
+    ("import.selected", "Import selected"),
+    ("import.details", "{0} · {1} messages · {2}"),
+    ("import.duplicate", " · already saved"),
+    ("import.nothing_found", "No conversations found to import"),
+    ("import.done", "Conversations imported: {0}"),
+    ("export.done", "Exported to {0}"),
+    ("export.write_failed", "Failed to write file: {0}"),
+    ("common.cancel", "Cancel"),
+    ("common.save", "Save"),
+    ("common.close", "Close"),
+    ("common.delete", "Delete"),
+    ("common.edit", "Edit"),
+    ("common.undo", "Undo"),
+];
@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+use chrono::{Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+mod en;
+mod pl;
+
+// Język interfejsu
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    Polish,
+    English,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::Polish, Language::English];
+
+    /// Nazwa języka w nim samym, do listy wyboru
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Language::Polish => "Polski",
+            Language::English => "English",
+        }
+    }
+
+    /// Kod języka, np. do atrybutu `lang` w eksporcie HTML
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::Polish => "pl",
+            Language::English => "en",
+        }
+    }
+
+    /// Język z ustawień regionalnych systemu (LC_ALL, LC_MESSAGES, LANG)
+    pub fn from_system() -> Language {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default();
+
+        if locale.is_empty() || locale.starts_with("pl") {
+            Language::Polish
+        } else {
+            Language::English
+        }
+    }
+
+    fn catalog(self) -> &'static HashMap<&'static str, &'static str> {
+        static POLISH: OnceLock<HashMap<&str, &str>> = OnceLock::new();
+        static ENGLISH: OnceLock<HashMap<&str, &str>> = OnceLock::new();
+
+        match self {
+            Language::Polish => POLISH.get_or_init(|| pl::MESSAGES.iter().copied().collect()),
+            Language::English => ENGLISH.get_or_init(|| en::MESSAGES.iter().copied().collect()),
+        }
+    }
+}
+
+impl Default for Language {
+    fn default() -> Self {
+        Language::from_system()
+    }
+}
+
+// Bieżący język jest globalny, żeby funkcje budujące widok nie musiały go przekazywać
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_language(language: Language) {
+    CURRENT.store(language as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Language::English,
+        _ => Language::Polish,
+    }
+}
+
+/// Tekst dla klucza w bieżącym języku; brakujące tłumaczenie zastępuje polskie, a potem sam klucz
+pub fn t(key: &'static str) -> &'static str {
+    language()
+        .catalog()
+        .get(key)
+        .or_else(|| Language::Polish.catalog().get(key))
+        .copied()
+        .unwrap_or(key)
+}
+
+/// Jak `t`, z podstawieniem argumentów w miejsca `{0}`, `{1}`, ...
+pub fn tf(key: &'static str, args: &[&dyn Display]) -> String {
+    args.iter()
+        .enumerate()
+        .fold(t(key).to_string(), |text, (index, arg)| {
+            text.replace(&format!("{{{}}}", index), &arg.to_string())
+        })
+}
+
+// Formaty, w jakich zapisywane są znaczniki czasu wiadomości
+const STORED_FORMATS: [&str; 2] = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"];
+
+/// Znacznik czasu wiadomości w formacie bieżącego języka; dzisiejsze pokazują samą godzinę.
+/// Starsze wpisy z samą godziną zostają bez zmian.
+pub fn format_timestamp(stored: &str) -> String {
+    let Some(time) = STORED_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(stored, format).ok())
+    else {
+        return stored.to_string();
+    };
+
+    let today = time.date() == Local::now().date_naive();
+    let format = match (language(), today) {
+        (Language::Polish, true) => "%H:%M",
+        (Language::Polish, false) => "%-d.%m.%Y %H:%M",
+        (Language::English, true) => "%-I:%M %p",
+        (Language::English, false) => "%b %-d, %Y %-I:%M %p",
+    };
+    time.format(format).to_string()
+}
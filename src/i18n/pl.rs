@@ -0,0 +1,268 @@
+// Polskie teksty interfejsu; uzupełniają brakujące tłumaczenia w innych językach
+pub const MESSAGES: &[(&str, &str)] = &[
+    ("app.title", "Chat z LM Studio"),
+    ("conversation.default_name", "Nowa rozmowa"),
+    ("sidebar.conversations", "Rozmowy"),
+    ("sidebar.new_conversation", "+ Nowa rozmowa"),
+    ("sidebar.pinned", "📌 Przypięte"),
+    ("sidebar.unfiled", "Bez folderu"),
+    ("sidebar.unfiled_selected", "✓ Bez folderu"),
+    ("sidebar.new_folder", "Nowy folder..."),
+    ("sidebar.import", "Importuj rozmowy"),
+    ("sidebar.search", "Szukaj rozmów... (Ctrl+F)"),
+    ("sidebar.all_tags", "Wszystkie"),
+    ("sidebar.rename_placeholder", "Nazwa rozmowy..."),
+    ("sidebar.rename", "Zmień nazwę"),
+    ("sidebar.pin", "Przypnij"),
+    ("sidebar.unpin", "Odepnij"),
+    ("sidebar.new_tag", "Nowy tag..."),
+    ("sidebar.folder", "Folder"),
+    ("sidebar.tags", "Tagi"),
+    ("sidebar.export_conversation", "Eksportuj rozmowę"),
+    ("sidebar.export_all", "Eksportuj wszystkie"),
+    ("knowledge.title", "Baza wiedzy"),
+    ("knowledge.use_in_answers", "Używaj w odpowiedziach"),
+    ("knowledge.add_documents", "+ Dodaj dokumenty"),
+    ("knowledge.manage_collections", "Zarządzaj kolekcjami"),
+    ("knowledge.watch_collection", "Kolekcja:"),
+    ("knowledge.watch_folder", "+ Obserwuj folder"),
+    ("knowledge.indexing_changes", "⏳ Indeksowanie zmian..."),
+    ("knowledge.documents_filter", "Dokumenty"),
+    ("knowledge.indexing", "Indeksowanie dokumentów..."),
+    ("knowledge.indexed", "Zaindeksowano {0} plików ({1} fragmentów)"),
+    ("knowledge.synced", "Zsynchronizowano {0} – {1}"),
+    ("memory.title", "Pamięć"),
+    ("memory.remember_facts", "Zapamiętuj fakty z rozmów"),
+    ("memory.browse", "Przeglądaj pamięć"),
+    ("memory.panel_title", "Pamięć długoterminowa"),
+    ("memory.empty", "Brak zapamiętanych faktów"),
+    ("memory.fact_placeholder", "Treść faktu..."),
+    ("memory.forget", "Zapomnij"),
+    ("memory.remembered", "Zapamiętano faktów: {0}"),
+    ("models.button", "Modele"),
+    ("models.title", "Modele LM Studio"),
+    ("models.chat_model", "Model czatu: {0}"),
+    ("models.loaded", "załadowany"),
+    ("models.refresh", "Odśwież"),
+    ("models.reload", "Przeładuj model"),
+    ("models.fetching", "Pobieranie listy modeli..."),
+    ("models.reloading", "Przeładowywanie {0}..."),
+    ("models.reload_failed", "Nie udało się przeładować modelu: {0}"),
+    ("models.reloaded", "Model przeładowany"),
+    ("settings.button", "⚙ Ustawienia"),
+    ("settings.title", "Ustawienia"),
+    ("settings.language", "Język"),
+    ("settings.theme", "Motyw"),
+    ("settings.api_title", "Lokalne API"),
+    ("settings.api_enabled", "Udostępniaj API na localhost"),
+    ("settings.port", "Port:"),
+    ("settings.port_hint", "Nowy port obowiązuje po ponownym włączeniu serwera"),
+    ("settings.api_running", "Serwer API: http://{0}"),
+    ("theme.system", "Systemowy"),
+    ("theme.light", "Jasny"),
+    ("theme.dark", "Ciemny"),
+    ("theme.high_contrast", "Wysoki kontrast"),
+    ("trash.title", "Kosz"),
+    ("trash.retention", "Usuwaj na zawsze po (dni, 0 - nigdy):"),
+    ("trash.empty_button", "Opróżnij kosz"),
+    ("trash.empty_confirm", "Usunąć na zawsze wszystkie rozmowy z kosza?"),
+    ("trash.is_empty", "Kosz jest pusty"),
+    ("trash.deleted", "Usunięto {0} · {1} wiadomości"),
+    ("trash.restore", "Przywróć"),
+    ("trash.purge", "Usuń na zawsze"),
+    ("trash.purge_confirm", "Usunąć na zawsze rozmowę „{0}”?"),
+    ("trash.moved", "Przeniesiono „{0}” do kosza"),
+    ("chat.regenerate_title", "Wygeneruj tytuł ponownie"),
+    ("chat.save", "Zapisz rozmowę"),
+    ("chat.clear", "Wyczyść czat"),
+    ("chat.attach", "Załącz plik"),
+    ("chat.stop", "Zatrzymaj"),
+    ("chat.send", "Wyślij"),
+    ("chat.jump_to_latest", "↓ Przejdź do najnowszych"),
+    ("chat.error", "Błąd komunikacji z LM Studio: {0}"),
+    ("message.copy", "Kopiuj"),
+    ("message.copy_text", "Kopiuj tekst"),
+    ("message.quote", "Cytuj"),
+    ("message.pin", "Przypnij"),
+    ("message.unpin", "Odepnij"),
+    ("chat.thinking", "Model pisze"),
+    ("chat.elapsed", "{0} s od wysłania"),
+    ("stats.speed", "{0} tok/s"),
+    ("stats.tokens", "{0} tok. pytania, {1} tok. odpowiedzi"),
+    ("stats.first_token", "pierwszy token po {0} s"),
+    ("stats.duration", "łącznie {0} s"),
+    ("compare.button", "⚖ Porównaj"),
+    ("compare.tooltip", "Wyślij pytanie do kilku modeli i wybierz najlepszą odpowiedź"),
+    ("compare.models", "Porównywane modele:"),
+    ("compare.select_hint", "Zaznacz co najmniej dwa modele"),
+    ("compare.sequential", "Po kolei (w pamięci mieści się jeden model)"),
+    ("compare.keep", "Zachowaj tę odpowiedź"),
+    ("compare.stopped", "Zatrzymano"),
+    ("templates.tooltip", "Szablony poleceń"),
+    ("templates.title", "Szablony poleceń"),
+    ("templates.empty", "Brak szablonów. Utwórz nowy albo zaimportuj plik."),
+    ("templates.use", "Użyj"),
+    ("templates.new", "Nowy szablon"),
+    ("templates.import", "Importuj"),
+    ("templates.export", "Eksportuj"),
+    ("templates.delete_confirm", "Usunąć szablon \"{0}\"?"),
+    ("templates.imported", "Dodano szablonów: {0}"),
+    ("templates.read_failed", "Nie udało się odczytać pliku: {0}"),
+    ("templates.from_clipboard", "ze schowka"),
+    ("templates.today", "dzisiejsza data"),
+    ("templates.from_file", "treść wybranego pliku"),
+    ("templates.pick_file", "Wybierz plik"),
+    ("templates.no_file", "Nie wybrano pliku"),
+    ("templates.file_loaded", "Wczytano {0} znaków"),
+    ("templates.insert", "Wstaw"),
+    ("templates.edit_title", "Szablon polecenia"),
+    ("templates.name", "Nazwa"),
+    ("templates.description", "Opis"),
+    ("templates.content", "Treść"),
+    ("templates.variables_hint", "Zmienne wpisz jako {{nazwa}}. Aplikacja sama wypełnia {{clipboard}}, {{date}} i {{selected_file}}."),
+    ("templates.missing_fields", "Podaj nazwę i treść szablonu"),
+    ("templates.name_taken", "Szablon o nazwie \"{0}\" już istnieje"),
+    ("commands.model", "Zmienia model czatu"),
+    ("commands.system", "Ustawia instrukcję systemową rozmowy (bez tekstu usuwa)"),
+    ("commands.temp", "Ustawia temperaturę odpowiedzi"),
+    ("commands.save", "Zapisuje rozmowę pod podaną nazwą"),
+    ("commands.clear", "Czyści bieżącą rozmowę"),
+    ("commands.search", "Filtruje listę rozmów"),
+    ("commands.ingest", "Indeksuje plik lub folder w kolekcji rozmowy"),
+    ("commands.export", "Eksportuje zapisaną rozmowę"),
+    ("commands.model_usage", "<id>"),
+    ("commands.system_usage", "<tekst>"),
+    ("commands.temp_usage", "<0-2>"),
+    ("commands.save_usage", "[nazwa]"),
+    ("commands.search_usage", "<fraza>"),
+    ("commands.ingest_usage", "<ścieżka>"),
+    ("commands.export_usage", "<md|json|html|jsonl>"),
+    ("commands.unknown", "Nieznane polecenie {0}. Aby wysłać tekst zaczynający się od /, poprzedź go drugim ukośnikiem"),
+    ("commands.missing_argument", "Polecenie {0} wymaga argumentu {1}"),
+    ("commands.invalid_temperature", "Niepoprawna temperatura \"{0}\" - podaj liczbę od 0 do {1}"),
+    ("commands.unknown_format", "Nieznany format eksportu \"{0}\""),
+    ("commands.unknown_model", "Model {0} nie jest dostępny w LM Studio"),
+    ("commands.model_set", "Model czatu: {0}"),
+    ("commands.system_set", "Ustawiono instrukcję systemową rozmowy"),
+    ("commands.system_cleared", "Usunięto instrukcję systemową rozmowy"),
+    ("commands.temperature_set", "Temperatura: {0}"),
+    ("commands.temperature_chip", "🌡 {0}"),
+    ("commands.nothing_to_save", "Rozmowa jest pusta"),
+    ("commands.saved", "Zapisano rozmowę \"{0}\""),
+    ("commands.export_unsaved", "Najpierw zapisz rozmowę poleceniem /save"),
+    ("commands.ingesting", "Indeksowanie {0} w kolekcji {1}..."),
+    ("commands.no_documents", "Nie znaleziono obsługiwanych dokumentów"),
+    ("inspector.title", "Inspektor żądań"),
+    ("inspector.log_directory", "Log aplikacji zapisywany jest w folderze {0}"),
+    ("inspector.clear", "Wyczyść"),
+    ("inspector.empty", "Nie wysłano jeszcze żadnych żądań do LM Studio"),
+    ("inspector.select_hint", "Wybierz żądanie z listy, aby zobaczyć szczegóły"),
+    ("inspector.no_response", "brak odpowiedzi"),
+    ("inspector.duration", "{0} ms"),
+    ("inspector.tokens", "tokeny: {0} → {1}"),
+    ("inspector.copy_curl", "Kopiuj jako curl"),
+    ("inspector.copy_response", "Kopiuj odpowiedź"),
+    ("inspector.error", "Błąd: {0}"),
+    ("inspector.request_headers", "Nagłówki żądania"),
+    ("inspector.request_body", "Treść żądania"),
+    ("inspector.response_headers", "Nagłówki odpowiedzi"),
+    ("inspector.response_body", "Odpowiedź"),
+    ("inspector.no_body", "(brak)"),
+    ("message.delete_confirm", "Usunąć tę wiadomość z rozmowy?"),
+    ("knowledge.sync_summary", "Nowe: {0}, zmienione: {1}, usunięte: {2}, bez zmian: {3}, błędy: {4}"),
+    ("knowledge.page", "str. {0}"),
+    ("import.read_failed", "Nie udało się odczytać {0}: {1}"),
+    ("import.invalid_json", "{0} nie jest poprawnym plikiem JSON: {1}"),
+    ("import.unknown_format", "Nieznany format eksportu: {0}"),
+    ("import.branch", "{0} (gałąź {1})"),
+    ("export.user", "Użytkownik"),
+    ("export.assistant", "Asystent"),
+    ("export.conversations", "Rozmowy"),
+    ("export.file_name", "rozmowy"),
+    ("settings.log_bodies", "Zapisuj w logu treść żądań i odpowiedzi"),
+    ("settings.log_bodies_hint", "Treść zawiera prompty, fragmenty dokumentów i wspomnienia; w logu jest przycinana"),
+    ("documents.unsupported_type", "Nieobsługiwany typ pliku: .{0}"),
+    ("documents.read_failed", "Nie udało się odczytać pliku: {0}"),
+    ("documents.open_failed", "Nie udało się otworzyć pliku: {0}"),
+    ("documents.invalid_docx", "Niepoprawny plik DOCX: {0}"),
+    ("documents.docx_read_failed", "Nie udało się odczytać DOCX: {0}"),
+    ("documents.docx_parse_failed", "Błąd parsowania DOCX: {0}"),
+    ("documents.invalid_epub", "Niepoprawny plik EPUB: {0}"),
+    ("documents.epub_no_opf", "Brak pliku OPF w EPUB"),
+    ("documents.epub_missing_entry", "Brak pliku {0} w EPUB: {1}"),
+    ("documents.entry_read_failed", "Nie udało się odczytać {0}: {1}"),
+    ("documents.epub_parse_failed", "Błąd parsowania EPUB: {0}"),
+    ("documents.pdf_read_failed", "Nie udało się odczytać PDF: {0}"),
+    ("knowledge.upsert_failed", "Nie udało się zapisać fragmentów w Qdrant: {0}"),
+    ("knowledge.delete_points_failed", "Nie udało się usunąć fragmentów z Qdrant: {0}"),
+    ("knowledge.list_failed", "Nie udało się pobrać listy kolekcji: {0}"),
+    ("knowledge.collection_exists", "Kolekcja {0} już istnieje"),
+    ("knowledge.delete_failed", "Nie udało się usunąć kolekcji {0}: {1}"),
+    ("knowledge.rename_incomplete", "Przeniesiono {0} z {1} fragmentów kolekcji {2}"),
+    ("knowledge.read_failed", "Błąd odczytu kolekcji {0}: {1}"),
+    ("knowledge.point_without_vector", "Fragment kolekcji {0} nie ma wektora do skopiowania"),
+    ("knowledge.point_without_id", "Fragment kolekcji {0} nie ma identyfikatora"),
+    ("knowledge.move_failed", "Nie udało się przenieść fragmentów: {0}"),
+    ("knowledge.no_info", "Brak informacji o kolekcji {0}"),
+    ("knowledge.info_failed", "Nie udało się pobrać informacji o kolekcji: {0}"),
+    ("knowledge.search_failed", "Błąd wyszukiwania w Qdrant: {0}"),
+    ("knowledge.connect_failed", "Nie udało się połączyć z Qdrant: {0}"),
+    ("knowledge.create_failed", "Nie udało się utworzyć kolekcji {0}: {1}"),
+    ("knowledge.embedding_failed", "Błąd generowania osadzeń w LM Studio: {0}"),
+    ("knowledge.file_read_failed", "Nie udało się odczytać {0}: {1}"),
+    ("knowledge.index_dir_failed", "Nie udało się utworzyć katalogu indeksu: {0}"),
+    ("knowledge.index_save_failed", "Nie udało się zapisać indeksu słów kluczowych: {0}"),
+    ("memory.extract_failed", "Błąd wyodrębniania faktów: {0}"),
+    ("memory.read_failed", "Błąd odczytu pamięci: {0}"),
+    ("memory.delete_failed", "Nie udało się usunąć wspomnienia: {0}"),
+    ("memory.save_failed", "Nie udało się zapisać wspomnienia: {0}"),
+    ("memory.search_failed", "Błąd wyszukiwania w pamięci: {0}"),
+    ("memory.no_embedding", "LM Studio nie zwróciło osadzenia"),
+    ("memory.create_failed", "Nie udało się utworzyć kolekcji pamięci: {0}"),
+    ("templates.export_failed", "Nie udało się zapisać szablonów: {0}"),
+    ("templates.invalid_json", "Plik nie jest poprawnym plikiem JSON: {0}"),
+    ("templates.no_templates", "Plik nie zawiera szablonów: {0}"),
+    ("chat.title_failed", "Nie udało się wygenerować tytułu: {0}"),
+    ("chat.title_empty", "Model nie zwrócił tytułu"),
+    ("lm_studio.request_failed", "Nie udało się przygotować żądania: {0}"),
+    ("lm_studio.invalid_response", "Niepoprawna odpowiedź LM Studio: {0}"),
+    ("lm_studio.no_model_list", "Niepoprawna odpowiedź LM Studio: brak listy modeli"),
+    ("lm_studio.stream_interrupted", "Przerwano strumień odpowiedzi: {0}"),
+    ("export.conversation", "rozmowa"),
+    ("settings.api_start_failed", "Nie udało się uruchomić serwera API na {0}: {1}"),
+    ("settings.api_runtime_failed", "Nie udało się uruchomić środowiska asynchronicznego: {0}"),
+    ("save.name_label", "Nazwa rozmowy:"),
+    ("save.name_placeholder", "Wpisz nazwę..."),
+    ("save.suggest_title", "Zaproponuj tytuł"),
+    ("collections.title", "Kolekcje wiedzy"),
+    ("collections.new_name", "Nazwa nowej kolekcji..."),
+    ("collections.embedding_model", "Model osadzeń"),
+    ("collections.create", "Utwórz"),
+    ("collections.hint", "Zaznaczone kolekcje są przeszukiwane w bieżącej rozmowie"),
+    ("collections.rename_placeholder", "Nowa nazwa..."),
+    ("collections.details", "Szczegóły"),
+    ("collections.delete_confirm", "Usunąć kolekcję „{0}” razem ze wszystkimi zaindeksowanymi dokumentami?"),
+    ("collections.delete", "Usuń kolekcję"),
+    ("collections.loading", "Wczytywanie kolekcji {0}..."),
+    ("collections.name", "Kolekcja: {0}"),
+    ("collections.stats", "Dokumenty: {0}   Fragmenty: {1}   Model osadzeń: {2}   Rozmiar wektora: {3}"),
+    ("collections.hybrid", "Wyszukiwanie hybrydowe (BM25 + wektory)"),
+    ("collections.rerank", "Ponowna ocena trafności przez model czatu"),
+    ("collections.add_documents", "+ Dodaj dokumenty do kolekcji"),
+    ("collections.chunks", "{0} fragm."),
+    ("collections.moving", "Przenoszenie kolekcji {0}..."),
+    ("import.title", "Import rozmów ({0} z {1} zaznaczonych)"),
+    ("import.selected", "Importuj zaznaczone"),
+    ("import.details", "{0} · {1} wiadomości · {2}"),
+    ("import.duplicate", " · już zapisana"),
+    ("import.nothing_found", "Nie znaleziono rozmów do zaimportowania"),
+    ("import.done", "Zaimportowano rozmów: {0}"),
+    ("export.done", "Wyeksportowano do {0}"),
+    ("export.write_failed", "Nie udało się zapisać pliku: {0}"),
+    ("common.cancel", "Anuluj"),
+    ("common.save", "Zapisz"),
+    ("common.close", "Zamknij"),
+    ("common.delete", "Usuń"),
+    ("common.edit", "Edytuj"),
+    ("common.undo", "Cofnij"),
+];
@@ -0,0 +1,536 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Local};
+use serde_json::Value;
+
+use crate::conversation::{self, default_collections, ChatMessage, SavedConversation};
+use crate::i18n::tf;
+
+// Rozpoznane formaty eksportu z innych aplikacji czatu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportSource {
+    ChatGpt,
+    OpenWebUi,
+    LmStudio,
+}
+
+impl ImportSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            ImportSource::ChatGpt => "ChatGPT",
+            ImportSource::OpenWebUi => "Open WebUI",
+            ImportSource::LmStudio => "LM Studio",
+        }
+    }
+}
+
+// Rozmowa odczytana z eksportu; boczne gałęzie drzewa nie są domyślnie zaznaczane
+#[derive(Debug, Clone)]
+pub struct ImportedConversation {
+    pub conversation: SavedConversation,
+    pub side_branch: bool,
+}
+
+impl From<SavedConversation> for ImportedConversation {
+    fn from(conversation: SavedConversation) -> Self {
+        ImportedConversation {
+            conversation,
+            side_branch: false,
+        }
+    }
+}
+
+// Rozmowa odczytana z pliku, czekająca na zatwierdzenie w podglądzie
+#[derive(Debug, Clone)]
+pub struct ImportCandidate {
+    pub conversation: SavedConversation,
+    pub source: ImportSource,
+    // Ta sama treść jest już zapisana albo pojawiła się wcześniej w imporcie
+    pub duplicate: bool,
+    pub selected: bool,
+}
+
+// Węzeł drzewa wiadomości; edycje i ponowne generowanie tworzą w nim gałęzie
+struct Node {
+    parent: Option<String>,
+    children: Vec<String>,
+    message: Option<ChatMessage>,
+}
+
+/// Odczytuje plik eksportu, rozpoznając jego format po strukturze
+pub fn read_file(path: &Path) -> Result<(ImportSource, Vec<ImportedConversation>), String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| tf("import.read_failed", &[&path.display(), &e]))?;
+    let value: Value = serde_json::from_str(&content)
+        .map_err(|e| tf("import.invalid_json", &[&path.display(), &e]))?;
+
+    let fallback_name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().trim_end_matches(".conversation").to_string())
+        .unwrap_or_default();
+
+    let source = detect(&value)
+        .ok_or_else(|| tf("import.unknown_format", &[&path.display()]))?;
+    let conversations = match source {
+        ImportSource::ChatGpt => items(&value).iter().flat_map(|item| parse_chatgpt(item)).collect(),
+        ImportSource::OpenWebUi => items(&value).iter().flat_map(|item| parse_open_webui(item)).collect(),
+        ImportSource::LmStudio => items(&value)
+            .iter()
+            .filter_map(|item| parse_lm_studio(item, &fallback_name))
+            .map(ImportedConversation::from)
+            .collect(),
+    };
+
+    Ok((source, conversations))
+}
+
+/// Oznacza duplikaty względem zapisanych rozmów i między sobą; duplikaty nie są domyślnie zaznaczone
+pub fn preview(
+    imported: Vec<(ImportSource, ImportedConversation)>,
+    existing: &[SavedConversation],
+) -> Vec<ImportCandidate> {
+    let mut seen: HashSet<Vec<(bool, String)>> = existing.iter().map(fingerprint).collect();
+
+    imported
+        .into_iter()
+        .map(|(source, imported)| {
+            let duplicate = !seen.insert(fingerprint(&imported.conversation));
+            ImportCandidate {
+                conversation: imported.conversation,
+                source,
+                duplicate,
+                // Domyślnie tylko aktywna gałąź; pozostałe użytkownik zaznacza sam
+                selected: !duplicate && !imported.side_branch,
+            }
+        })
+        .collect()
+}
+
+/// Dopisuje zaznaczone rozmowy, nadając unikalne nazwy; zwraca liczbę dodanych
+pub fn merge(existing: &mut Vec<SavedConversation>, candidates: Vec<ImportCandidate>) -> usize {
+    let mut added = 0;
+
+    for candidate in candidates.into_iter().filter(|c| c.selected) {
+        let mut conversation = candidate.conversation;
+        conversation.name = conversation::unique_name(existing, &conversation.name, "");
+        existing.push(conversation);
+        added += 1;
+    }
+
+    added
+}
+
+fn detect(value: &Value) -> Option<ImportSource> {
+    let first = items(value).into_iter().next()?;
+
+    if first.get("mapping").is_some() {
+        Some(ImportSource::ChatGpt)
+    } else if first.get("chat").is_some() {
+        Some(ImportSource::OpenWebUi)
+    } else if first.get("messages").is_some() {
+        Some(ImportSource::LmStudio)
+    } else {
+        None
+    }
+}
+
+// Eksport bywa tablicą rozmów albo pojedynczym obiektem
+fn items(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::Array(items) => items.iter().collect(),
+        Value::Object(_) => vec![value],
+        _ => Vec::new(),
+    }
+}
+
+// conversations.json z ChatGPT: drzewo `mapping`, aktywna gałąź kończy się w `current_node`
+fn parse_chatgpt(item: &Value) -> Vec<ImportedConversation> {
+    let title = text_field(item, "title").unwrap_or_else(|| "ChatGPT".to_string());
+    let Some(mapping) = item.get("mapping").and_then(|m| m.as_object()) else {
+        return Vec::new();
+    };
+
+    let nodes = mapping
+        .iter()
+        .map(|(id, node)| {
+            let message = node.get("message").and_then(|message| {
+                let role = message.pointer("/author/role").and_then(|r| r.as_str())?;
+                let parts = message.pointer("/content/parts").and_then(|p| p.as_array())?;
+                let content: Vec<&str> = parts.iter().filter_map(|part| part.as_str()).collect();
+                chat_message(role, &content.join("\n"), message.get("create_time"))
+            });
+
+            (id.clone(), Node {
+                parent: text_field(node, "parent"),
+                children: string_list(node.get("children")),
+                message,
+            })
+        })
+        .collect();
+
+    branches(&title, nodes, text_field(item, "current_node"))
+}
+
+// Eksport Open WebUI: `chat.history` zawiera drzewo, a `chat.messages` samą aktywną gałąź
+fn parse_open_webui(item: &Value) -> Vec<ImportedConversation> {
+    let chat = &item["chat"];
+    let title = text_field(item, "title")
+        .or_else(|| text_field(chat, "title"))
+        .unwrap_or_else(|| "Open WebUI".to_string());
+
+    if let Some(history) = chat.pointer("/history/messages").and_then(|m| m.as_object()) {
+        let nodes = history
+            .iter()
+            .map(|(id, message)| {
+                (id.clone(), Node {
+                    parent: text_field(message, "parentId"),
+                    children: string_list(message.get("childrenIds")),
+                    message: chat_message(
+                        message["role"].as_str().unwrap_or_default(),
+                        message["content"].as_str().unwrap_or_default(),
+                        message.get("timestamp"),
+                    ),
+                })
+            })
+            .collect();
+        return branches(&title, nodes, chat.pointer("/history/currentId").and_then(|id| id.as_str()).map(String::from));
+    }
+
+    let messages: Vec<ChatMessage> = chat["messages"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|message| {
+            chat_message(
+                message["role"].as_str().unwrap_or_default(),
+                message["content"].as_str().unwrap_or_default(),
+                message.get("timestamp"),
+            )
+        })
+        .collect();
+    conversation(title, messages).into_iter().map(ImportedConversation::from).collect()
+}
+
+// Plik rozmowy LM Studio: każda wiadomość ma wersje, wybraną wskazuje `currentlySelected`
+fn parse_lm_studio(item: &Value, fallback_name: &str) -> Option<SavedConversation> {
+    let title = text_field(item, "name").unwrap_or_else(|| fallback_name.to_string());
+    let created_at = item.get("createdAt");
+
+    let messages = item["messages"]
+        .as_array()?
+        .iter()
+        .filter_map(|message| {
+            let selected = match message.get("versions").and_then(|v| v.as_array()) {
+                Some(versions) => {
+                    let index = message["currentlySelected"].as_u64().unwrap_or(0) as usize;
+                    versions.get(index).or_else(|| versions.last())?
+                }
+                // Starszy format bez wersji
+                None => message,
+            };
+            let role = selected["role"].as_str().unwrap_or_default();
+            chat_message(role, &lm_studio_text(selected), created_at)
+        })
+        .collect();
+
+    conversation(title, messages)
+}
+
+// Treść wiadomości LM Studio: zwykły tekst, bloki `content` albo kroki odpowiedzi wieloetapowej
+fn lm_studio_text(message: &Value) -> String {
+    fn blocks(content: &Value) -> Vec<String> {
+        match content {
+            Value::String(text) => vec![text.clone()],
+            Value::Array(blocks) => blocks
+                .iter()
+                .filter_map(|block| block.get("text").and_then(|t| t.as_str()).map(String::from))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    let mut parts = blocks(&message["content"]);
+    for step in message["steps"].as_array().into_iter().flatten() {
+        if step["type"].as_str() == Some("contentBlock") {
+            parts.extend(blocks(&step["content"]));
+        }
+    }
+    parts.join("\n")
+}
+
+// Każdy liść drzewa to osobna gałąź; aktywna zachowuje tytuł, pozostałe dostają numer
+fn branches(title: &str, nodes: HashMap<String, Node>, current: Option<String>) -> Vec<ImportedConversation> {
+    let mut leaves: Vec<&String> = nodes
+        .iter()
+        .filter(|(_, node)| node.children.iter().all(|child| !nodes.contains_key(child)))
+        .map(|(id, _)| id)
+        .collect();
+    leaves.sort();
+    if let Some(position) = current.as_ref().and_then(|current| leaves.iter().position(|id| *id == current)) {
+        let main = leaves.remove(position);
+        leaves.insert(0, main);
+    }
+
+    let mut conversations = Vec::new();
+    for leaf in leaves {
+        let mut messages = Vec::new();
+        let mut visited = HashSet::new();
+        let mut cursor = Some(leaf.clone());
+        while let Some(id) = cursor {
+            if !visited.insert(id.clone()) {
+                break;
+            }
+            let Some(node) = nodes.get(&id) else { break };
+            if let Some(message) = &node.message {
+                messages.push(message.clone());
+            }
+            cursor = node.parent.clone();
+        }
+        messages.reverse();
+
+        let name = if conversations.is_empty() {
+            title.to_string()
+        } else {
+            tf("import.branch", &[&title, &(conversations.len() + 1)])
+        };
+        let side_branch = !conversations.is_empty();
+        conversations.extend(conversation(name, messages).map(|conversation| ImportedConversation {
+            conversation,
+            side_branch,
+        }));
+    }
+
+    conversations
+}
+
+fn conversation(name: String, messages: Vec<ChatMessage>) -> Option<SavedConversation> {
+    if messages.is_empty() {
+        return None;
+    }
+    // Datą zmiany jest czas ostatniej wiadomości z eksportu
+    let updated_at = messages.last().map(|m| m.timestamp.clone()).unwrap_or_default();
+    let mut conversation = SavedConversation::new(name, messages, default_collections());
+    if !updated_at.is_empty() {
+        conversation.updated_at = updated_at;
+    }
+    Some(conversation)
+}
+
+// Pomija wiadomości systemowe i narzędziowe oraz puste
+fn chat_message(role: &str, content: &str, timestamp: Option<&Value>) -> Option<ChatMessage> {
+    let is_user = match role {
+        "user" => true,
+        "assistant" => false,
+        _ => return None,
+    };
+    if content.trim().is_empty() {
+        return None;
+    }
+
+    Some(ChatMessage {
+        content: content.to_string(),
+        is_user,
+        timestamp: timestamp.and_then(format_timestamp).unwrap_or_default(),
+        attachments: Vec::new(),
+        pinned: false,
+        stats: None,
+    })
+}
+
+// Znaczniki czasu bywają w sekundach (także ułamkowych) albo w milisekundach
+fn format_timestamp(value: &Value) -> Option<String> {
+    let mut seconds = value.as_f64()?;
+    if seconds > 1e11 {
+        seconds /= 1000.0;
+    }
+    let time = DateTime::from_timestamp(seconds as i64, 0)?;
+    Some(time.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+}
+
+fn text_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(|v| v.as_str()).map(String::from)
+}
+
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|items| items.iter().filter_map(|i| i.as_str().map(String::from)).collect())
+        .unwrap_or_default()
+}
+
+// Rozmowy są duplikatami, gdy mają te same wiadomości, niezależnie od nazwy i czasu
+fn fingerprint(conversation: &SavedConversation) -> Vec<(bool, String)> {
+    conversation
+        .messages
+        .iter()
+        .map(|m| (m.is_user, m.content.trim().to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn texts(conversation: &SavedConversation) -> Vec<(bool, &str)> {
+        conversation.messages.iter().map(|m| (m.is_user, m.content.as_str())).collect()
+    }
+
+    fn texts_to_messages(texts: &[(bool, &str)]) -> Vec<ChatMessage> {
+        texts
+            .iter()
+            .map(|(is_user, content)| chat_message(if *is_user { "user" } else { "assistant" }, content, None).unwrap())
+            .collect()
+    }
+
+    fn chatgpt_node(parent: Option<&str>, children: &[&str], role: &str, text: &str) -> Value {
+        json!({
+            "parent": parent,
+            "children": children,
+            "message": {
+                "author": {"role": role},
+                "content": {"parts": [text]},
+                "create_time": 1_700_000_000.5
+            }
+        })
+    }
+
+    // Pytanie edytowane raz i odpowiedź wygenerowana ponownie: trzy liście, aktywny w środku
+    fn branched_chatgpt() -> Value {
+        json!([{
+            "title": "Podróż",
+            "current_node": "a2",
+            "mapping": {
+                "root": {"parent": null, "children": ["sys"], "message": null},
+                "sys": chatgpt_node(Some("root"), &["q1", "q2"], "system", "Jesteś pomocny"),
+                "q1": chatgpt_node(Some("sys"), &["a1"], "user", "Dokąd w maju?"),
+                "a1": chatgpt_node(Some("q1"), &[], "assistant", "Do Lizbony."),
+                "q2": chatgpt_node(Some("sys"), &["a2", "a3"], "user", "Dokąd w czerwcu?"),
+                "a2": chatgpt_node(Some("q2"), &[], "assistant", "Do Bergen."),
+                "a3": chatgpt_node(Some("q2"), &[], "assistant", "Na Maderę."),
+            }
+        }])
+    }
+
+    #[test]
+    fn chatgpt_mapping_is_split_into_branches() {
+        let value = branched_chatgpt();
+        assert_eq!(detect(&value), Some(ImportSource::ChatGpt));
+
+        let branches = parse_chatgpt(items(&value)[0]);
+
+        assert_eq!(branches.len(), 3);
+        assert_eq!(branches[0].conversation.name, "Podróż");
+        assert!(!branches[0].side_branch);
+        assert_eq!(texts(&branches[0].conversation), vec![(true, "Dokąd w czerwcu?"), (false, "Do Bergen.")]);
+        assert_eq!(texts(&branches[1].conversation), vec![(true, "Dokąd w maju?"), (false, "Do Lizbony.")]);
+        assert_eq!(texts(&branches[2].conversation), vec![(true, "Dokąd w czerwcu?"), (false, "Na Maderę.")]);
+        for branch in &branches[1..] {
+            assert!(branch.side_branch);
+            assert!(branch.conversation.name.starts_with("Podróż ("));
+            assert_ne!(branch.conversation.name, branches[0].conversation.name);
+        }
+        assert!(!branches[0].conversation.messages[0].timestamp.is_empty());
+    }
+
+    #[test]
+    fn preview_selects_only_the_active_branch_and_skips_duplicates() {
+        let value = branched_chatgpt();
+        let imported = parse_chatgpt(items(&value)[0])
+            .into_iter()
+            .map(|conversation| (ImportSource::ChatGpt, conversation))
+            .collect();
+        let existing = vec![SavedConversation::new(
+            "Stara".to_string(),
+            texts_to_messages(&[(true, "Dokąd w maju?"), (false, " Do Lizbony. ")]),
+            default_collections(),
+        )];
+
+        let candidates = preview(imported, &existing);
+
+        let flags: Vec<(bool, bool)> = candidates.iter().map(|c| (c.selected, c.duplicate)).collect();
+        assert_eq!(flags, vec![(true, false), (false, true), (false, false)]);
+
+        let mut saved = vec![SavedConversation::new("Podróż".to_string(), Vec::new(), default_collections())];
+        assert_eq!(merge(&mut saved, candidates), 1);
+        assert_eq!(saved[1].name, "Podróż (2)");
+    }
+
+    #[test]
+    fn open_webui_uses_history_tree_or_flat_messages() {
+        let tree = json!({
+            "title": "Kod",
+            "chat": {
+                "history": {
+                    "currentId": "m3",
+                    "messages": {
+                        "m1": {"parentId": null, "childrenIds": ["m2", "m3"], "role": "user", "content": "Napisz funkcję", "timestamp": 1_700_000_000_000u64},
+                        "m2": {"parentId": "m1", "childrenIds": [], "role": "assistant", "content": "fn a() {}"},
+                        "m3": {"parentId": "m1", "childrenIds": [], "role": "assistant", "content": "fn b() {}"}
+                    }
+                }
+            }
+        });
+        let flat = json!({
+            "chat": {
+                "title": "Płaska",
+                "messages": [
+                    {"role": "user", "content": "Cześć"},
+                    {"role": "assistant", "content": "  "},
+                    {"role": "assistant", "content": "Dzień dobry"}
+                ]
+            }
+        });
+        assert_eq!(detect(&tree), Some(ImportSource::OpenWebUi));
+
+        let branches = parse_open_webui(&tree);
+        assert_eq!(branches.len(), 2);
+        assert_eq!(texts(&branches[0].conversation), vec![(true, "Napisz funkcję"), (false, "fn b() {}")]);
+        assert!(branches[1].side_branch);
+
+        let flat = parse_open_webui(&flat);
+        assert_eq!(flat.len(), 1);
+        assert_eq!(flat[0].conversation.name, "Płaska");
+        assert_eq!(texts(&flat[0].conversation), vec![(true, "Cześć"), (false, "Dzień dobry")]);
+    }
+
+    #[test]
+    fn lm_studio_takes_selected_versions_and_content_blocks() {
+        let value = json!({
+            "createdAt": 1_700_000_000_000u64,
+            "messages": [
+                {"versions": [{"role": "user", "content": [{"type": "text", "text": "Ile to 2+2?"}]}], "currentlySelected": 0},
+                {
+                    "versions": [
+                        {"role": "assistant", "content": "Pięć"},
+                        {"role": "assistant", "steps": [
+                            {"type": "contentBlock", "content": [{"type": "text", "text": "Cztery."}]},
+                            {"type": "debugInfo", "content": "ignorowane"}
+                        ]}
+                    ],
+                    "currentlySelected": 1
+                },
+                {"role": "user", "content": "Dzięki"}
+            ]
+        });
+        assert_eq!(detect(&value), Some(ImportSource::LmStudio));
+
+        let conversation = parse_lm_studio(&value, "rozmowa").unwrap();
+
+        assert_eq!(conversation.name, "rozmowa");
+        assert_eq!(
+            texts(&conversation),
+            vec![(true, "Ile to 2+2?"), (false, "Cztery."), (true, "Dzięki")]
+        );
+        assert!(parse_lm_studio(&json!({"messages": []}), "pusta").is_none());
+    }
+
+    #[test]
+    fn unknown_structure_is_not_detected() {
+        assert_eq!(detect(&json!({"items": []})), None);
+        assert_eq!(detect(&json!([])), None);
+        assert_eq!(detect(&json!("tekst")), None);
+    }
+}
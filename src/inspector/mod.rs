@@ -0,0 +1,181 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use chrono::Local;
+
+// Liczba zapamiętanych żądań; starsze są usuwane
+const RECORD_LIMIT: usize = 200;
+
+// Dłuższe odpowiedzi (np. wektory osadzeń) są przycinane
+const RESPONSE_LIMIT: usize = 50_000;
+
+// W logu treści są krótsze; całe zostają tylko w pamięci inspektora
+const LOG_BODY_LIMIT: usize = 2_000;
+
+// Żądania wszystkich klientów LM Studio w procesie, od najstarszego
+static RECORDS: Mutex<VecDeque<RequestRecord>> = Mutex::new(VecDeque::new());
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+// Treści zawierają prompty, fragmenty dokumentów i wspomnienia, więc do logu trafiają tylko na życzenie
+static LOG_BODIES: AtomicBool = AtomicBool::new(false);
+
+// Żądanie wysłane do LM Studio razem z odpowiedzią, do podglądu w inspektorze
+#[derive(Debug, Clone)]
+pub struct RequestRecord {
+    pub id: u64,
+    pub timestamp: String,
+    pub method: String,
+    pub url: String,
+    pub request_headers: Vec<(String, String)>,
+    pub request_body: Option<String>,
+    // Brak statusu - połączenie się nie udało
+    pub status: Option<u16>,
+    pub response_headers: Vec<(String, String)>,
+    pub response_body: Option<String>,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+    pub prompt_tokens: Option<u64>,
+    pub completion_tokens: Option<u64>,
+}
+
+impl RequestRecord {
+    pub fn new(method: &str, url: &str, request_headers: Vec<(String, String)>, request_body: Option<String>) -> Self {
+        RequestRecord {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            timestamp: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            method: method.to_string(),
+            url: url.to_string(),
+            request_headers,
+            request_body,
+            status: None,
+            response_headers: Vec::new(),
+            response_body: None,
+            error: None,
+            duration_ms: 0,
+            prompt_tokens: None,
+            completion_tokens: None,
+        }
+    }
+
+    /// Zapisuje treść odpowiedzi, przyciętą do `RESPONSE_LIMIT` znaków
+    pub fn set_response_body(&mut self, body: &str) {
+        self.response_body = Some(truncate(body, RESPONSE_LIMIT));
+    }
+
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none() && self.status.is_some_and(|status| (200..300).contains(&status))
+    }
+
+    /// Polecenie curl powtarzające żądanie
+    pub fn to_curl(&self) -> String {
+        let mut parts = vec![format!("curl -X {} {}", self.method, shell_quote(&self.url))];
+        for (name, value) in &self.request_headers {
+            parts.push(format!("-H {}", shell_quote(&format!("{}: {}", name, value))));
+        }
+        if let Some(body) = &self.request_body {
+            parts.push(format!("--data-raw {}", shell_quote(body)));
+        }
+        parts.join(" \\\n  ")
+    }
+}
+
+fn truncate(text: &str, limit: usize) -> String {
+    let length = text.chars().count();
+    if length > limit {
+        let kept: String = text.chars().take(limit).collect();
+        format!("{}\n… (pominięto {} znaków)", kept, length - limit)
+    } else {
+        text.to_string()
+    }
+}
+
+// Apostrofy wewnątrz tekstu zamykają i ponownie otwierają cytowanie
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Włącza zapis (przyciętych) treści żądań i odpowiedzi w logu
+pub fn set_log_bodies(enabled: bool) {
+    LOG_BODIES.store(enabled, Ordering::Relaxed);
+}
+
+/// Dodaje zakończone żądanie do inspektora i do logu
+pub fn record(record: RequestRecord) {
+    let status = record.status.map(|s| s.to_string()).unwrap_or_else(|| "-".to_string());
+    if record.succeeded() {
+        tracing::info!(
+            target: "lm_studio",
+            id = record.id,
+            duration_ms = record.duration_ms,
+            prompt_tokens = record.prompt_tokens,
+            completion_tokens = record.completion_tokens,
+            "{} {} {}",
+            record.method,
+            record.url,
+            status,
+        );
+    } else {
+        tracing::warn!(
+            target: "lm_studio",
+            id = record.id,
+            duration_ms = record.duration_ms,
+            error = record.error.as_deref(),
+            "{} {} {}",
+            record.method,
+            record.url,
+            status,
+        );
+    }
+    if LOG_BODIES.load(Ordering::Relaxed) {
+        tracing::info!(
+            target: "lm_studio",
+            id = record.id,
+            request = record.request_body.as_deref().map(|body| truncate(body, LOG_BODY_LIMIT)),
+            response = record.response_body.as_deref().map(|body| truncate(body, LOG_BODY_LIMIT)),
+            "treść żądania i odpowiedzi",
+        );
+    }
+
+    let mut records = RECORDS.lock().unwrap_or_else(|e| e.into_inner());
+    records.push_back(record);
+    while records.len() > RECORD_LIMIT {
+        records.pop_front();
+    }
+}
+
+/// Zapamiętane żądania, od najnowszego
+pub fn records() -> Vec<RequestRecord> {
+    let records = RECORDS.lock().unwrap_or_else(|e| e.into_inner());
+    records.iter().rev().cloned().collect()
+}
+
+pub fn clear() {
+    RECORDS.lock().unwrap_or_else(|e| e.into_inner()).clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncates_by_characters() {
+        assert_eq!(truncate("zażółć", 6), "zażółć");
+        assert_eq!(truncate("zażółć jaźń", 6), "zażółć\n… (pominięto 5 znaków)");
+    }
+
+    #[test]
+    fn curl_quotes_apostrophes() {
+        let record = RequestRecord::new(
+            "POST",
+            "http://localhost:1234/v1/chat/completions",
+            vec![("Content-Type".to_string(), "application/json".to_string())],
+            Some(r#"{"content":"it's"}"#.to_string()),
+        );
+
+        assert_eq!(
+            record.to_curl(),
+            "curl -X POST 'http://localhost:1234/v1/chat/completions' \\\n  -H 'Content-Type: application/json' \\\n  --data-raw '{\"content\":\"it'\\''s\"}'"
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::ChunkPayload;
+use crate::i18n::tf;
+
+// Katalog z indeksami słów kluczowych (po jednym pliku na kolekcję)
+const INDEX_DIR: &str = "knowledge_index";
+
+// Standardowe parametry BM25
+const K1: f32 = 1.2;
+const B: f32 = 0.75;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedChunk {
+    payload: ChunkPayload,
+    terms: HashMap<String, u32>,
+    length: u32,
+}
+
+// Indeks BM25 przechowywany obok wektorów w Qdrant
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Bm25Index {
+    chunks: Vec<IndexedChunk>,
+    // Pliki bez tekstu do zaindeksowania (ścieżka → hash), których nie ma w Qdrant
+    #[serde(default)]
+    empty_files: HashMap<String, String>,
+}
+
+impl Bm25Index {
+    /// Wczytuje indeks kolekcji; `None`, jeśli jeszcze go nie zbudowano
+    pub fn load(collection: &str) -> Option<Self> {
+        let content = fs::read_to_string(index_path(collection)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn save(&self, collection: &str) -> Result<(), String> {
+        fs::create_dir_all(INDEX_DIR)
+            .map_err(|e| tf("knowledge.index_dir_failed", &[&e]))?;
+        let json = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        fs::write(index_path(collection), json)
+            .map_err(|e| tf("knowledge.index_save_failed", &[&e]))
+    }
+
+    pub fn remove(collection: &str) {
+        let _ = fs::remove_file(index_path(collection));
+    }
+
+    pub fn rename(from: &str, to: &str) {
+        let _ = fs::rename(index_path(from), index_path(to));
+    }
+
+    pub fn add(&mut self, payload: ChunkPayload) {
+        let mut terms = HashMap::new();
+        let mut length = 0;
+        for term in tokenize(&payload.text) {
+            *terms.entry(term).or_insert(0) += 1;
+            length += 1;
+        }
+        self.chunks.push(IndexedChunk {
+            payload,
+            terms,
+            length,
+        });
+    }
+
+    pub fn remove_path(&mut self, path: &str) {
+        self.chunks.retain(|c| c.payload.path != path);
+        self.empty_files.remove(path);
+    }
+
+    /// Zapamiętuje hash pliku, z którego nie wydobyto żadnego fragmentu
+    pub fn add_empty(&mut self, path: String, content_hash: String) {
+        self.empty_files.insert(path, content_hash);
+    }
+
+    pub fn empty_files(&self) -> &HashMap<String, String> {
+        &self.empty_files
+    }
+
+    /// Zwraca fragmenty z najwyższym wynikiem BM25 dla zapytania
+    pub fn search(&self, query: &str, limit: usize) -> Vec<(ChunkPayload, f32)> {
+        let query_terms = tokenize(query);
+        if query_terms.is_empty() || self.chunks.is_empty() {
+            return Vec::new();
+        }
+
+        let count = self.chunks.len() as f32;
+        let average_length =
+            self.chunks.iter().map(|c| c.length as f32).sum::<f32>() / count;
+
+        let idf: HashMap<&str, f32> = query_terms
+            .iter()
+            .map(|term| {
+                let df = self.chunks.iter().filter(|c| c.terms.contains_key(term)).count() as f32;
+                (term.as_str(), ((count - df + 0.5) / (df + 0.5) + 1.0).ln())
+            })
+            .collect();
+
+        let mut scored: Vec<(usize, f32)> = self
+            .chunks
+            .iter()
+            .enumerate()
+            .map(|(index, chunk)| {
+                let score = query_terms
+                    .iter()
+                    .map(|term| {
+                        let tf = *chunk.terms.get(term).unwrap_or(&0) as f32;
+                        let norm = K1 * (1.0 - B + B * chunk.length as f32 / average_length.max(1.0));
+                        idf[term.as_str()] * tf * (K1 + 1.0) / (tf + norm)
+                    })
+                    .sum::<f32>();
+                (index, score)
+            })
+            .filter(|(_, score)| *score > 0.0)
+            .collect();
+
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored
+            .into_iter()
+            .take(limit)
+            .map(|(index, score)| (self.chunks[index].payload.clone(), score))
+            .collect()
+    }
+}
+
+// Identyfikatory i kody błędów (np. E0425, max_tokens) traktujemy jako całe słowa
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect()
+}
+
+fn index_path(collection: &str) -> PathBuf {
+    let file_name: String = collection
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    PathBuf::from(INDEX_DIR).join(format!("{}.json", file_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload(path: &str, text: &str) -> ChunkPayload {
+        ChunkPayload {
+            path: path.to_string(),
+            file_name: path.to_string(),
+            chunk_index: 0,
+            text: text.to_string(),
+            page: None,
+            heading: None,
+            content_hash: String::new(),
+        }
+    }
+
+    fn index(chunks: &[(&str, &str)]) -> Bm25Index {
+        let mut index = Bm25Index::default();
+        for (path, text) in chunks {
+            index.add(payload(path, text));
+        }
+        index
+    }
+
+    fn paths(results: &[(ChunkPayload, f32)]) -> Vec<&str> {
+        results.iter().map(|(p, _)| p.path.as_str()).collect()
+    }
+
+    #[test]
+    fn ranks_more_frequent_terms_higher() {
+        let index = index(&[
+            ("a", "kompilator zgłasza błąd"),
+            ("b", "błąd E0425 i jeszcze raz błąd E0425"),
+            ("c", "dokumentacja modułu sieciowego"),
+        ]);
+
+        let results = index.search("E0425", 10);
+
+        assert_eq!(paths(&results), vec!["b"]);
+        let results = index.search("błąd", 10);
+        assert_eq!(paths(&results), vec!["b", "a"]);
+        assert!(results[0].1 > results[1].1);
+    }
+
+    #[test]
+    fn rare_terms_outweigh_common_ones() {
+        let index = index(&[
+            ("a", "serwer serwer konfiguracja"),
+            ("b", "serwer max_tokens"),
+            ("c", "serwer logi"),
+        ]);
+
+        let results = index.search("serwer max_tokens", 10);
+
+        assert_eq!(results[0].0.path, "b");
+    }
+
+    #[test]
+    fn respects_limit_and_ignores_missing_terms() {
+        let index = index(&[("a", "jeden dwa"), ("b", "dwa trzy"), ("c", "trzy cztery")]);
+
+        assert_eq!(index.search("dwa trzy", 1).len(), 1);
+        assert!(index.search("pięć", 10).is_empty());
+        assert!(index.search("  ", 10).is_empty());
+    }
+
+    #[test]
+    fn removes_chunks_of_a_file() {
+        let mut index = index(&[("a", "raport kwartalny"), ("b", "raport roczny")]);
+
+        index.remove_path("a");
+
+        assert_eq!(paths(&index.search("raport", 10)), vec!["b"]);
+    }
+
+    #[test]
+    fn removing_a_file_forgets_its_empty_hash() {
+        let mut index = index(&[("a", "raport kwartalny")]);
+        index.add_empty("pusty".to_string(), "hash".to_string());
+
+        index.remove_path("pusty");
+
+        assert!(index.empty_files().is_empty());
+        assert_eq!(paths(&index.search("raport", 10)), vec!["a"]);
+    }
+
+    #[test]
+    fn tokenizer_keeps_identifiers_whole() {
+        assert_eq!(tokenize("Błąd E0425: max_tokens=512"), vec!["błąd", "e0425", "max_tokens", "512"]);
+    }
+}
@@ -0,0 +1,935 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use qdrant_client::qdrant::{
+    Condition, CountPointsBuilder, CreateCollectionBuilder, DeletePointsBuilder, Distance, Filter,
+    PayloadIncludeSelector, PointStruct, ScrollPointsBuilder, SearchPointsBuilder,
+    UpsertPointsBuilder, Value, VectorParamsBuilder, vector_output, vectors_config,
+};
+use qdrant_client::{Payload, Qdrant};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+// Indeks słów kluczowych BM25
+pub mod bm25;
+
+use crate::documents;
+use crate::i18n::tf;
+use crate::lm_studio_client::{LMStudioClient, Message as LMMessage};
+use bm25::Bm25Index;
+
+// Domyślna kolekcja, do której trafiają dokumenty
+pub const DEFAULT_COLLECTION: &str = "knowledge";
+pub const DEFAULT_EMBEDDING_MODEL: &str = "text-embedding-nomic-embed-text-v1.5";
+
+pub const DEFAULT_QDRANT_URL: &str = "http://localhost:6334";
+// Adres ustawiony przez `set_qdrant_url`; pusty oznacza domyślny
+static QDRANT_URL: RwLock<String> = RwLock::new(String::new());
+const EMBEDDING_BATCH: usize = 32;
+
+// Stała k z metody Reciprocal Rank Fusion
+const RRF_K: f32 = 60.0;
+// Ile kandydatów z każdej metody wyszukiwania trafia do fuzji
+const CANDIDATE_MULTIPLIER: u64 = 3;
+// Ile znaków fragmentu widzi model oceniający trafność
+const RERANK_PREVIEW_CHARS: usize = 600;
+
+// Kolekcja wiedzy wraz z modelem, którym osadzono jej fragmenty
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollectionSettings {
+    pub name: String,
+    pub embedding_model: String,
+    // Łączenie wyszukiwania wektorowego z BM25
+    #[serde(default = "default_hybrid")]
+    pub hybrid: bool,
+    // Ponowne ułożenie najlepszych kandydatów przez model czatu
+    #[serde(default)]
+    pub rerank: bool,
+}
+
+fn default_hybrid() -> bool {
+    true
+}
+
+impl Default for CollectionSettings {
+    fn default() -> Self {
+        CollectionSettings {
+            name: DEFAULT_COLLECTION.to_string(),
+            embedding_model: DEFAULT_EMBEDDING_MODEL.to_string(),
+            hybrid: true,
+            rerank: false,
+        }
+    }
+}
+
+// Dokument zaindeksowany w kolekcji
+#[derive(Debug, Clone)]
+pub struct IndexedDocument {
+    pub path: String,
+    pub file_name: String,
+    pub chunks: usize,
+}
+
+// Statystyki kolekcji pokazywane w panelu zarządzania
+#[derive(Debug, Clone)]
+pub struct CollectionStats {
+    pub name: String,
+    pub embedding_model: String,
+    pub vector_size: u64,
+    pub chunk_count: u64,
+    pub documents: Vec<IndexedDocument>,
+}
+
+// Metadane fragmentu przechowywane w payloadzie punktu Qdrant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkPayload {
+    pub path: String,
+    pub file_name: String,
+    pub chunk_index: usize,
+    pub text: String,
+    pub page: Option<u32>,
+    pub heading: Option<String>,
+    #[serde(default)]
+    pub content_hash: String,
+}
+
+// Wynik synchronizacji obserwowanych folderów z kolekcją
+#[derive(Debug, Clone, Default)]
+pub struct SyncReport {
+    pub added: usize,
+    pub modified: usize,
+    pub removed: usize,
+    pub unchanged: usize,
+    // Pliki pominięte z powodu błędu, razem z jego opisem
+    pub failed: Vec<(String, String)>,
+}
+
+impl SyncReport {
+    pub fn summary(&self) -> String {
+        tf(
+            "knowledge.sync_summary",
+            &[&self.added, &self.modified, &self.removed, &self.unchanged, &self.failed.len()],
+        )
+    }
+}
+
+// Fragment znaleziony w bazie wiedzy
+#[derive(Debug, Clone)]
+pub struct RetrievedChunk {
+    pub payload: ChunkPayload,
+    pub score: f32,
+}
+
+impl RetrievedChunk {
+    /// Opis źródła do cytowania, np. "raport.pdf, str. 12 – Wnioski"
+    pub fn citation(&self) -> String {
+        let mut citation = self.payload.file_name.clone();
+        if let Some(page) = self.payload.page {
+            citation.push_str(&format!(", {}", tf("knowledge.page", &[&page])));
+        }
+        if let Some(heading) = &self.payload.heading {
+            citation.push_str(&format!(" – {}", heading));
+        }
+        citation
+    }
+}
+
+// Baza wiedzy: osadzenia z LM Studio przechowywane w Qdrant
+pub struct KnowledgeBase {
+    qdrant: Qdrant,
+    lm_client: LMStudioClient,
+    embedding_model: String,
+}
+
+impl KnowledgeBase {
+    pub fn new(lm_client: LMStudioClient, embedding_model: &str) -> Result<Self, String> {
+        Ok(KnowledgeBase {
+            qdrant: connect_qdrant()?,
+            lm_client,
+            embedding_model: embedding_model.to_string(),
+        })
+    }
+
+    /// Dodaje plik do kolekcji, zastępując fragmenty z poprzedniego indeksowania
+    pub async fn ingest_file(&self, collection: &str, path: &Path) -> Result<usize, String> {
+        let mut index = Bm25Index::load(collection).unwrap_or_default();
+        let count = self.ingest_into(collection, path, &mut index).await?;
+        index.save(collection)?;
+        Ok(count)
+    }
+
+    // Indeksuje plik w Qdrant i we wskazanym indeksie BM25, który zapisuje wywołujący
+    async fn ingest_into(&self, collection: &str, path: &Path, index: &mut Bm25Index) -> Result<usize, String> {
+        let content_hash = file_hash(path)?;
+        let sections = documents::extract_sections(path)?;
+        let chunks = documents::chunk_sections(&sections);
+        let path_str = path.to_string_lossy().to_string();
+        // Pusta nowa wersja zastępuje starą; hash chroni ją przed ponownym indeksowaniem
+        if chunks.is_empty() {
+            self.delete_points(collection, &path_str).await?;
+            index.remove_path(&path_str);
+            index.add_empty(path_str, content_hash);
+            return Ok(0);
+        }
+
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path_str.clone());
+
+        let mut points = Vec::with_capacity(chunks.len());
+        let mut payloads = Vec::with_capacity(chunks.len());
+        let mut vector_size = 0;
+        for batch in chunks.chunks(EMBEDDING_BATCH) {
+            let vectors = self.embed(batch.iter().map(|c| c.text.clone()).collect()).await?;
+
+            for (chunk, vector) in batch.iter().zip(vectors) {
+                vector_size = vector.len() as u64;
+                let payload = ChunkPayload {
+                    path: path_str.clone(),
+                    file_name: file_name.clone(),
+                    chunk_index: chunk.index,
+                    text: chunk.text.clone(),
+                    page: chunk.page,
+                    heading: chunk.heading.clone(),
+                    content_hash: content_hash.clone(),
+                };
+                points.push(PointStruct::new(
+                    uuid::Uuid::new_v4().to_string(),
+                    vector,
+                    to_payload(&payload)?,
+                ));
+                payloads.push(payload);
+            }
+        }
+
+        self.ensure_collection(collection, vector_size).await?;
+        self.delete_points(collection, &path_str).await?;
+
+        let count = points.len();
+        self.qdrant
+            .upsert_points(UpsertPointsBuilder::new(collection, points).wait(true))
+            .await
+            .map_err(|e| tf("knowledge.upsert_failed", &[&e]))?;
+
+        index.remove_path(&path_str);
+        for payload in payloads {
+            index.add(payload);
+        }
+
+        Ok(count)
+    }
+
+    /// Dodaje kilka plików naraz; błąd jednego pliku nie przerywa pozostałych.
+    /// Indeks słów kluczowych jest zapisywany raz, na końcu
+    pub async fn ingest_files(
+        &self,
+        collection: &str,
+        files: &[PathBuf],
+    ) -> Result<Vec<(PathBuf, Result<usize, String>)>, String> {
+        let mut index = Bm25Index::load(collection).unwrap_or_default();
+        let mut results = Vec::with_capacity(files.len());
+        for file in files {
+            let result = self.ingest_into(collection, file, &mut index).await;
+            results.push((file.clone(), result));
+        }
+        index.save(collection)?;
+        Ok(results)
+    }
+
+    /// Usuwa z kolekcji wszystkie fragmenty danego pliku
+    pub async fn delete_file(&self, collection: &str, path: &str) -> Result<(), String> {
+        self.delete_points(collection, path).await?;
+
+        if let Some(mut index) = Bm25Index::load(collection) {
+            index.remove_path(path);
+            index.save(collection)?;
+        }
+
+        Ok(())
+    }
+
+    // Usuwa fragmenty pliku tylko z Qdrant
+    async fn delete_points(&self, collection: &str, path: &str) -> Result<(), String> {
+        if !self.collection_exists(collection).await? {
+            return Ok(());
+        }
+
+        self.qdrant
+            .delete_points(
+                DeletePointsBuilder::new(collection)
+                    .points(Filter::must([Condition::matches("path", path.to_string())]))
+                    .wait(true),
+            )
+            .await
+            .map_err(|e| tf("knowledge.delete_points_failed", &[&e]))?;
+
+        Ok(())
+    }
+
+    /// Zwraca zaindeksowane pliki kolekcji wraz z hashem ich zawartości
+    pub async fn indexed_files(&self, collection: &str) -> Result<HashMap<String, String>, String> {
+        let payloads = self.scroll_payloads(collection, &["path", "content_hash"]).await?;
+
+        Ok(payloads
+            .into_iter()
+            .filter_map(|json| {
+                let path = json.get("path")?.as_str()?.to_string();
+                let hash = json.get("content_hash").and_then(|v| v.as_str()).unwrap_or("");
+                Some((path, hash.to_string()))
+            })
+            .collect())
+    }
+
+    /// Lista dokumentów kolekcji z liczbą fragmentów każdego z nich
+    pub async fn documents(&self, collection: &str) -> Result<Vec<IndexedDocument>, String> {
+        let payloads = self.scroll_payloads(collection, &["path", "file_name"]).await?;
+        let mut documents: Vec<IndexedDocument> = Vec::new();
+
+        for json in payloads {
+            let Some(path) = json.get("path").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            match documents.iter_mut().find(|d| d.path == path) {
+                Some(document) => document.chunks += 1,
+                None => documents.push(IndexedDocument {
+                    path: path.to_string(),
+                    file_name: json
+                        .get("file_name")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or(path)
+                        .to_string(),
+                    chunks: 1,
+                }),
+            }
+        }
+
+        documents.sort_by_key(|d| d.file_name.to_lowercase());
+        Ok(documents)
+    }
+
+    /// Nazwy wszystkich kolekcji w Qdrant
+    pub async fn list_collections(&self) -> Result<Vec<String>, String> {
+        let response = self
+            .qdrant
+            .list_collections()
+            .await
+            .map_err(|e| tf("knowledge.list_failed", &[&e]))?;
+
+        let mut names: Vec<String> = response.collections.into_iter().map(|c| c.name).collect();
+        names.sort();
+        Ok(names)
+    }
+
+    /// Tworzy pustą kolekcję o rozmiarze wektora zgodnym z modelem osadzeń
+    pub async fn create_collection(&self, collection: &str) -> Result<(), String> {
+        if self.collection_exists(collection).await? {
+            return Err(tf("knowledge.collection_exists", &[&collection]));
+        }
+
+        let probe = self.embed(vec!["test".to_string()]).await?;
+        let vector_size = probe.first().map(|v| v.len() as u64).unwrap_or(0);
+        self.ensure_collection(collection, vector_size).await
+    }
+
+    pub async fn delete_collection(&self, collection: &str) -> Result<(), String> {
+        self.qdrant
+            .delete_collection(collection)
+            .await
+            .map_err(|e| tf("knowledge.delete_failed", &[&collection, &e]))?;
+        Bm25Index::remove(collection);
+        Ok(())
+    }
+
+    /// Qdrant nie obsługuje zmiany nazwy, więc fragmenty są przenoszone do nowej kolekcji
+    pub async fn rename_collection(&self, from: &str, to: &str) -> Result<(), String> {
+        if self.collection_exists(to).await? {
+            return Err(tf("knowledge.collection_exists", &[&to]));
+        }
+
+        // Punkty są kopiowane razem z wektorami, więc nic nie trzeba osadzać ponownie
+        let (_, vector_size) = self.collection_info(from).await?;
+        self.ensure_collection(to, vector_size).await?;
+
+        // Źródło jest usuwane dopiero wtedy, gdy skopiowano wszystkie jego punkty
+        let copied = match self.copy_points(from, to).await {
+            Ok(copied) => copied,
+            Err(error) => {
+                let _ = self.delete_collection(to).await;
+                return Err(error);
+            }
+        };
+        let expected = self.count_points(from).await?;
+        if copied != expected || self.count_points(to).await? != expected {
+            let _ = self.delete_collection(to).await;
+            return Err(tf("knowledge.rename_incomplete", &[&copied, &expected, &from]));
+        }
+
+        // Payloady są te same, więc indeks słów kluczowych wystarczy przenieść
+        Bm25Index::rename(from, to);
+        self.delete_collection(from).await
+    }
+
+    // Kopiuje wszystkie punkty z wektorami i zwraca ich liczbę
+    async fn copy_points(&self, from: &str, to: &str) -> Result<u64, String> {
+        let mut copied = 0;
+        let mut offset = None;
+        loop {
+            let mut request = ScrollPointsBuilder::new(from)
+                .limit(256)
+                .with_payload(true)
+                .with_vectors(true);
+            if let Some(offset) = offset.take() {
+                request = request.offset(offset);
+            }
+
+            let response = self
+                .qdrant
+                .scroll(request)
+                .await
+                .map_err(|e| tf("knowledge.read_failed", &[&from, &e]))?;
+
+            let points = response
+                .result
+                .into_iter()
+                .map(|point| {
+                    let vector = match point.vectors.as_ref().and_then(|v| v.get_vector()) {
+                        Some(vector_output::Vector::Dense(dense)) => dense.data,
+                        _ => return Err(tf("knowledge.point_without_vector", &[&from])),
+                    };
+                    let id = point.id.ok_or_else(|| tf("knowledge.point_without_id", &[&from]))?;
+                    Ok(PointStruct::new(id, vector, Payload::from(point.payload)))
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            copied += points.len() as u64;
+            if !points.is_empty() {
+                self.qdrant
+                    .upsert_points(UpsertPointsBuilder::new(to, points).wait(true))
+                    .await
+                    .map_err(|e| tf("knowledge.move_failed", &[&e]))?;
+            }
+
+            match response.next_page_offset {
+                Some(next) => offset = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(copied)
+    }
+
+    // Dokładna liczba punktów kolekcji
+    async fn count_points(&self, collection: &str) -> Result<u64, String> {
+        self.qdrant
+            .count(CountPointsBuilder::new(collection).exact(true))
+            .await
+            .map_err(|e| tf("knowledge.read_failed", &[&collection, &e]))?
+            .result
+            .map(|r| r.count)
+            .ok_or_else(|| tf("knowledge.no_info", &[&collection]))
+    }
+
+    /// Liczba fragmentów, rozmiar wektora i dokumenty kolekcji
+    pub async fn collection_stats(&self, collection: &str) -> Result<CollectionStats, String> {
+        let (chunk_count, vector_size) = self.collection_info(collection).await?;
+
+        Ok(CollectionStats {
+            name: collection.to_string(),
+            embedding_model: self.embedding_model.clone(),
+            vector_size,
+            chunk_count,
+            documents: self.documents(collection).await?,
+        })
+    }
+
+    // Liczba punktów i rozmiar wektora kolekcji
+    async fn collection_info(&self, collection: &str) -> Result<(u64, u64), String> {
+        let info = self
+            .qdrant
+            .collection_info(collection)
+            .await
+            .map_err(|e| tf("knowledge.info_failed", &[&e]))?
+            .result
+            .ok_or_else(|| tf("knowledge.no_info", &[&collection]))?;
+
+        let vector_size = info
+            .config
+            .and_then(|c| c.params)
+            .and_then(|p| p.vectors_config)
+            .and_then(|v| v.config)
+            .map(|config| match config {
+                vectors_config::Config::Params(params) => params.size,
+                _ => 0,
+            })
+            .unwrap_or(0);
+
+        Ok((info.points_count.unwrap_or(0), vector_size))
+    }
+
+    // Przegląda wszystkie punkty kolekcji, zwracając wybrane pola payloadu (wszystkie dla pustej listy)
+    async fn scroll_payloads(
+        &self,
+        collection: &str,
+        fields: &[&str],
+    ) -> Result<Vec<serde_json::Map<String, serde_json::Value>>, String> {
+        let mut payloads = Vec::new();
+        if !self.collection_exists(collection).await? {
+            return Ok(payloads);
+        }
+
+        let mut offset = None;
+        loop {
+            let mut request = ScrollPointsBuilder::new(collection)
+                .limit(256)
+                .with_vectors(false);
+            request = if fields.is_empty() {
+                request.with_payload(true)
+            } else {
+                request.with_payload(PayloadIncludeSelector {
+                    fields: fields.iter().map(|f| f.to_string()).collect(),
+                })
+            };
+            if let Some(offset) = offset.take() {
+                request = request.offset(offset);
+            }
+
+            let response = self
+                .qdrant
+                .scroll(request)
+                .await
+                .map_err(|e| tf("knowledge.read_failed", &[&collection, &e]))?;
+
+            payloads.extend(response.result.into_iter().map(|point| payload_to_json(point.payload)));
+
+            match response.next_page_offset {
+                Some(next) => offset = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(payloads)
+    }
+
+    /// Indeksuje tylko nowe i zmienione pliki z folderów, usuwa fragmenty skasowanych.
+    /// Błąd jednego pliku trafia do raportu i nie przerywa synchronizacji pozostałych
+    pub async fn sync_directories(
+        &self,
+        collection: &str,
+        directories: &[String],
+    ) -> Result<SyncReport, String> {
+        // Indeks słów kluczowych jest zapisywany raz, po przetworzeniu wszystkich plików
+        let mut index = Bm25Index::load(collection).unwrap_or_default();
+        let mut indexed = self.indexed_files(collection).await?;
+        indexed.extend(index.empty_files().clone());
+        let mut report = SyncReport::default();
+        let mut present = Vec::new();
+
+        for directory in directories {
+            for path in supported_files(Path::new(directory)) {
+                let path_str = path.to_string_lossy().to_string();
+                present.push(path_str.clone());
+                let hash = match file_hash(&path) {
+                    Ok(hash) => hash,
+                    Err(error) => {
+                        report.failed.push((path_str, error));
+                        continue;
+                    }
+                };
+
+                let existing = indexed.get(&path_str);
+                if existing == Some(&hash) {
+                    report.unchanged += 1;
+                    continue;
+                }
+                match self.ingest_into(collection, &path, &mut index).await {
+                    Ok(_) if existing.is_some() => report.modified += 1,
+                    Ok(_) => report.added += 1,
+                    Err(error) => report.failed.push((path_str, error)),
+                }
+            }
+        }
+
+        // Pliki z obserwowanych folderów, których już nie ma na dysku
+        for path in indexed.keys() {
+            let watched = directories.iter().any(|d| Path::new(path).starts_with(d));
+            if watched && !present.contains(path) {
+                match self.delete_points(collection, path).await {
+                    Ok(()) => {
+                        index.remove_path(path);
+                        report.removed += 1;
+                    }
+                    Err(error) => report.failed.push((path.clone(), error)),
+                }
+            }
+        }
+
+        index.save(collection)?;
+        for (path, error) in &report.failed {
+            tracing::warn!("Pominięto {} podczas synchronizacji: {}", path, error);
+        }
+        Ok(report)
+    }
+
+    /// Wyszukuje fragmenty najbardziej podobne do zapytania
+    pub async fn search(
+        &self,
+        collection: &str,
+        query: &str,
+        limit: u64,
+    ) -> Result<Vec<RetrievedChunk>, String> {
+        if !self.collection_exists(collection).await? {
+            return Ok(Vec::new());
+        }
+
+        let vector = self
+            .embed(vec![query.to_string()])
+            .await?
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+
+        let response = self
+            .qdrant
+            .search_points(SearchPointsBuilder::new(collection, vector, limit).with_payload(true))
+            .await
+            .map_err(|e| tf("knowledge.search_failed", &[&e]))?;
+
+        Ok(response
+            .result
+            .into_iter()
+            .filter_map(|point| {
+                from_payload(point.payload).map(|payload| RetrievedChunk {
+                    payload,
+                    score: point.score,
+                })
+            })
+            .collect())
+    }
+
+    /// Wyszukiwanie słów kluczowych; indeks budowany z payloadów, jeśli go brakuje
+    pub async fn keyword_search(
+        &self,
+        collection: &str,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<RetrievedChunk>, String> {
+        let index = match Bm25Index::load(collection) {
+            Some(index) => index,
+            None => {
+                let mut index = Bm25Index::default();
+                for json in self.scroll_payloads(collection, &[]).await? {
+                    if let Ok(payload) = serde_json::from_value(serde_json::Value::Object(json)) {
+                        index.add(payload);
+                    }
+                }
+                index.save(collection)?;
+                index
+            }
+        };
+
+        Ok(index
+            .search(query, limit)
+            .into_iter()
+            .map(|(payload, score)| RetrievedChunk { payload, score })
+            .collect())
+    }
+
+    /// Wyszukiwanie zgodne z ustawieniami kolekcji: wektorowe lub hybrydowe (RRF)
+    pub async fn hybrid_search(
+        &self,
+        settings: &CollectionSettings,
+        query: &str,
+        limit: u64,
+    ) -> Result<Vec<RetrievedChunk>, String> {
+        if !settings.hybrid {
+            return self.search(&settings.name, query, limit).await;
+        }
+
+        let candidates = limit * CANDIDATE_MULTIPLIER;
+        let vector = self.search(&settings.name, query, candidates).await?;
+        let keyword = self.keyword_search(&settings.name, query, candidates as usize).await?;
+
+        let mut fused = reciprocal_rank_fusion(vec![vector, keyword]);
+        fused.truncate(limit as usize);
+        Ok(fused)
+    }
+
+    async fn collection_exists(&self, collection: &str) -> Result<bool, String> {
+        self.qdrant
+            .collection_exists(collection)
+            .await
+            .map_err(|e| tf("knowledge.connect_failed", &[&e]))
+    }
+
+    async fn ensure_collection(&self, collection: &str, vector_size: u64) -> Result<(), String> {
+        if self.collection_exists(collection).await? {
+            return Ok(());
+        }
+
+        self.qdrant
+            .create_collection(
+                CreateCollectionBuilder::new(collection)
+                    .vectors_config(VectorParamsBuilder::new(vector_size, Distance::Cosine)),
+            )
+            .await
+            .map_err(|e| tf("knowledge.create_failed", &[&collection, &e]))?;
+
+        Ok(())
+    }
+
+    async fn embed(&self, input: Vec<String>) -> Result<Vec<Vec<f32>>, String> {
+        self.lm_client
+            .embed(&self.embedding_model, input)
+            .await
+            .map_err(|e| tf("knowledge.embedding_failed", &[&e]))
+    }
+}
+
+/// Przeszukuje kilka kolekcji (każdą jej własnym modelem osadzeń) i łączy wyniki
+pub async fn search_collections(
+    lm_client: &LMStudioClient,
+    collections: &[CollectionSettings],
+    query: &str,
+    limit: u64,
+    chat_model: &str,
+) -> Result<Vec<RetrievedChunk>, String> {
+    let mut lists = Vec::new();
+
+    for collection in collections {
+        let kb = KnowledgeBase::new(lm_client.clone(), &collection.embedding_model)?;
+        let candidates = if collection.rerank { limit * CANDIDATE_MULTIPLIER } else { limit };
+        let mut results = kb.hybrid_search(collection, query, candidates).await?;
+        if collection.rerank {
+            results = rerank(lm_client, chat_model, query, results).await;
+        }
+        lists.push(results);
+    }
+
+    // Wyniki różnych kolekcji mają nieporównywalne wyniki, więc łączymy je po pozycjach
+    let mut results = if lists.len() == 1 {
+        lists.remove(0)
+    } else {
+        reciprocal_rank_fusion(lists)
+    };
+    results.truncate(limit as usize);
+    Ok(results)
+}
+
+/// Łączy rankingi metodą Reciprocal Rank Fusion: wynik = suma 1 / (k + pozycja)
+pub fn reciprocal_rank_fusion(lists: Vec<Vec<RetrievedChunk>>) -> Vec<RetrievedChunk> {
+    let mut fused: Vec<RetrievedChunk> = Vec::new();
+
+    for list in lists {
+        for (rank, chunk) in list.into_iter().enumerate() {
+            let score = 1.0 / (RRF_K + rank as f32 + 1.0);
+            let existing = fused.iter_mut().find(|c| {
+                c.payload.path == chunk.payload.path
+                    && c.payload.chunk_index == chunk.payload.chunk_index
+            });
+            match existing {
+                Some(existing) => existing.score += score,
+                None => fused.push(RetrievedChunk {
+                    payload: chunk.payload,
+                    score,
+                }),
+            }
+        }
+    }
+
+    fused.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    fused
+}
+
+/// Prosi model czatu o ułożenie kandydatów od najbardziej trafnego; przy błędzie zostawia kolejność
+pub async fn rerank(
+    lm_client: &LMStudioClient,
+    model: &str,
+    query: &str,
+    candidates: Vec<RetrievedChunk>,
+) -> Vec<RetrievedChunk> {
+    if candidates.len() < 2 {
+        return candidates;
+    }
+
+    let mut prompt = format!(
+        "Pytanie: {}\n\nPoniżej są ponumerowane fragmenty dokumentów. \
+         Zwróć wyłącznie numery fragmentów uporządkowane od najbardziej do najmniej \
+         przydatnego do odpowiedzi na pytanie, oddzielone przecinkami.\n",
+        query
+    );
+    for (index, chunk) in candidates.iter().enumerate() {
+        let preview: String = chunk.payload.text.chars().take(RERANK_PREVIEW_CHARS).collect();
+        prompt.push_str(&format!("\n[{}] {}\n", index + 1, preview));
+    }
+
+    let response = match lm_client.send_message(
+        model,
+        vec![LMMessage {
+            role: "user".to_string(),
+            content: prompt,
+        }],
+    ).await {
+        Ok(response) => response,
+        Err(_) => return candidates,
+    };
+
+    // Numery w kolejności z odpowiedzi, bez powtórzeń; pominięte fragmenty trafiają na koniec
+    let mut order: Vec<usize> = Vec::new();
+    for number in response.split(|c: char| !c.is_ascii_digit()).filter_map(|n| n.parse::<usize>().ok()) {
+        if number >= 1 && number <= candidates.len() && !order.contains(&(number - 1)) {
+            order.push(number - 1);
+        }
+    }
+    for index in 0..candidates.len() {
+        if !order.contains(&index) {
+            order.push(index);
+        }
+    }
+
+    let count = order.len() as f32;
+    order
+        .into_iter()
+        .enumerate()
+        .map(|(rank, index)| RetrievedChunk {
+            payload: candidates[index].payload.clone(),
+            score: (count - rank as f32) / count,
+        })
+        .collect()
+}
+
+/// Buduje treść wiadomości systemowej z fragmentami i źródłami do cytowania
+pub fn format_context(chunks: &[RetrievedChunk]) -> String {
+    let mut context = String::from(
+        "Odpowiadaj na podstawie poniższych fragmentów bazy wiedzy. \
+         Powołując się na fragment, podaj jego źródło w nawiasie kwadratowym.\n",
+    );
+
+    for (index, chunk) in chunks.iter().enumerate() {
+        context.push_str(&format!(
+            "\n[{}] {}\n{}\n",
+            index + 1,
+            chunk.citation(),
+            chunk.payload.text
+        ));
+    }
+
+    context
+}
+
+/// Klient Qdrant współdzielony przez bazę wiedzy i pamięć długoterminową
+/// Zmienia adres Qdrant dla wszystkich baz wiedzy i pamięci tworzonych później w procesie
+pub fn set_qdrant_url(url: &str) {
+    *QDRANT_URL.write().unwrap_or_else(|e| e.into_inner()) = url.trim_end_matches('/').to_string();
+}
+
+pub fn connect_qdrant() -> Result<Qdrant, String> {
+    let url = QDRANT_URL.read().unwrap_or_else(|e| e.into_inner()).clone();
+    Qdrant::from_url(if url.is_empty() { DEFAULT_QDRANT_URL } else { &url })
+        .build()
+        .map_err(|e| tf("knowledge.connect_failed", &[&e]))
+}
+
+pub fn to_payload<T: Serialize>(payload: &T) -> Result<Payload, String> {
+    let json = serde_json::to_value(payload).map_err(|e| e.to_string())?;
+    Payload::try_from(json).map_err(|e| e.to_string())
+}
+
+pub fn payload_to_json(payload: HashMap<String, Value>) -> serde_json::Map<String, serde_json::Value> {
+    payload
+        .into_iter()
+        .map(|(key, value)| (key, serde_json::Value::from(value)))
+        .collect()
+}
+
+fn from_payload(payload: HashMap<String, Value>) -> Option<ChunkPayload> {
+    serde_json::from_value(serde_json::Value::Object(payload_to_json(payload))).ok()
+}
+
+/// Hash SHA-256 zawartości pliku zapisywany w payloadzie fragmentów
+pub fn file_hash(path: &Path) -> Result<String, String> {
+    let bytes = fs::read(path)
+        .map_err(|e| tf("knowledge.file_read_failed", &[&path.display(), &e]))?;
+    Ok(format!("{:x}", Sha256::digest(&bytes)))
+}
+
+/// Zamienia foldery na listę zawartych w nich obsługiwanych plików
+pub fn expand_paths(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            files.extend(supported_files(path));
+        } else {
+            files.push(path.clone());
+        }
+    }
+    files
+}
+
+/// Rekurencyjnie zbiera pliki w obsługiwanych formatach
+pub fn supported_files(directory: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let Ok(entries) = fs::read_dir(directory) else {
+        return files;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            files.extend(supported_files(&path));
+        } else if documents::is_supported(&path) {
+            files.push(path);
+        }
+    }
+
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(path: &str, chunk_index: usize) -> RetrievedChunk {
+        RetrievedChunk {
+            payload: ChunkPayload {
+                path: path.to_string(),
+                file_name: path.to_string(),
+                chunk_index,
+                text: String::new(),
+                page: None,
+                heading: None,
+                content_hash: String::new(),
+            },
+            score: 0.0,
+        }
+    }
+
+    fn order(chunks: &[RetrievedChunk]) -> Vec<(&str, usize)> {
+        chunks.iter().map(|c| (c.payload.path.as_str(), c.payload.chunk_index)).collect()
+    }
+
+    #[test]
+    fn rrf_favours_chunks_found_by_both_searches() {
+        let vector = vec![chunk("a", 0), chunk("b", 0), chunk("c", 0)];
+        let keyword = vec![chunk("c", 0), chunk("d", 0), chunk("b", 0)];
+
+        let fused = reciprocal_rank_fusion(vec![vector, keyword]);
+
+        assert_eq!(order(&fused), vec![("c", 0), ("b", 0), ("a", 0), ("d", 0)]);
+        let expected = 1.0 / (RRF_K + 3.0) + 1.0 / (RRF_K + 1.0);
+        assert!((fused[0].score - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn rrf_distinguishes_chunks_of_the_same_file() {
+        let fused = reciprocal_rank_fusion(vec![vec![chunk("a", 0), chunk("a", 1)], vec![chunk("a", 1)]]);
+
+        assert_eq!(order(&fused), vec![("a", 1), ("a", 0)]);
+    }
+
+    #[test]
+    fn rrf_of_nothing_is_empty() {
+        assert!(reciprocal_rank_fusion(vec![Vec::new(), Vec::new()]).is_empty());
+    }
+}
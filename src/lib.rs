@@ -0,0 +1,34 @@
+// Biblioteka z logiką aplikacji: klient LM Studio, model rozmów, zapis danych,
+// baza wiedzy i pamięć. Okno iced oraz tryb wiersza poleceń są jej konsumentami.
+
+// Klient API LM Studio (czat, strumieniowanie, osadzenia)
+pub mod lm_studio_client;
+// Wiadomości, załączniki i zapisane rozmowy
+pub mod conversation;
+// Ustawienia aplikacji
+pub mod settings;
+// Odczyt i zapis rozmów oraz ustawień na dysku
+pub mod storage;
+// Eksport rozmów do Markdown, HTML i JSONL
+pub mod export;
+// Import rozmów z ChatGPT, Open WebUI i LM Studio
+pub mod import;
+// Wyodrębnianie tekstu z dokumentów
+pub mod documents;
+// Baza wiedzy w Qdrant
+pub mod knowledge;
+// Pamięć długoterminowa
+pub mod memory;
+// Przygotowanie historii i kontekstu dla modelu
+pub mod chat;
+// Lokalny serwer HTTP API
+pub mod server;
+// Tłumaczenia interfejsu i formatowanie dat
+pub mod i18n;
+// Szablony poleceń ze zmiennymi
+pub mod templates;
+// Polecenia z ukośnikiem wpisywane w polu wiadomości
+pub mod commands;
+// Log w pliku i podgląd żądań wysyłanych do LM Studio
+pub mod logging;
+pub mod inspector;
@@ -1,14 +1,26 @@
-use reqwest::blocking::Client;
+use reqwest::{Client, Response};
+use reqwest::Method;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
-use reqwest::Url;
+use std::time::{Duration, Instant};
+
+use crate::i18n::{t, tf};
+use crate::inspector::{self, RequestRecord};
+
+// Model używany, gdy użytkownik nie wybrał innego
+pub const DEFAULT_MODEL: &str = "bielik-11b-v2.3-instruct";
+// Adres serwera LM Studio
+pub const DEFAULT_BASE_URL: &str = "http://localhost:1234";
+// Temperatura odpowiedzi, gdy rozmowa nie ustawia własnej
+pub const DEFAULT_TEMPERATURE: f32 = 0.7;
 
 #[derive(Clone, Debug)]
 pub struct LMStudioClient {
     client: Client,
+    base_url: String,
 }
 
-#[derive(Serialize,Deserialize, Clone)]
+#[derive(Serialize,Deserialize, Clone, Debug)]
 pub struct Message {
     pub role: String,
     pub content: String,
@@ -22,6 +34,39 @@ struct CompletionsRequest {
     temperature: f32,
     max_tokens: i32,
     stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<StreamOptions>,
+}
+
+// Prośba o liczniki tokenów w ostatnim fragmencie odpowiedzi strumieniowej
+#[derive(Serialize)]
+struct StreamOptions {
+    include_usage: bool,
+}
+
+// Liczniki tokenów z pola `usage` odpowiedzi
+#[derive(Deserialize, Clone, Copy)]
+struct Usage {
+    prompt_tokens: u64,
+    completion_tokens: u64,
+}
+
+/// Statystyki wygenerowanej odpowiedzi: liczby tokenów i czasy w milisekundach
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CompletionStats {
+    pub prompt_tokens: Option<u64>,
+    pub completion_tokens: Option<u64>,
+    pub time_to_first_token_ms: Option<u64>,
+    pub duration_ms: u64,
+}
+
+impl CompletionStats {
+    /// Szybkość generowania liczona od pierwszego tokenu
+    pub fn tokens_per_second(&self) -> Option<f64> {
+        let tokens = self.completion_tokens?;
+        let generation_ms = self.duration_ms.saturating_sub(self.time_to_first_token_ms.unwrap_or(0));
+        (generation_ms > 0).then(|| tokens as f64 * 1000.0 / generation_ms as f64)
+    }
 }
 
 #[derive(Deserialize)]
@@ -34,57 +79,247 @@ struct CompletionsResponse {
     choices: Vec<ChatChoice>,
 }
 
+// Fragment odpowiedzi strumieniowej (Server-Sent Events)
+#[derive(Deserialize)]
+struct StreamDelta {
+    content: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct StreamChoice {
+    delta: StreamDelta,
+}
+
 #[derive(Deserialize)]
-struct ModelEntry {
-     pub id: String,
+struct StreamChunk {
+    choices: Vec<StreamChoice>,
+    usage: Option<Usage>,
+}
+
+#[derive(Serialize)]
+struct EmbeddingsRequest {
+    model: String,
+    input: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    embedding: Vec<f32>,
+    index: usize,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingsResponse {
+    data: Vec<EmbeddingData>,
+}
+
+
+// Żądanie w toku; zapis dla inspektora jest uzupełniany po odczytaniu odpowiedzi
+struct Exchange {
+    record: RequestRecord,
+    started: Instant,
+}
+
+impl Exchange {
+    fn finish(mut self, body: Option<&str>, error: Option<String>, usage: Option<Usage>) {
+        if let Some(body) = body {
+            self.record.set_response_body(body);
+        }
+        self.record.error = error;
+        self.record.duration_ms = self.started.elapsed().as_millis() as u64;
+        self.record.prompt_tokens = usage.map(|u| u.prompt_tokens);
+        self.record.completion_tokens = usage.map(|u| u.completion_tokens);
+        inspector::record(self.record);
+    }
 }
 
+// Jedna linia zdarzeń SSE; zwraca `true` po znaczniku końca strumienia
+fn read_stream_line(line: &[u8], full: &mut String, usage: &mut Option<Usage>, on_token: &mut impl FnMut(&str)) -> bool {
+    let line = String::from_utf8_lossy(line);
+    let Some(data) = line.strip_prefix("data:") else { return false };
+    let data = data.trim();
+    if data == "[DONE]" {
+        return true;
+    }
+
+    if let Ok(chunk) = serde_json::from_str::<StreamChunk>(data) {
+        if let Some(content) = chunk.choices.first().and_then(|c| c.delta.content.as_deref()) {
+            on_token(content);
+            full.push_str(content);
+        }
+        *usage = chunk.usage.or(*usage);
+    }
+    false
+}
+
+fn json_body(body: &impl Serialize) -> Result<Option<serde_json::Value>, String> {
+    serde_json::to_value(body)
+        .map(Some)
+        .map_err(|e| tf("lm_studio.request_failed", &[&e]))
+}
+
+impl Default for LMStudioClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl LMStudioClient {
     pub fn new() -> Self {
+        Self::with_base_url(DEFAULT_BASE_URL)
+    }
+
+    /// Klient serwera zgodnego z LM Studio pod innym adresem
+    pub fn with_base_url(base_url: &str) -> Self {
         let client = Client::builder()
             .timeout(Duration::from_secs(1200))
             .build()
             .expect("Nie udało się zbudować klienta HTTP");
 
         LMStudioClient {
-            client
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn url(&self, endpoint: &str) -> String {
+        format!("{}/v1/{}", self.base_url, endpoint)
+    }
+
+    // Wysyła żądanie; każde trafia do inspektora i do logu, także nieudane
+    async fn send_request(
+        &self,
+        method: Method,
+        url: &str,
+        body: Option<serde_json::Value>,
+    ) -> Result<(Response, Exchange), String> {
+        let mut builder = self.client.request(method, url);
+        if let Some(body) = &body {
+            builder = builder.json(body);
         }
+        let request = builder.build().map_err(|e| tf("lm_studio.request_failed", &[&e]))?;
+
+        // Inspektor pokazuje dokładnie to, co zostanie wysłane
+        let headers = request
+            .headers()
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_str().unwrap_or_default().to_string()))
+            .collect();
+        let sent_body = request
+            .body()
+            .and_then(|b| b.as_bytes())
+            .map(|bytes| String::from_utf8_lossy(bytes).into_owned());
+        let mut exchange = Exchange {
+            record: RequestRecord::new(request.method().as_str(), request.url().as_str(), headers, sent_body),
+            started: Instant::now(),
+        };
+
+        match self.client.execute(request).await {
+            Ok(response) => {
+                exchange.record.status = Some(response.status().as_u16());
+                exchange.record.response_headers = response
+                    .headers()
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_str().unwrap_or_default().to_string()))
+                    .collect();
+                Ok((response, exchange))
+            }
+            Err(e) => {
+                let error = e.to_string();
+                exchange.finish(None, Some(error.clone()), None);
+                Err(error)
+            }
+        }
+    }
+
+    // Żądanie z odpowiedzią JSON; status spoza 2xx jest błędem z treścią odpowiedzi
+    async fn request_json<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        url: &str,
+        body: Option<serde_json::Value>,
+    ) -> Result<T, String> {
+        let (response, exchange) = self.send_request(method, url, body).await?;
+        let status = response.status();
+        let text = match response.text().await {
+            Ok(text) => text,
+            Err(e) => {
+                let error = e.to_string();
+                exchange.finish(None, Some(error.clone()), None);
+                return Err(error);
+            }
+        };
+        if !status.is_success() {
+            let error = format!("HTTP {}: {}", status, text.trim());
+            exchange.finish(Some(&text), Some(error.clone()), None);
+            return Err(error);
+        }
+
+        let value = serde_json::from_str::<serde_json::Value>(&text);
+        let usage = value
+            .as_ref()
+            .ok()
+            .and_then(|v| v.get("usage"))
+            .and_then(|u| Usage::deserialize(u).ok());
+        let result = value
+            .and_then(serde_json::from_value)
+            .map_err(|e| tf("lm_studio.invalid_response", &[&e]));
+        exchange.finish(Some(&text), result.as_ref().err().cloned(), usage);
+        result
     }
 
-    pub fn list_models(&self) -> Result<Vec<String>, reqwest::Error> {
-        let url = Url::parse("http://localhost:1234/v1/models").unwrap();
-        let resp: serde_json::Value = self.client.get(url).send()?.json()?;
+    // Żądanie, którego odpowiedź trafia tylko do inspektora
+    async fn request_ignoring_response(
+        &self,
+        method: Method,
+        url: &str,
+        body: Option<serde_json::Value>,
+    ) -> Result<(), String> {
+        let (response, exchange) = self.send_request(method, url, body).await?;
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        let error = (!status.is_success()).then(|| format!("HTTP {}", status));
+        exchange.finish(Some(&text), error, None);
+        Ok(())
+    }
+
+    pub async fn list_models(&self) -> Result<Vec<String>, String> {
+        let resp: serde_json::Value = self
+            .request_json(Method::GET, &self.url("models"), None)
+            .await?;
         let ids = resp["data"]
-            .as_array().unwrap()
+            .as_array()
+            .ok_or(t("lm_studio.no_model_list"))?
             .iter()
             .filter_map(|e| e.get("id").and_then(|v| v.as_str()).map(String::from))
             .collect();
         Ok(ids)
     }
 
-    /// Wysyła synchronicznie historię czatu do LMStudio i zwraca odpowiedź
-    pub fn send_message(
+    /// Wysyła historię czatu do LMStudio i zwraca całą odpowiedź
+    pub async fn send_message(
         &self,
         model: &str,
         history: Vec<Message>,
-    ) -> Result<String, reqwest::Error> {
+    ) -> Result<String, String> {
         let body = CompletionsRequest {
             model: model.to_string(),
             messages: history,
-            temperature: 0.7,
+            temperature: DEFAULT_TEMPERATURE,
             max_tokens: -1,
             stream: false,
+            stream_options: None,
         };
 
-        let resp: CompletionsResponse = self
-            .client
-            .post("http://localhost:1234/v1/chat/completions")
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()?
-            .error_for_status()?
-            .json()?;
+        let resp: CompletionsResponse = self.request_json(
+            Method::POST,
+            &self.url("chat/completions"),
+            json_body(&body)?,
+        ).await?;
 
         Ok(resp
             .choices
@@ -93,26 +328,161 @@ impl LMStudioClient {
             .map(|c| c.message.content)
             .unwrap_or_default())
     }
+    /// Wysyła historię z włączonym strumieniowaniem, przekazując kolejne fragmenty do `on_token`
+    pub async fn send_message_stream(
+        &self,
+        model: &str,
+        history: Vec<Message>,
+        on_token: impl FnMut(&str),
+    ) -> Result<String, String> {
+        self.stream_completion(model, history, DEFAULT_TEMPERATURE, on_token)
+            .await
+            .map(|(full, _)| full)
+    }
+
+    /// Wysyła historię i mierzy odpowiedź: czas do pierwszego tokenu, czas całkowity i liczby tokenów
+    pub async fn send_message_with_stats(
+        &self,
+        model: &str,
+        history: Vec<Message>,
+        temperature: f32,
+    ) -> Result<(String, CompletionStats), String> {
+        let started = Instant::now();
+        let mut first_token = None;
+
+        let (full, usage) = self.stream_completion(model, history, temperature, |token| {
+            if !token.is_empty() {
+                first_token.get_or_insert_with(|| started.elapsed());
+            }
+        }).await?;
+
+        let stats = CompletionStats {
+            prompt_tokens: usage.map(|u| u.prompt_tokens),
+            completion_tokens: usage.map(|u| u.completion_tokens),
+            time_to_first_token_ms: first_token.map(|t| t.as_millis() as u64),
+            duration_ms: started.elapsed().as_millis() as u64,
+        };
+        Ok((full, stats))
+    }
+
+    // Odpowiedź strumieniowa z licznikami tokenów, o ile serwer je przesłał
+    async fn stream_completion(
+        &self,
+        model: &str,
+        history: Vec<Message>,
+        temperature: f32,
+        mut on_token: impl FnMut(&str),
+    ) -> Result<(String, Option<Usage>), String> {
+        let body = CompletionsRequest {
+            model: model.to_string(),
+            messages: history,
+            temperature,
+            max_tokens: -1,
+            stream: true,
+            stream_options: Some(StreamOptions { include_usage: true }),
+        };
+
+        let (mut resp, exchange) = self.send_request(
+            Method::POST,
+            &self.url("chat/completions"),
+            json_body(&body)?,
+        ).await?;
+        let status = resp.status();
+        if !status.is_success() {
+            let text = resp.text().await.unwrap_or_default();
+            let error = format!("HTTP {}: {}", status, text.trim());
+            exchange.finish(Some(&text), Some(error.clone()), None);
+            return Err(error);
+        }
+
+        let mut full = String::new();
+        let mut usage = None;
+        // Fragmenty z sieci nie pokrywają się z liniami, więc niepełna linia czeka na resztę
+        let mut pending: Vec<u8> = Vec::new();
+        let mut done = false;
+        while !done {
+            match resp.chunk().await {
+                Ok(Some(bytes)) => pending.extend_from_slice(&bytes),
+                Ok(None) => break,
+                // Urwana odpowiedź nie może wyglądać na kompletną
+                Err(e) => {
+                    let error = tf("lm_studio.stream_interrupted", &[&e]);
+                    exchange.finish(Some(&full), Some(error.clone()), usage);
+                    return Err(error);
+                }
+            }
+            while let Some(end) = pending.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = pending.drain(..=end).collect();
+                done = read_stream_line(&line, &mut full, &mut usage, &mut on_token);
+                if done {
+                    break;
+                }
+            }
+        }
+        // Ostatnia linia strumienia może nie mieć znaku nowej linii
+        if !done && !pending.is_empty() {
+            read_stream_line(&pending, &mut full, &mut usage, &mut on_token);
+        }
+
+        // Zamiast setek zdarzeń strumienia inspektor pokazuje złożoną odpowiedź
+        exchange.finish(Some(&full), None, usage);
+        Ok((full, usage))
+    }
+
+    /// Przekazuje gotowe żądanie w formacie OpenAI i zwraca surową odpowiedź (także strumieniową)
+    pub async fn forward_chat_completion(
+        &self,
+        body: &serde_json::Value,
+    ) -> Result<Response, String> {
+        let (response, exchange) = self.send_request(
+            Method::POST,
+            &self.url("chat/completions"),
+            Some(body.clone()),
+        ).await?;
+        // Treść odpowiedzi czyta klient API, więc inspektor widzi tylko status i nagłówki
+        exchange.finish(None, None, None);
+        Ok(response)
+    }
+
+    /// Zwraca wektory osadzeń dla podanych tekstów, w tej samej kolejności
+    pub async fn embed(
+        &self,
+        model: &str,
+        input: Vec<String>,
+    ) -> Result<Vec<Vec<f32>>, String> {
+        let body = EmbeddingsRequest {
+            model: model.to_string(),
+            input,
+        };
+
+        let mut resp: EmbeddingsResponse = self.request_json(
+            Method::POST,
+            &self.url("embeddings"),
+            json_body(&body)?,
+        ).await?;
+
+        resp.data.sort_by_key(|d| d.index);
+        Ok(resp.data.into_iter().map(|d| d.embedding).collect())
+    }
+
     /// Sprawdza aktualnie załadowany model
-    pub fn get_loaded_model(&self) -> Result<Option<String>, reqwest::Error> {
+    pub async fn get_loaded_model(&self) -> Result<Option<String>, String> {
         let resp: serde_json::Value = self
-            .client
-            .get("http://localhost:1234/v1/models/loaded")
-            .send()?
-            .json()?;
+            .request_json(Method::GET, &self.url("models/loaded"), None)
+            .await?;
 
         Ok(resp.get("model").and_then(|v| v.as_str()).map(String::from))
     }
 
     /// Wymusza przeładowanie modelu przez zatrzymanie i ponowne uruchomienie
-    pub fn force_reload_model(&self, model: &str) -> Result<(), reqwest::Error> {
+    pub async fn force_reload_model(&self, model: &str) -> Result<(), String> {
         // Spróbuj zatrzymać serwer modelu
-        let _ = self.client
-            .post("http://localhost:1234/v1/server/stop")
-            .send();
+        let _ = self
+            .request_ignoring_response(Method::POST, &self.url("server/stop"), None)
+            .await;
 
-        // Poczekaj chwilę
-        std::thread::sleep(Duration::from_millis(1000));
+        // Poczekaj chwilę, nie blokując wątku środowiska asynchronicznego
+        tokio::time::sleep(Duration::from_millis(1000)).await;
 
         // Wyślij żądanie z nowym modelem - to powinno go załadować na GPU
         let test_body = CompletionsRequest {
@@ -124,17 +494,104 @@ impl LMStudioClient {
             temperature: 0.1,
             max_tokens: 1,
             stream: false,
+            stream_options: None,
         };
 
         // To żądanie spowoduje załadowanie modelu na GPU
-        let _ = self
-            .client
-            .post("http://localhost:1234/v1/chat/completions")
-            .header("Content-Type", "application/json")
-            .json(&test_body)
-            .send()?;
+        self.request_ignoring_response(
+            Method::POST,
+            &self.url("chat/completions"),
+            json_body(&test_body)?,
+        ).await
+    }
 
-        Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc::{self, Receiver};
+    use std::thread;
+
+    const FIRST: &str = "data: {\"choices\":[{\"delta\":{\"content\":\"Dzień \"}}]}\n\n";
+
+    // Serwer odsyła nagłówki z długością treści `length`, a potem tylko `body` i zamyka połączenie.
+    // Odebrane żądanie trafia do kanału
+    fn mock_lm_studio(body: String, length: usize) -> (LMStudioClient, Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, received) = mpsc::channel();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = sender.send(read_request(&mut stream));
+            let head = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nContent-Length: {}\r\n\r\n",
+                length
+            );
+            let _ = stream.write_all(head.as_bytes());
+            let _ = stream.write_all(body.as_bytes());
+        });
+        (LMStudioClient::with_base_url(&address), received)
+    }
+
+    // Czyta nagłówki i treść żądania o długości z Content-Length
+    fn read_request(stream: &mut impl Read) -> String {
+        let mut request = Vec::new();
+        let mut buffer = [0; 4096];
+        loop {
+            let text = String::from_utf8_lossy(&request).into_owned();
+            if let Some(end) = text.find("\r\n\r\n") {
+                let length = text[..end]
+                    .lines()
+                    .find_map(|line| line.to_lowercase().strip_prefix("content-length:").map(|v| v.trim().to_string()))
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .unwrap_or(0);
+                if request.len() >= end + 4 + length {
+                    return text;
+                }
+            }
+            match stream.read(&mut buffer) {
+                Ok(0) | Err(_) => return text,
+                Ok(read) => request.extend_from_slice(&buffer[..read]),
+            }
+        }
+    }
+
+    fn history() -> Vec<Message> {
+        vec![Message { role: "user".to_string(), content: "Cześć".to_string() }]
+    }
+
+    #[tokio::test]
+    async fn parses_final_line_without_newline() {
+        let body = format!("{}data: {{\"choices\":[{{\"delta\":{{\"content\":\"dobry\"}}}}]}}", FIRST);
+        let (client, _) = mock_lm_studio(body.clone(), body.len());
+
+        let full = client.send_message_stream("mock", history(), |_| {}).await.unwrap();
+        assert_eq!(full, "Dzień dobry");
+    }
+
+    #[tokio::test]
+    async fn reports_interrupted_stream() {
+        let (client, _) = mock_lm_studio(FIRST.to_string(), FIRST.len() + 100);
+
+        assert!(client.send_message_stream("mock", history(), |_| {}).await.is_err());
     }
 
-}
\ No newline at end of file
+    #[tokio::test]
+    async fn records_the_request_as_sent() {
+        let (client, received) = mock_lm_studio(FIRST.to_string(), FIRST.len());
+        client.send_message_stream("mock", history(), |_| {}).await.unwrap();
+
+        let sent = received.recv().unwrap();
+        let url = client.url("chat/completions");
+        let record = inspector::records().into_iter().find(|r| r.url == url).unwrap();
+        let body = record.request_body.unwrap();
+        assert!(sent.ends_with(&format!("\r\n\r\n{}", body)), "{}", sent);
+        for (name, value) in &record.request_headers {
+            assert!(sent.contains(&format!("{}: {}\r\n", name, value)), "{}: {}", name, value);
+        }
+        assert!(record.request_headers.iter().any(|(name, _)| name == "content-type"));
+    }
+}
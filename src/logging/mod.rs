@@ -0,0 +1,37 @@
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{Builder, Rotation};
+use tracing_subscriber::EnvFilter;
+
+// Folder z plikami logu; każdego dnia powstaje nowy plik
+pub const LOG_DIRECTORY: &str = "logs";
+const LOG_FILE_PREFIX: &str = "knowledge-app";
+const LOG_FILE_SUFFIX: &str = "log";
+// Liczba przechowywanych plików, czyli dni
+const MAX_LOG_FILES: usize = 7;
+
+// Zmienna środowiskowa z filtrem poziomów, np. KNOWLEDGE_APP_LOG=lm_studio=debug
+const FILTER_VARIABLE: &str = "KNOWLEDGE_APP_LOG";
+const DEFAULT_FILTER: &str = "info";
+
+/// Włącza zapis logów do pliku. Zwróconego strażnika trzeba trzymać do końca programu,
+/// inaczej ostatnie wpisy nie zostaną zapisane
+pub fn init() -> Result<WorkerGuard, String> {
+    let appender = Builder::new()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .filename_suffix(LOG_FILE_SUFFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(LOG_DIRECTORY)
+        .map_err(|e| format!("Nie udało się otworzyć pliku logu: {}", e))?;
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+
+    let filter = EnvFilter::try_from_env(FILTER_VARIABLE).unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+    tracing_subscriber::fmt()
+        .with_writer(writer)
+        .with_ansi(false)
+        .with_env_filter(filter)
+        .try_init()
+        .map_err(|e| format!("Nie udało się włączyć logowania: {}", e))?;
+
+    Ok(guard)
+}
@@ -1,79 +1,251 @@
 use iced::{
-    executor, theme, window, Application, Command, Element, Length, Settings, Size,
+    executor, theme, window, Application, Command, Element, Settings, Size, Subscription,
 };
-use iced::widget::{column, container, row};
-use serde::{Deserialize, Serialize};
-use std::fs;
-use std::path::Path;
-use chrono::Local;
+use iced::widget::{column, row, scrollable, text_editor};
+use iced::keyboard::{self, key};
+use iced::time::Instant;
 
 // Deklaracja modułów
 mod ui;
-mod lm_studio_client;
+mod cli;
+mod message;
+mod watcher;
+
+// Importy z biblioteki aplikacji
+use knowledge_app::{chat, commands, conversation, documents, export, inspector, knowledge, lm_studio_client, logging, memory, storage, templates};
+use knowledge_app::commands::SlashCommand;
+use knowledge_app::chat::{ContextOptions, Session};
+use knowledge_app::conversation::{default_collections, Attachment, ChatMessage, PendingAttachment, SavedConversation, TrashedConversation, DEFAULT_CONVERSATION_NAME};
+use knowledge_app::knowledge::{CollectionSettings, CollectionStats, KnowledgeBase};
+use knowledge_app::lm_studio_client::{CompletionStats, LMStudioClient, Message as LMMessage};
+use knowledge_app::memory::{MemoryEntry, MemoryStore};
+use knowledge_app::export::ExportFormat;
+use knowledge_app::import::{self, ImportCandidate};
+use knowledge_app::server::ApiServer;
+use knowledge_app::settings::{AppSettings, TagSettings, ThemePreference};
+use knowledge_app::i18n::{self, t, tf};
+use knowledge_app::templates::PromptTemplate;
+use knowledge_app::inspector::RequestRecord;
 
 // Importy z modułów
-use ui::{create_sidebar, create_chat_area, create_save_dialog};
-use lm_studio_client::{LMStudioClient, Message as LMMessage};
+pub use message::{CollectionChange, Comparison, ComparisonAnswer, Confirmation, Dialog, Message};
+use ui::markdown::MarkdownCache;
+use ui::modal::Modal;
+use ui::palette::{self, Palette};
+use ui::{create_sidebar, create_chat_area, create_dialog, create_collections_panel, create_memory_panel, create_import_panel, create_trash_panel, create_inspector_panel, create_undo_toast, TAG_COLOR_COUNT};
+
+// Czas, przez który po usunięciu rozmowy widać przycisk cofnięcia
+const UNDO_TOAST_SECONDS: u64 = 8;
+
+// Co ile sekund sprawdzać, czy system przełączył jasny/ciemny motyw
+const SYSTEM_THEME_POLL_SECONDS: u64 = 10;
+
+// Odstęp odświeżania wskaźnika generowania odpowiedzi
+const GENERATION_TICK_MILLIS: u64 = 250;
+
+// Względne przewinięcie, od którego lista uznawana jest za przewiniętą na sam dół
+const FOLLOW_LATEST_THRESHOLD: f32 = 0.99;
+
+// Co ile sekund otwarty inspektor pobiera nowe żądania
+const INSPECTOR_REFRESH_SECONDS: u64 = 1;
+
+// Liczba zapamiętanych poleceń dostępnych strzałką w górę
+const INPUT_HISTORY_LIMIT: usize = 100;
 
 // Główna struktura aplikacji
 #[derive(Debug)]
 pub struct ChatApp {
     messages: Vec<ChatMessage>,
-    input_value: String,
+    input_editor: text_editor::Content,
+    // Wysłane polecenia; `history_position` wskazuje przywołane, a `history_draft` to tekst sprzed przywołania
+    input_history: Vec<String>,
+    history_position: Option<usize>,
+    history_draft: String,
+    // Wciśnięte klawisze modyfikujące; Shift+Enter wstawia nową linię zamiast wysyłać
+    modifiers: keyboard::Modifiers,
+    // Oczekiwanie na odpowiedź; `generation` odróżnia bieżące żądanie od zatrzymanych
+    generating: bool,
+    generation: u64,
+    // Początek bieżącego żądania i ostatni takt zegara, do licznika czasu oczekiwania
+    generation_started: Instant,
+    generation_now: Instant,
+    conversation_search: String,
+    // Wiadomość pod kursorem, przy której widać przyciski akcji
+    hovered_message: Option<usize>,
+    // Widoczny obszar listy wiadomości; renderowane są tylko wiadomości w jego pobliżu
+    messages_viewport: Size,
+    messages_scroll_offset: f32,
+    // Lista przewija się do nowych wiadomości, dopóki użytkownik nie przewinie jej w górę
+    follow_latest: bool,
+    markdown_cache: MarkdownCache,
+    compare_mode: bool,
+    compare_models: Vec<String>,
+    comparison: Option<Comparison>,
+    // Ustawienia bieżącej rozmowy zmieniane poleceniami /system i /temp
+    system_prompt: Option<String>,
+    temperature: Option<f32>,
+    // Wynik ostatniego polecenia z ukośnikiem albo błąd jego składni
+    command_status: Option<String>,
+    templates: Vec<PromptTemplate>,
+    // Zmienne wypełnianego szablonu z wpisanymi wartościami, w kolejności z treści
+    template_values: Vec<(String, String)>,
+    template_name_input: String,
+    template_description_input: String,
+    template_editor: text_editor::Content,
+    templates_status: Option<String>,
     current_conversation_name: String,
     saved_conversations: Vec<SavedConversation>,
-    show_save_dialog: bool,
+    dialog: Option<Dialog>,
     save_name_input: String,
+    pending_attachments: Vec<PendingAttachment>,
+    attachment_error: Option<String>,
     lm_client: LMStudioClient,
     model: String,
+    embedding_model: String,
+    conversation_collections: Vec<String>,
+    knowledge_enabled: bool,
+    knowledge_status: Option<String>,
+    settings: AppSettings,
+    indexing: bool,
+    resync_pending: bool,
+    index_status: Option<String>,
+    show_collections_panel: bool,
+    new_collection_name: String,
+    new_collection_model: String,
+    selected_collection: Option<String>,
+    collection_stats: Option<CollectionStats>,
+    renaming_collection: Option<String>,
+    rename_collection_input: String,
+    collections_status: Option<String>,
+    show_memory_panel: bool,
+    memories: Vec<MemoryEntry>,
+    editing_memory: Option<String>,
+    memory_input: String,
+    memory_status: Option<String>,
+    api_server: Option<ApiServer>,
+    api_server_status: Option<String>,
+    conversation_menu: Option<usize>,
+    export_status: Option<String>,
+    import_candidates: Vec<ImportCandidate>,
+    import_status: Option<String>,
+    renaming_conversation: Option<usize>,
+    rename_conversation_input: String,
+    new_folder_input: String,
+    new_tag_input: String,
+    tag_filter: Option<String>,
+    // `title_request` odróżnia tytuł bieżącej rozmowy od zamówionych przed przełączeniem
+    generating_title: bool,
+    title_request: u64,
+    title_error: Option<String>,
+    trash: Vec<TrashedConversation>,
+    show_trash_panel: bool,
+    trash_retention_input: String,
+    // Podgląd żądań do LM Studio: kopia zapisów z inspektora i wybrane żądanie
+    show_inspector_panel: bool,
+    inspector_records: Vec<RequestRecord>,
+    selected_request: Option<u64>,
+    // Nazwa ostatnio usuniętej rozmowy, którą można jeszcze przywrócić
+    undo_toast: Option<String>,
+    toast_generation: u64,
+    available_models: Vec<String>,
+    loaded_model: Option<String>,
+    models_status: Option<String>,
+    api_port_input: String,
+    // Wykryty ciemny motyw systemu, używany przy ustawieniu "Systemowy"
+    system_dark: bool,
 }
 
 impl Default for ChatApp {
     fn default() -> Self {
         Self {
             messages: Vec::new(),
-            input_value: String::new(),
-            current_conversation_name: "Nowa rozmowa".to_string(),
+            input_editor: text_editor::Content::new(),
+            input_history: Vec::new(),
+            history_position: None,
+            history_draft: String::new(),
+            modifiers: keyboard::Modifiers::default(),
+            generating: false,
+            generation: 0,
+            generation_started: Instant::now(),
+            generation_now: Instant::now(),
+            conversation_search: String::new(),
+            hovered_message: None,
+            messages_viewport: Size::new(900.0, 600.0),
+            messages_scroll_offset: 0.0,
+            follow_latest: true,
+            markdown_cache: MarkdownCache::default(),
+            compare_mode: false,
+            compare_models: Vec::new(),
+            comparison: None,
+            system_prompt: None,
+            temperature: None,
+            command_status: None,
+            templates: Vec::new(),
+            template_values: Vec::new(),
+            template_name_input: String::new(),
+            template_description_input: String::new(),
+            template_editor: text_editor::Content::new(),
+            templates_status: None,
+            current_conversation_name: DEFAULT_CONVERSATION_NAME.to_string(),
             saved_conversations: Vec::new(),
-            show_save_dialog: false,
+            dialog: None,
             save_name_input: String::new(),
+            pending_attachments: Vec::new(),
+            attachment_error: None,
             lm_client: LMStudioClient::new(),
-            model: "bielik-11b-v2.3-instruct".to_string()
+            model: lm_studio_client::DEFAULT_MODEL.to_string(),
+            embedding_model: knowledge::DEFAULT_EMBEDDING_MODEL.to_string(),
+            conversation_collections: default_collections(),
+            knowledge_enabled: false,
+            knowledge_status: None,
+            settings: AppSettings::default(),
+            indexing: false,
+            resync_pending: false,
+            index_status: None,
+            show_collections_panel: false,
+            new_collection_name: String::new(),
+            new_collection_model: knowledge::DEFAULT_EMBEDDING_MODEL.to_string(),
+            selected_collection: None,
+            collection_stats: None,
+            renaming_collection: None,
+            rename_collection_input: String::new(),
+            collections_status: None,
+            show_memory_panel: false,
+            memories: Vec::new(),
+            editing_memory: None,
+            memory_input: String::new(),
+            memory_status: None,
+            api_server: None,
+            api_server_status: None,
+            conversation_menu: None,
+            export_status: None,
+            import_candidates: Vec::new(),
+            import_status: None,
+            renaming_conversation: None,
+            rename_conversation_input: String::new(),
+            new_folder_input: String::new(),
+            new_tag_input: String::new(),
+            tag_filter: None,
+            generating_title: false,
+            title_request: 0,
+            title_error: None,
+            trash: Vec::new(),
+            show_trash_panel: false,
+            trash_retention_input: String::new(),
+            show_inspector_panel: false,
+            inspector_records: Vec::new(),
+            selected_request: None,
+            undo_toast: None,
+            toast_generation: 0,
+            available_models: Vec::new(),
+            loaded_model: None,
+            models_status: None,
+            api_port_input: String::new(),
+            system_dark: false,
         }
     }
 }
 
-// Struktura dla wiadomości w czacie
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ChatMessage {
-    pub content: String,
-    pub is_user: bool,
-    pub timestamp: String,
-}
-
-// Struktura dla zapisanych rozmów
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SavedConversation {
-    pub name: String,
-    pub messages: Vec<ChatMessage>,
-}
-
-// Enum dla komunikatów w aplikacji
-#[derive(Debug, Clone)]
-pub enum Message {
-    InputChanged(String),
-    SendMessage,
-    NewConversation,
-    LoadConversation(usize),
-    DeleteConversation(usize),
-    ShowSaveDialog,
-    HideSaveDialog,
-    SaveNameChanged(String),
-    ConfirmSave,
-    ClearChat,
-    MessageReceived(Result<String, String>),
-}
-
 impl Application for ChatApp {
     type Message = Message;
     type Theme = theme::Theme;
@@ -85,114 +257,773 @@ impl Application for ChatApp {
         
         // Wczytaj zapisane rozmowy
         app.load_conversations();
+        app.load_settings();
+        app.trash = storage::load_trash(app.settings.trash_retention_days);
+        app.templates = storage::load_templates();
+        app.trash_retention_input = app.settings.trash_retention_days.to_string();
+        app.api_port_input = app.settings.api_server_port.to_string();
+        app.system_dark = palette::system_prefers_dark();
+
+        if app.settings.api_server_enabled {
+            app.start_api_server();
+        }
+
+        // Dogoń zmiany w obserwowanych folderach, które zaszły przy wyłączonej aplikacji
+        let command = app.sync_watched_directories();
         
-        (app, Command::none())
+        (app, command)
     }
 
     fn title(&self) -> String {
-        "Chat z LM Studio".to_string()
+        t("app.title").to_string()
     }
 
     fn update(&mut self, message: Message) -> Command<Message> {
         match message {
-            Message::InputChanged(value) => {
-                self.input_value = value;
+            Message::EditorAction(action) => {
+                match action {
+                    // Enter wysyła, Shift+Enter łamie linię
+                    text_editor::Action::Edit(text_editor::Edit::Enter) if !self.modifiers.shift() => {
+                        return self.update(Message::SendMessage);
+                    }
+                    // Strzałki na pierwszej i ostatniej linii przeglądają wysłane polecenia
+                    text_editor::Action::Move(text_editor::Motion::Up)
+                        if self.input_editor.cursor_position().0 == 0 && !self.input_history.is_empty() =>
+                    {
+                        self.recall_prompt(true);
+                    }
+                    text_editor::Action::Move(text_editor::Motion::Down)
+                        if self.history_position.is_some()
+                            && self.input_editor.cursor_position().0 + 1 >= self.input_editor.line_count() =>
+                    {
+                        self.recall_prompt(false);
+                    }
+                    action => {
+                        if action.is_edit() {
+                            self.history_position = None;
+                            self.command_status = None;
+                        }
+                        let was_command = self.input_editor.text().starts_with('/');
+                        self.input_editor.perform(action);
+                        // Lista modeli do podpowiedzi /model pobierana jest przy rozpoczęciu polecenia
+                        if !was_command && self.available_models.is_empty() && self.input_editor.text().starts_with('/') {
+                            return self.fetch_models();
+                        }
+                    }
+                }
+                Command::none()
+            }
+            Message::ModifiersChanged(modifiers) => {
+                self.modifiers = modifiers;
                 Command::none()
             }
             Message::SendMessage => {
-                if !self.input_value.trim().is_empty() {
+                let typed = self.input_editor.text().trim_end().to_string();
+                if let Some(command) = commands::parse(&typed) {
+                    return self.run_command(typed, command);
+                }
+                let input = commands::unescape(&typed).to_string();
+                if !input.trim().is_empty() && !self.generating {
+                    let attachments: Vec<PendingAttachment> = self.pending_attachments.drain(..).collect();
                     let user_message = ChatMessage {
-                        content: self.input_value.clone(),
+                        content: input.clone(),
                         is_user: true,
-                        timestamp: Local::now().format("%H:%M").to_string(),
+                        timestamp: conversation::now(),
+                        attachments: attachments.iter().map(|a| a.attachment.clone()).collect(),
+                        pinned: false,
+                        stats: None,
                     };
                     
                     self.messages.push(user_message);
-                    self.input_value.clear();
+                    self.input_editor = text_editor::Content::new();
+                    self.attachment_error = None;
+                    self.command_status = None;
+                    self.remember_prompt(typed);
+                    self.generating = true;
+                    self.generation += 1;
+                    self.generation_started = Instant::now();
+                    self.generation_now = self.generation_started;
+                    let generation = self.generation;
+                    self.comparison = None;
                     
                     // Przygotuj historię wiadomości dla LM Studio
-                    let mut history = Vec::new();
-                    for msg in &self.messages {
-                        history.push(LMMessage {
-                            role: if msg.is_user { "user" } else { "assistant" }.to_string(),
-                            content: msg.content.clone(),
+                    let mut history = chat::build_history(&self.messages, &attachments);
+                    if let Some(prompt) = &self.system_prompt {
+                        history.insert(0, LMMessage {
+                            role: "system".to_string(),
+                            content: prompt.clone(),
                         });
                     }
+                    let query = self.messages.last().map(|m| m.content.clone()).unwrap_or_default();
+
+                    // W trybie porównania pytanie trafia do wszystkich zaznaczonych modeli
+                    if self.compare_mode && self.compare_models.len() >= 2 {
+                        let requests = self.start_comparison(history, query);
+                        return Command::batch([requests, self.scroll_to_latest()]);
+                    }
                     
-                    // Sklonuj potrzebne zmienne przed move
-                    let client = self.lm_client.clone();
-                    let model = self.model.clone(); // Sklonuj model zamiast borrowować
-                    
-                    return Command::perform(
-                        async move {
-                            match client.send_message(&model, history) {
-                                Ok(response) => Ok(response),
-                                Err(e) => Err(format!("Błąd komunikacji z LM Studio: {}", e)),
-                            }
-                        },
-                        Message::MessageReceived,
+                    let session = self.session(&self.model);
+                    let request = Command::perform(
+                        async move { session.reply(&query, history).await },
+                        move |result| Message::MessageReceived(generation, result),
                     );
+                    return Command::batch([request, self.scroll_to_latest()]);
                 }
                 Command::none()
             }
-            Message::MessageReceived(result) => {
-                let content = match result {
-                    Ok(response) => response,
-                    Err(error) => error,
+            Message::GenerationTick(now) => {
+                self.generation_now = now;
+                Command::none()
+            }
+            Message::StopGeneration => {
+                self.stop_generation();
+                Command::none()
+            }
+            Message::ConversationSearchChanged(value) => {
+                self.conversation_search = value;
+                Command::none()
+            }
+            Message::FocusConversationSearch => iced::widget::text_input::focus(ui::conversation_search_id()),
+            Message::MessageReceived(generation, result) => {
+                // Odpowiedź na zatrzymane żądanie albo z poprzedniej rozmowy
+                if generation != self.generation || !self.generating {
+                    return Command::none();
+                }
+                self.generating = false;
+
+                let succeeded = result.is_ok();
+                let (content, stats) = match result {
+                    Ok((response, stats)) => (response, Some(stats)),
+                    Err(error) => (error, None),
                 };
                 
                 let ai_message = ChatMessage {
                     content,
                     is_user: false,
-                    timestamp: Local::now().format("%H:%M").to_string(),
+                    timestamp: conversation::now(),
+                    attachments: Vec::new(),
+                    pinned: false,
+                    stats,
                 };
                 self.messages.push(ai_message);
+
+                if succeeded {
+                    self.reply_added()
+                } else if self.follow_latest {
+                    self.scroll_to_latest()
+                } else {
+                    Command::none()
+                }
+            }
+            Message::ToggleCompareMode(enabled) => {
+                self.compare_mode = enabled;
+                if enabled && self.available_models.is_empty() {
+                    return self.fetch_models();
+                }
+                Command::none()
+            }
+            Message::ToggleCompareModel(model, selected) => {
+                self.compare_models.retain(|m| *m != model);
+                if selected {
+                    self.compare_models.push(model);
+                }
+                Command::none()
+            }
+            Message::ToggleCompareSequential(sequential) => {
+                self.settings.compare_sequential = sequential;
+                self.save_settings();
+                Command::none()
+            }
+            Message::ComparisonAnswered(generation, index, result) => {
+                if generation != self.generation || !self.generating {
+                    return Command::none();
+                }
+                let Some(comparison) = self.comparison.as_mut() else {
+                    return Command::none();
+                };
+                if let Some(answer) = comparison.answers.get_mut(index) {
+                    answer.result = Some(result);
+                }
+
+                let next = comparison.answers.iter().position(|a| a.result.is_none());
+                match next {
+                    Some(next) if comparison.sequential => self.request_comparison_answer(next),
+                    Some(_) => Command::none(),
+                    None => {
+                        self.generating = false;
+                        Command::none()
+                    }
+                }
+            }
+            Message::KeepComparisonAnswer(index) => {
+                let Some(comparison) = self.comparison.take() else {
+                    return Command::none();
+                };
+                let Some(ComparisonAnswer { model, result: Some(Ok((content, stats))) }) =
+                    comparison.answers.into_iter().nth(index)
+                else {
+                    return Command::none();
+                };
+                // Pozostałe odpowiedzi nie są już potrzebne
+                self.stop_generation();
+
+                self.messages.push(ChatMessage {
+                    content,
+                    is_user: false,
+                    timestamp: conversation::now(),
+                    attachments: Vec::new(),
+                    pinned: false,
+                    stats: Some(stats),
+                });
+                *self.settings.comparison_votes.entry(model).or_insert(0) += 1;
+                self.save_settings();
+
+                self.reply_added()
+            }
+            Message::ApplySuggestion(completion) => {
+                self.input_editor = text_editor::Content::with_text(&completion);
+                self.input_editor.perform(text_editor::Action::Move(text_editor::Motion::DocumentEnd));
+                self.history_position = None;
+                self.command_status = None;
+                Command::none()
+            }
+            Message::SetSystemPrompt(prompt) => {
+                self.command_status = Some(match &prompt {
+                    Some(_) => t("commands.system_set").to_string(),
+                    None => t("commands.system_cleared").to_string(),
+                });
+                self.system_prompt = prompt;
+                Command::none()
+            }
+            Message::SetTemperature(temperature) => {
+                self.temperature = temperature;
+                self.command_status = Some(tf("commands.temperature_set", &[&self.temperature()]));
+                Command::none()
+            }
+            Message::IngestPath(path) => {
+                let collection = self
+                    .conversation_collections
+                    .first()
+                    .cloned()
+                    .unwrap_or_else(|| knowledge::DEFAULT_COLLECTION.to_string());
+                let client = self.lm_client.clone();
+                let embedding_model = self.collection_settings(&collection).embedding_model;
+                self.knowledge_status = Some(t("knowledge.indexing").to_string());
+                self.command_status = Some(tf("commands.ingesting", &[&path, &collection]));
+
+                Command::perform(
+                    async move {
+                        // Folder jest indeksowany razem ze wszystkimi obsługiwanymi plikami
+                        let files = knowledge::expand_paths(&[std::path::PathBuf::from(path)]);
+                        if files.is_empty() {
+                            return Some(Err(t("commands.no_documents").to_string()));
+                        }
+                        Some(ingest(client, embedding_model, collection, files).await)
+                    },
+                    Message::DocumentsIngested,
+                )
+            }
+            Message::ShowTemplates => {
+                self.dialog = Some(Dialog::Templates);
+                self.templates_status = None;
+                Command::none()
+            }
+            Message::UseTemplate(index) => {
+                let Some(template) = self.templates.get(index) else {
+                    return Command::none();
+                };
+                let variables = template.variables();
+                // Szablon bez zmiennych od razu trafia do pola wiadomości
+                if variables.is_empty() {
+                    let content = template.content.clone();
+                    self.dialog = None;
+                    self.append_to_input(&content);
+                    return Command::none();
+                }
+
+                let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+                let reads_clipboard = variables.iter().any(|v| v == templates::CLIPBOARD_VARIABLE);
+                self.template_values = variables
+                    .into_iter()
+                    .map(|variable| {
+                        let value = if variable == templates::DATE_VARIABLE { today.clone() } else { String::new() };
+                        (variable, value)
+                    })
+                    .collect();
+                self.dialog = Some(Dialog::FillTemplate(index));
+
+                if reads_clipboard {
+                    iced::clipboard::read(Message::TemplateClipboardRead)
+                } else {
+                    Command::none()
+                }
+            }
+            Message::TemplateValueChanged(variable, value) => {
+                if let Some(entry) = self.template_values.iter_mut().find(|(name, _)| *name == variable) {
+                    entry.1 = value;
+                }
+                Command::none()
+            }
+            Message::TemplateClipboardRead(content) => {
+                self.update(Message::TemplateValueChanged(
+                    templates::CLIPBOARD_VARIABLE.to_string(),
+                    content.unwrap_or_default(),
+                ))
+            }
+            Message::PickTemplateFile => {
+                Command::perform(
+                    async {
+                        let file = rfd::AsyncFileDialog::new()
+                            .add_filter(t("knowledge.documents_filter"), documents::SUPPORTED_EXTENSIONS)
+                            .pick_file()
+                            .await?;
+                        Some(documents::read_document_text(file.path()))
+                    },
+                    Message::TemplateFilePicked,
+                )
+            }
+            Message::TemplateFilePicked(result) => {
+                match result {
+                    Some(Ok(content)) => {
+                        self.templates_status = None;
+                        return self.update(Message::TemplateValueChanged(
+                            templates::SELECTED_FILE_VARIABLE.to_string(),
+                            content,
+                        ));
+                    }
+                    Some(Err(error)) => self.templates_status = Some(error),
+                    None => {}
+                }
+                Command::none()
+            }
+            Message::ApplyTemplate(send) => {
+                let Some(Dialog::FillTemplate(index)) = self.dialog else {
+                    return Command::none();
+                };
+                let Some(template) = self.templates.get(index) else {
+                    return Command::none();
+                };
+                let values = std::mem::take(&mut self.template_values).into_iter().collect();
+                let content = template.render(&values);
+                self.dialog = None;
+                self.templates_status = None;
+                self.append_to_input(&content);
+
+                if send {
+                    self.update(Message::SendMessage)
+                } else {
+                    Command::none()
+                }
+            }
+            Message::NewTemplate => {
+                self.template_name_input.clear();
+                self.template_description_input.clear();
+                self.template_editor = text_editor::Content::new();
+                self.templates_status = None;
+                self.dialog = Some(Dialog::EditTemplate(None));
+                Command::none()
+            }
+            Message::EditTemplate(index) => {
+                if let Some(template) = self.templates.get(index) {
+                    self.template_name_input = template.name.clone();
+                    self.template_description_input = template.description.clone();
+                    self.template_editor = text_editor::Content::with_text(&template.content);
+                    self.templates_status = None;
+                    self.dialog = Some(Dialog::EditTemplate(Some(index)));
+                }
+                Command::none()
+            }
+            Message::TemplateNameChanged(name) => {
+                self.template_name_input = name;
+                Command::none()
+            }
+            Message::TemplateDescriptionChanged(description) => {
+                self.template_description_input = description;
+                Command::none()
+            }
+            Message::TemplateEditorAction(action) => {
+                self.template_editor.perform(action);
+                Command::none()
+            }
+            Message::SaveTemplate => {
+                let Some(Dialog::EditTemplate(editing)) = self.dialog else {
+                    return Command::none();
+                };
+                let template = PromptTemplate {
+                    name: self.template_name_input.trim().to_string(),
+                    description: self.template_description_input.trim().to_string(),
+                    content: self.template_editor.text().trim_end().to_string(),
+                };
+                if template.name.is_empty() || template.content.trim().is_empty() {
+                    self.templates_status = Some(t("templates.missing_fields").to_string());
+                    return Command::none();
+                }
+                let name_taken = self
+                    .templates
+                    .iter()
+                    .enumerate()
+                    .any(|(index, existing)| existing.name == template.name && Some(index) != editing);
+                if name_taken {
+                    self.templates_status = Some(tf("templates.name_taken", &[&template.name]));
+                    return Command::none();
+                }
+
+                match editing.and_then(|index| self.templates.get_mut(index)) {
+                    Some(existing) => *existing = template,
+                    None => self.templates.push(template),
+                }
+                storage::save_templates(&self.templates);
+                self.templates_status = None;
+                self.dialog = Some(Dialog::Templates);
+                Command::none()
+            }
+            Message::DeleteTemplate(index) => {
+                if index < self.templates.len() {
+                    self.templates.remove(index);
+                    storage::save_templates(&self.templates);
+                }
+                self.dialog = Some(Dialog::Templates);
+                Command::none()
+            }
+            Message::ImportTemplates => {
+                Command::perform(
+                    async {
+                        let files = rfd::AsyncFileDialog::new()
+                            .add_filter("JSON", &["json"])
+                            .pick_files()
+                            .await?;
+
+                        let mut imported = Vec::new();
+                        for file in &files {
+                            let result = std::fs::read_to_string(file.path())
+                                .map_err(|e| tf("templates.read_failed", &[&e]))
+                                .and_then(|content| templates::import(&content));
+                            match result {
+                                Ok(templates) => imported.extend(templates),
+                                Err(error) => return Some(Err(error)),
+                            }
+                        }
+                        Some(Ok(imported))
+                    },
+                    Message::TemplatesImported,
+                )
+            }
+            Message::TemplatesImported(result) => {
+                match result {
+                    Some(Ok(imported)) => {
+                        let added = templates::merge(&mut self.templates, imported);
+                        storage::save_templates(&self.templates);
+                        self.templates_status = Some(tf("templates.imported", &[&added]));
+                    }
+                    Some(Err(error)) => self.templates_status = Some(error),
+                    None => {}
+                }
+                Command::none()
+            }
+            Message::ExportTemplates => {
+                if self.templates.is_empty() {
+                    return Command::none();
+                }
+                let templates = self.templates.clone();
+
+                Command::perform(
+                    async move {
+                        let file = rfd::AsyncFileDialog::new()
+                            .set_file_name(storage::TEMPLATES_FILE)
+                            .add_filter("JSON", &["json"])
+                            .save_file()
+                            .await?;
+
+                        let result = templates::export(&templates).and_then(|content| {
+                            std::fs::write(file.path(), content)
+                                .map(|_| tf("export.done", &[&file.path().display()]))
+                                .map_err(|e| tf("export.write_failed", &[&e]))
+                        });
+                        Some(result)
+                    },
+                    Message::TemplatesExported,
+                )
+            }
+            Message::TemplatesExported(result) => {
+                if let Some(status) = result {
+                    self.templates_status = Some(status.unwrap_or_else(|error| error));
+                }
                 Command::none()
             }
             Message::NewConversation => {
+                self.stop_generation();
+                self.comparison = None;
                 self.messages.clear();
-                self.current_conversation_name = "Nowa rozmowa".to_string();
-                Command::none()
+                self.conversation_collections = default_collections();
+                self.current_conversation_name = DEFAULT_CONVERSATION_NAME.to_string();
+                self.system_prompt = None;
+                self.temperature = None;
+                self.cancel_title();
+                self.scroll_to_latest()
             }
             Message::LoadConversation(index) => {
+                self.stop_generation();
+                self.comparison = None;
                 if let Some(conversation) = self.saved_conversations.get(index) {
                     self.messages = conversation.messages.clone();
+                    self.conversation_collections = conversation.collections.clone();
                     self.current_conversation_name = conversation.name.clone();
+                    self.system_prompt = conversation.system_prompt.clone();
+                    self.temperature = conversation.temperature;
+                    self.cancel_title();
                 }
+                self.scroll_to_latest()
+            }
+            Message::MessagesScrolled(viewport) => {
+                self.messages_viewport = viewport.bounds().size();
+                self.messages_scroll_offset = viewport.absolute_offset().y;
+                // Przewinięcie w górę wyłącza podążanie za nowymi wiadomościami, powrót na dół je włącza
+                let content_fits = viewport.content_bounds().height <= viewport.bounds().height;
+                self.follow_latest = content_fits || viewport.relative_offset().y >= FOLLOW_LATEST_THRESHOLD;
                 Command::none()
             }
+            Message::JumpToLatest => self.scroll_to_latest(),
             Message::DeleteConversation(index) => {
-                if index < self.saved_conversations.len() {
-                    self.saved_conversations.remove(index);
-                    self.save_conversations();
+                self.conversation_menu = None;
+                self.renaming_conversation = None;
+                if index >= self.saved_conversations.len() {
+                    return Command::none();
+                }
+
+                // Rozmowa trafia do kosza, a przez chwilę można cofnąć usunięcie
+                let conversation = self.saved_conversations.remove(index);
+                self.undo_toast = Some(conversation.name.clone());
+                self.trash.push(TrashedConversation::new(conversation));
+                self.save_conversations();
+                storage::save_trash(&self.trash);
+
+                self.toast_generation += 1;
+                let generation = self.toast_generation;
+                Command::perform(
+                    tokio::time::sleep(std::time::Duration::from_secs(UNDO_TOAST_SECONDS)),
+                    move |_| Message::HideUndoToast(generation),
+                )
+            }
+            Message::UndoDelete => {
+                if let Some(name) = self.undo_toast.take()
+                    && let Some(index) = self.trash.iter().rposition(|t| t.conversation.name == name)
+                {
+                    self.restore_from_trash(index);
                 }
                 Command::none()
             }
-            Message::ShowSaveDialog => {
-                self.show_save_dialog = true;
-                self.save_name_input = self.current_conversation_name.clone();
+            Message::HideUndoToast(generation) => {
+                // Późniejsze usunięcie pokazało już nowy komunikat
+                if generation == self.toast_generation {
+                    self.undo_toast = None;
+                }
+                Command::none()
+            }
+            Message::DismissUndoToast => {
+                self.undo_toast = None;
+                Command::none()
+            }
+            Message::ShowTrashPanel => {
+                self.show_trash_panel = true;
+                self.show_collections_panel = false;
+                self.show_memory_panel = false;
+                self.show_inspector_panel = false;
+                Command::none()
+            }
+            Message::HideTrashPanel => {
+                self.show_trash_panel = false;
+                Command::none()
+            }
+            Message::RestoreConversation(index) => {
+                if index < self.trash.len() {
+                    self.restore_from_trash(index);
+                }
+                Command::none()
+            }
+            Message::PurgeConversation(index) => {
+                if index < self.trash.len() {
+                    self.trash.remove(index);
+                    storage::save_trash(&self.trash);
+                }
+                Command::none()
+            }
+            Message::EmptyTrash => {
+                self.trash.clear();
+                self.undo_toast = None;
+                storage::save_trash(&self.trash);
+                Command::none()
+            }
+            Message::ShowInspectorPanel => {
+                self.show_inspector_panel = true;
+                self.show_collections_panel = false;
+                self.show_memory_panel = false;
+                self.show_trash_panel = false;
+                self.update(Message::RefreshInspector)
+            }
+            Message::HideInspectorPanel => {
+                self.show_inspector_panel = false;
+                Command::none()
+            }
+            Message::RefreshInspector => {
+                self.inspector_records = inspector::records();
+                Command::none()
+            }
+            Message::ClearInspector => {
+                inspector::clear();
+                self.inspector_records.clear();
+                self.selected_request = None;
+                Command::none()
+            }
+            Message::SelectRequest(id) => {
+                self.selected_request = Some(id);
+                Command::none()
+            }
+            Message::CopyRequestAsCurl(id) => match self.inspector_records.iter().find(|r| r.id == id) {
+                Some(record) => iced::clipboard::write(record.to_curl()),
+                None => Command::none(),
+            },
+            Message::CopyResponse(id) => {
+                match self.inspector_records.iter().find(|r| r.id == id).and_then(|r| r.response_body.clone()) {
+                    Some(body) => iced::clipboard::write(body),
+                    None => Command::none(),
+                }
+            }
+            Message::TrashRetentionChanged(value) => {
+                if value.is_empty() || value.chars().all(|c| c.is_ascii_digit()) {
+                    if let Ok(days) = value.parse() {
+                        self.settings.trash_retention_days = days;
+                        self.save_settings();
+                        self.trash.retain(|item| !item.is_expired(days));
+                        storage::save_trash(&self.trash);
+                    }
+                    self.trash_retention_input = value;
+                }
                 Command::none()
             }
-            Message::HideSaveDialog => {
-                self.show_save_dialog = false;
+            Message::ShowSaveDialog => {
+                self.dialog = Some(Dialog::Save);
+                self.save_name_input = self.conversation_display_name().to_string();
+                iced::widget::text_input::focus(ui::save_name_input_id())
+            }
+            Message::CloseDialog => {
+                self.dialog = None;
                 self.save_name_input.clear();
+                self.template_values.clear();
+                Command::none()
+            }
+            Message::RequestConfirmation(confirmation) => {
+                self.dialog = Some(Dialog::Confirm(confirmation));
+                Command::none()
+            }
+            Message::Confirm => {
+                match self.dialog.take() {
+                    Some(Dialog::Confirm(confirmation)) => self.update(*confirmation.action),
+                    _ => Command::none(),
+                }
+            }
+            Message::ShowSettings => {
+                self.dialog = Some(Dialog::Settings);
+                self.api_port_input = self.settings.api_server_port.to_string();
+                Command::none()
+            }
+            Message::ApiPortChanged(value) => {
+                if value.chars().all(|c| c.is_ascii_digit()) {
+                    if let Ok(port) = value.parse::<u16>()
+                        && port > 0
+                    {
+                        self.settings.api_server_port = port;
+                        self.save_settings();
+                    }
+                    self.api_port_input = value;
+                }
+                Command::none()
+            }
+            Message::ShowModelManager => {
+                self.dialog = Some(Dialog::Models);
+                self.fetch_models()
+            }
+            Message::ModelsLoaded(result) => {
+                match result {
+                    Ok((models, loaded)) => {
+                        self.available_models = models;
+                        self.loaded_model = loaded;
+                        self.models_status = None;
+                    }
+                    Err(error) => self.models_status = Some(error),
+                }
+                Command::none()
+            }
+            Message::SelectModel(model) => {
+                self.settings.chat_model = Some(model.clone());
+                self.model = model;
+                self.save_settings();
+                Command::none()
+            }
+            Message::ReloadModel => {
+                self.models_status = Some(tf("models.reloading", &[&self.model]));
+                let client = self.lm_client.clone();
+                let model = self.model.clone();
+
+                Command::perform(
+                    async move {
+                        client
+                            .force_reload_model(&model)
+                            .await
+                            .map_err(|e| tf("models.reload_failed", &[&e]))
+                    },
+                    Message::ModelReloaded,
+                )
+            }
+            Message::ModelReloaded(result) => {
+                match result {
+                    Ok(()) => {
+                        self.loaded_model = Some(self.model.clone());
+                        self.models_status = Some(t("models.reloaded").to_string());
+                    }
+                    Err(error) => self.models_status = Some(error),
+                }
+                Command::none()
+            }
+            Message::SetLanguage(language) => {
+                i18n::set_language(language);
+                self.settings.language = language;
+                self.save_settings();
                 Command::none()
             }
+            Message::SetThemePreference(preference) => {
+                self.settings.theme = preference;
+                self.save_settings();
+                Command::none()
+            }
+            Message::CheckSystemTheme => {
+                self.system_dark = palette::system_prefers_dark();
+                Command::none()
+            }
+            Message::FocusNext => iced::widget::focus_next(),
+            Message::FocusPrevious => iced::widget::focus_previous(),
             Message::SaveNameChanged(name) => {
                 self.save_name_input = name;
                 Command::none()
             }
             Message::ConfirmSave => {
                 if !self.save_name_input.trim().is_empty() && !self.messages.is_empty() {
-                    let conversation = SavedConversation {
-                        name: self.save_name_input.clone(),
-                        messages: self.messages.clone(),
-                    };
+                    let mut conversation = SavedConversation::new(
+                        self.save_name_input.clone(),
+                        self.messages.clone(),
+                        self.conversation_collections.clone(),
+                    );
+                    conversation.system_prompt = self.system_prompt.clone();
+                    conversation.temperature = self.temperature;
                     
                     // Sprawdź czy rozmowa o tej nazwie już istnieje
                     if let Some(existing_index) = self.saved_conversations
                         .iter()
                         .position(|c| c.name == conversation.name) {
+                        // Nadpisanie zachowuje folder, tagi i przypięcie
+                        let existing = &self.saved_conversations[existing_index];
+                        conversation.folder = existing.folder.clone();
+                        conversation.tags = existing.tags.clone();
+                        conversation.pinned = existing.pinned;
                         self.saved_conversations[existing_index] = conversation;
                     } else {
                         self.saved_conversations.push(conversation);
@@ -200,62 +1031,1308 @@ impl Application for ChatApp {
                     
                     self.current_conversation_name = self.save_name_input.clone();
                     self.save_conversations();
-                    self.show_save_dialog = false;
+                    self.dialog = None;
                     self.save_name_input.clear();
                 }
                 Command::none()
             }
             Message::ClearChat => {
+                self.stop_generation();
+                self.comparison = None;
                 self.messages.clear();
+                self.cancel_title();
+                self.scroll_to_latest()
+            }
+            Message::MessageHovered(index, hovered) => {
+                if hovered {
+                    self.hovered_message = Some(index);
+                } else if self.hovered_message == Some(index) {
+                    self.hovered_message = None;
+                }
                 Command::none()
             }
-        }
-    }
-
-    fn view(&self) -> Element<Message> {
-        let main_content = row![
-            create_sidebar(self),
-            create_chat_area(self)
-        ]
-        .spacing(0);
+            Message::CopyMessage(index, rendered) => match self.messages.get(index) {
+                Some(message) if rendered => iced::clipboard::write(export::plain_text(&message.content)),
+                Some(message) => iced::clipboard::write(message.content.clone()),
+                None => Command::none(),
+            },
+            Message::QuoteMessage(index) => {
+                if let Some(message) = self.messages.get(index) {
+                    let quote: String = message.content.trim().lines().map(|line| format!("> {}\n", line)).collect();
+                    self.append_to_input(&format!("{}\n", quote));
+                }
+                Command::none()
+            }
+            Message::DeleteMessage(index) => {
+                if !self.generating && index < self.messages.len() {
+                    self.messages.remove(index);
+                    self.hovered_message = None;
+                }
+                Command::none()
+            }
+            Message::TogglePinMessage(index) => {
+                if let Some(message) = self.messages.get_mut(index) {
+                    message.pinned = !message.pinned;
+                }
+                Command::none()
+            }
+            Message::AttachFile => {
+                Command::perform(
+                    async {
+                        let file = rfd::AsyncFileDialog::new()
+                            .add_filter(t("knowledge.documents_filter"), documents::SUPPORTED_EXTENSIONS)
+                            .pick_file()
+                            .await?;
+                        let path = file.path().to_path_buf();
 
-        if self.show_save_dialog {
-            container(
-                column![
-                    main_content,
-                    create_save_dialog(self)
-                ]
-            )
-            .into()
-        } else {
-            main_content.into()
-        }
-    }
+                        Some(documents::read_document_text(&path).map(|content| PendingAttachment {
+                            attachment: Attachment {
+                                name: file.file_name(),
+                                path: path.to_string_lossy().to_string(),
+                            },
+                            content,
+                        }))
+                    },
+                    Message::FileAttached,
+                )
+            }
+            Message::FileAttached(result) => {
+                match result {
+                    Some(Ok(attachment)) => {
+                        self.pending_attachments.push(attachment);
+                        self.attachment_error = None;
+                    }
+                    Some(Err(error)) => self.attachment_error = Some(error),
+                    None => {}
+                }
+                Command::none()
+            }
+            Message::RemoveAttachment(index) => {
+                if index < self.pending_attachments.len() {
+                    self.pending_attachments.remove(index);
+                }
+                Command::none()
+            }
+            Message::ToggleKnowledgeBase(enabled) => {
+                self.knowledge_enabled = enabled;
+                Command::none()
+            }
+            Message::IngestDocuments(collection) => {
+                let client = self.lm_client.clone();
+                let embedding_model = self.collection_settings(&collection).embedding_model;
+                self.knowledge_status = Some(t("knowledge.indexing").to_string());
 
-    fn theme(&self) -> theme::Theme {
-        theme::Theme::Light
-    }
-}
+                Command::perform(
+                    async move {
+                        let files = rfd::AsyncFileDialog::new()
+                            .add_filter(t("knowledge.documents_filter"), documents::SUPPORTED_EXTENSIONS)
+                            .pick_files()
+                            .await?;
 
-impl ChatApp {
-    fn save_conversations(&self) {
-        if let Ok(json) = serde_json::to_string_pretty(&self.saved_conversations) {
-            let _ = fs::write("conversations.json", json);
-        }
-    }
+                        let files = files.iter().map(|f| f.path().to_path_buf()).collect();
+                        Some(ingest(client, embedding_model, collection, files).await)
+                    },
+                    Message::DocumentsIngested,
+                )
+            }
+            Message::DocumentsIngested(result) => {
+                // Wynik indeksowania zleconego poleceniem /ingest widać też pod polem wiadomości
+                if self.command_status.is_some() {
+                    self.command_status = result.clone().map(|r| r.unwrap_or_else(|error| error));
+                }
+                self.knowledge_status = match result {
+                    Some(Ok(status)) => Some(status),
+                    Some(Err(error)) => Some(error),
+                    None => None,
+                };
+                self.reload_collection_stats()
+            }
+            Message::AddWatchDirectory => {
+                Command::perform(
+                    async {
+                        rfd::AsyncFileDialog::new()
+                            .pick_folder()
+                            .await
+                            .map(|folder| folder.path().to_string_lossy().to_string())
+                    },
+                    Message::WatchDirectoryPicked,
+                )
+            }
+            Message::WatchDirectoryPicked(directory) => {
+                match directory {
+                    Some(directory) if !self.settings.watch_directories.contains(&directory) => {
+                        self.settings.watch_directories.push(directory);
+                        self.save_settings();
+                        self.sync_watched_directories()
+                    }
+                    _ => Command::none(),
+                }
+            }
+            Message::RemoveWatchDirectory(index) => {
+                if index < self.settings.watch_directories.len() {
+                    self.settings.watch_directories.remove(index);
+                    self.save_settings();
+                }
+                Command::none()
+            }
+            Message::SelectWatchCollection(collection) => {
+                if collection == self.settings.watch_collection {
+                    return Command::none();
+                }
+                self.settings.watch_collection = collection;
+                self.save_settings();
+                self.sync_watched_directories()
+            }
+            Message::WatchedFilesChanged => self.sync_watched_directories(),
+            Message::SyncFinished(result) => {
+                self.indexing = false;
+                self.index_status = Some(match result {
+                    Ok(report) => tf(
+                        "knowledge.synced",
+                        &[&i18n::format_timestamp(&conversation::now()), &report.summary()],
+                    ),
+                    Err(error) => error,
+                });
 
-    fn load_conversations(&mut self) {
-        if Path::new("conversations.json").exists() {
-            if let Ok(content) = fs::read_to_string("conversations.json") {
-                if let Ok(conversations) = serde_json::from_str(&content) {
-                    self.saved_conversations = conversations;
+                if self.resync_pending {
+                    self.resync_pending = false;
+                    return self.sync_watched_directories();
                 }
+                self.reload_collection_stats()
             }
-        }
-    }
-}
+            Message::ShowCollectionsPanel => {
+                self.show_collections_panel = true;
+                self.show_memory_panel = false;
+                self.show_trash_panel = false;
+                self.show_inspector_panel = false;
+                let client = self.lm_client.clone();
+                let embedding_model = self.embedding_model.clone();
+
+                Command::perform(
+                    async move {
+                        KnowledgeBase::new(client, &embedding_model)?
+                            .list_collections()
+                            .await
+                    },
+                    Message::CollectionsListed,
+                )
+            }
+            Message::HideCollectionsPanel => {
+                self.show_collections_panel = false;
+                self.renaming_collection = None;
+                Command::none()
+            }
+            Message::CollectionsListed(result) => {
+                match result {
+                    Ok(names) => {
+                        // Kolekcje utworzone poza aplikacją dopisujemy z domyślnym modelem
+                        for name in names {
+                            // Kolekcja pamięci długoterminowej nie jest bazą wiedzy
+                            if name == memory::MEMORY_COLLECTION {
+                                continue;
+                            }
+                            if !self.settings.collections.iter().any(|c| c.name == name) {
+                                self.settings.collections.push(self.collection_settings(&name));
+                            }
+                        }
+                        self.save_settings();
+                        self.collections_status = None;
+                    }
+                    Err(error) => self.collections_status = Some(error),
+                }
+                Command::none()
+            }
+            Message::NewCollectionNameChanged(name) => {
+                self.new_collection_name = name;
+                Command::none()
+            }
+            Message::NewCollectionModelChanged(model) => {
+                self.new_collection_model = model;
+                Command::none()
+            }
+            Message::CreateCollection => {
+                let name = self.new_collection_name.trim().to_string();
+                let model = self.new_collection_model.trim().to_string();
+                if name.is_empty() || model.is_empty() {
+                    return Command::none();
+                }
+                let client = self.lm_client.clone();
+
+                Command::perform(
+                    async move {
+                        KnowledgeBase::new(client, &model)?.create_collection(&name).await?;
+                        Ok(CollectionChange::Created(CollectionSettings {
+                            name,
+                            embedding_model: model,
+                            ..Default::default()
+                        }))
+                    },
+                    Message::CollectionChanged,
+                )
+            }
+            Message::SelectCollection(name) => {
+                self.selected_collection = Some(name);
+                self.collection_stats = None;
+                self.reload_collection_stats()
+            }
+            Message::CollectionStatsLoaded(result) => {
+                match result {
+                    Ok(stats) => {
+                        if self.selected_collection.as_ref() == Some(&stats.name) {
+                            self.collection_stats = Some(stats);
+                        }
+                    }
+                    Err(error) => self.collections_status = Some(error),
+                }
+                Command::none()
+            }
+            Message::StartRenameCollection(name) => {
+                self.rename_collection_input = name.clone();
+                self.renaming_collection = Some(name);
+                Command::none()
+            }
+            Message::RenameCollectionInputChanged(name) => {
+                self.rename_collection_input = name;
+                Command::none()
+            }
+            Message::CancelRenameCollection => {
+                self.renaming_collection = None;
+                self.rename_collection_input.clear();
+                Command::none()
+            }
+            Message::ConfirmRenameCollection => {
+                let Some(from) = self.renaming_collection.take() else {
+                    return Command::none();
+                };
+                let to = self.rename_collection_input.trim().to_string();
+                if to.is_empty() || to == from {
+                    return Command::none();
+                }
+                let client = self.lm_client.clone();
+                let embedding_model = self.collection_settings(&from).embedding_model;
+                self.collections_status = Some(tf("collections.moving", &[&from]));
+
+                Command::perform(
+                    async move {
+                        KnowledgeBase::new(client, &embedding_model)?
+                            .rename_collection(&from, &to)
+                            .await?;
+                        Ok(CollectionChange::Renamed(from, to))
+                    },
+                    Message::CollectionChanged,
+                )
+            }
+            Message::DeleteCollection(name) => {
+                let client = self.lm_client.clone();
+                let embedding_model = self.collection_settings(&name).embedding_model;
+
+                Command::perform(
+                    async move {
+                        KnowledgeBase::new(client, &embedding_model)?
+                            .delete_collection(&name)
+                            .await?;
+                        Ok(CollectionChange::Deleted(name))
+                    },
+                    Message::CollectionChanged,
+                )
+            }
+            Message::RemoveDocument(collection, path) => {
+                let client = self.lm_client.clone();
+                let embedding_model = self.collection_settings(&collection).embedding_model;
+
+                Command::perform(
+                    async move {
+                        KnowledgeBase::new(client, &embedding_model)?
+                            .delete_file(&collection, &path)
+                            .await?;
+                        Ok(CollectionChange::DocumentRemoved)
+                    },
+                    Message::CollectionChanged,
+                )
+            }
+            Message::ToggleConversationCollection(name, enabled) => {
+                self.conversation_collections.retain(|c| *c != name);
+                if enabled {
+                    self.conversation_collections.push(name);
+                }
+                Command::none()
+            }
+            Message::SetCollectionHybrid(name, enabled) => {
+                if let Some(collection) = self.settings.collections.iter_mut().find(|c| c.name == name) {
+                    collection.hybrid = enabled;
+                    self.save_settings();
+                }
+                Command::none()
+            }
+            Message::SetCollectionRerank(name, enabled) => {
+                if let Some(collection) = self.settings.collections.iter_mut().find(|c| c.name == name) {
+                    collection.rerank = enabled;
+                    self.save_settings();
+                }
+                Command::none()
+            }
+            Message::ToggleMemory(enabled) => {
+                self.settings.memory_enabled = enabled;
+                self.save_settings();
+                Command::none()
+            }
+            Message::ToggleApiServer(enabled) => {
+                self.settings.api_server_enabled = enabled;
+                self.save_settings();
+                if enabled {
+                    self.start_api_server();
+                } else if let Some(server) = self.api_server.take() {
+                    server.stop();
+                    self.api_server_status = None;
+                }
+                Command::none()
+            }
+            Message::ToggleBodyLogging(enabled) => {
+                self.settings.log_request_bodies = enabled;
+                inspector::set_log_bodies(enabled);
+                self.save_settings();
+                Command::none()
+            }
+            Message::ToggleConversationMenu(index) => {
+                self.conversation_menu = if self.conversation_menu == Some(index) {
+                    None
+                } else {
+                    Some(index)
+                };
+                self.export_status = None;
+                Command::none()
+            }
+            Message::ExportConversations(index, format) => {
+                self.conversation_menu = None;
+                let conversations: Vec<SavedConversation> = match index {
+                    Some(index) => self.saved_conversations.get(index).cloned().into_iter().collect(),
+                    None => self.saved_conversations.clone(),
+                };
+                if conversations.is_empty() {
+                    return Command::none();
+                }
+
+                Command::perform(
+                    async move {
+                        let file = rfd::AsyncFileDialog::new()
+                            .set_file_name(export::file_name(&conversations, format))
+                            .add_filter(format.label(), &[format.extension()])
+                            .save_file()
+                            .await?;
+
+                        let result = export::export(&conversations, format).and_then(|content| {
+                            std::fs::write(file.path(), content)
+                                .map(|_| tf("export.done", &[&file.path().display()]))
+                                .map_err(|e| tf("export.write_failed", &[&e]))
+                        });
+                        Some(result)
+                    },
+                    Message::ConversationsExported,
+                )
+            }
+            Message::ConversationsExported(result) => {
+                self.export_status = match result {
+                    Some(Ok(status)) => Some(status),
+                    Some(Err(error)) => Some(error),
+                    None => None,
+                };
+                Command::none()
+            }
+            Message::ImportConversations => {
+                Command::perform(
+                    async {
+                        let files = rfd::AsyncFileDialog::new()
+                            .add_filter("JSON", &["json"])
+                            .pick_files()
+                            .await?;
+
+                        let mut imported = Vec::new();
+                        for file in &files {
+                            match import::read_file(file.path()) {
+                                Ok((source, conversations)) => {
+                                    imported.extend(conversations.into_iter().map(|c| (source, c)));
+                                }
+                                Err(error) => return Some(Err(error)),
+                            }
+                        }
+                        Some(Ok(imported))
+                    },
+                    Message::ImportFilesRead,
+                )
+            }
+            Message::ImportFilesRead(result) => {
+                match result {
+                    Some(Ok(imported)) if imported.is_empty() => {
+                        self.import_status = Some(t("import.nothing_found").to_string());
+                    }
+                    Some(Ok(imported)) => {
+                        self.import_candidates = import::preview(imported, &self.saved_conversations);
+                        self.import_status = None;
+                        self.show_collections_panel = false;
+                        self.show_memory_panel = false;
+                        self.show_trash_panel = false;
+                        self.show_inspector_panel = false;
+                    }
+                    Some(Err(error)) => self.import_status = Some(error),
+                    None => {}
+                }
+                Command::none()
+            }
+            Message::ToggleImportCandidate(index, selected) => {
+                if let Some(candidate) = self.import_candidates.get_mut(index) {
+                    candidate.selected = selected;
+                }
+                Command::none()
+            }
+            Message::ConfirmImport => {
+                let candidates = std::mem::take(&mut self.import_candidates);
+                let added = import::merge(&mut self.saved_conversations, candidates);
+                self.save_conversations();
+                self.import_status = Some(tf("import.done", &[&added]));
+                Command::none()
+            }
+            Message::CancelImport => {
+                self.import_candidates.clear();
+                Command::none()
+            }
+            Message::StartRenameConversation(index) => {
+                if let Some(conversation) = self.saved_conversations.get(index) {
+                    self.rename_conversation_input = conversation.name.clone();
+                    self.renaming_conversation = Some(index);
+                    self.conversation_menu = None;
+                }
+                Command::none()
+            }
+            Message::RenameConversationInputChanged(value) => {
+                self.rename_conversation_input = value;
+                Command::none()
+            }
+            Message::ConfirmRenameConversation => {
+                let new_name = self.rename_conversation_input.trim().to_string();
+                if let Some(index) = self.renaming_conversation {
+                    let taken = self.saved_conversations
+                        .iter()
+                        .enumerate()
+                        .any(|(i, c)| i != index && c.name == new_name);
+                    if new_name.is_empty() || taken {
+                        return Command::none();
+                    }
+
+                    if let Some(conversation) = self.saved_conversations.get_mut(index) {
+                        // Otwarta rozmowa zapisze się dalej pod nową nazwą
+                        if self.current_conversation_name == conversation.name {
+                            self.current_conversation_name = new_name.clone();
+                        }
+                        conversation.name = new_name;
+                        conversation.touch();
+                        self.save_conversations();
+                    }
+                }
+                self.renaming_conversation = None;
+                Command::none()
+            }
+            Message::CancelRenameConversation => {
+                self.renaming_conversation = None;
+                Command::none()
+            }
+            Message::TogglePinConversation(index) => {
+                if let Some(conversation) = self.saved_conversations.get_mut(index) {
+                    conversation.pinned = !conversation.pinned;
+                    self.save_conversations();
+                }
+                Command::none()
+            }
+            Message::MoveConversationToFolder(index, folder) => {
+                if let Some(conversation) = self.saved_conversations.get_mut(index) {
+                    conversation.folder = folder;
+                    self.save_conversations();
+                }
+                Command::none()
+            }
+            Message::NewFolderInputChanged(value) => {
+                self.new_folder_input = value;
+                Command::none()
+            }
+            Message::CreateFolder => {
+                let folder = self.new_folder_input.trim().to_string();
+                if !folder.is_empty() && !self.settings.folders.contains(&folder) {
+                    self.settings.folders.push(folder);
+                    self.save_settings();
+                }
+                self.new_folder_input.clear();
+                Command::none()
+            }
+            Message::DeleteFolder(folder) => {
+                // Rozmowy z usuwanego folderu trafiają do listy bez folderu
+                self.settings.folders.retain(|f| *f != folder);
+                for conversation in &mut self.saved_conversations {
+                    if conversation.folder.as_ref() == Some(&folder) {
+                        conversation.folder = None;
+                    }
+                }
+                self.save_settings();
+                self.save_conversations();
+                Command::none()
+            }
+            Message::ToggleConversationTag(index, tag) => {
+                if let Some(conversation) = self.saved_conversations.get_mut(index) {
+                    if conversation.tags.contains(&tag) {
+                        conversation.tags.retain(|t| *t != tag);
+                    } else {
+                        conversation.tags.push(tag);
+                    }
+                    self.save_conversations();
+                    self.remove_unused_tags();
+                }
+                Command::none()
+            }
+            Message::NewTagInputChanged(value) => {
+                self.new_tag_input = value;
+                Command::none()
+            }
+            Message::AddConversationTag(index) => {
+                let tag = self.new_tag_input.trim().to_string();
+                if tag.is_empty() {
+                    return Command::none();
+                }
+
+                if !self.settings.tags.iter().any(|t| t.name == tag) {
+                    // Nowe tagi dostają kolejne kolory z palety
+                    let color = self.settings.tags.len() % TAG_COLOR_COUNT;
+                    self.settings.tags.push(TagSettings { name: tag.clone(), color });
+                    self.save_settings();
+                }
+                if let Some(conversation) = self.saved_conversations.get_mut(index)
+                    && !conversation.tags.contains(&tag)
+                {
+                    conversation.tags.push(tag);
+                    self.save_conversations();
+                }
+                self.new_tag_input.clear();
+                Command::none()
+            }
+            Message::CycleTagColor(name) => {
+                if let Some(tag) = self.settings.tags.iter_mut().find(|t| t.name == name) {
+                    tag.color = (tag.color + 1) % TAG_COLOR_COUNT;
+                    self.save_settings();
+                }
+                Command::none()
+            }
+            Message::SetTagFilter(tag) => {
+                self.tag_filter = tag;
+                Command::none()
+            }
+            Message::RegenerateTitle => {
+                if self.messages.is_empty() || self.generating_title {
+                    return Command::none();
+                }
+                self.generate_title()
+            }
+            Message::TitleGenerated(request, result) => {
+                // Użytkownik przełączył się w międzyczasie na inną rozmowę
+                if request != self.title_request {
+                    return Command::none();
+                }
+                self.generating_title = false;
+
+                let requested_for = self.current_conversation_name.clone();
+                match result {
+                    Ok(title) => {
+                        let title = conversation::unique_name(&self.saved_conversations, &title, &requested_for);
+                        // Zapisana rozmowa zmienia nazwę zamiast powstawać pod nową
+                        if let Some(conversation) = self.saved_conversations
+                            .iter_mut()
+                            .find(|c| c.name == requested_for)
+                        {
+                            conversation.name = title.clone();
+                            conversation.touch();
+                            self.save_conversations();
+                        }
+                        if matches!(self.dialog, Some(Dialog::Save)) && self.save_name_input == requested_for {
+                            self.save_name_input = title.clone();
+                        }
+                        self.current_conversation_name = title;
+                        self.title_error = None;
+                    }
+                    Err(error) => self.title_error = Some(error),
+                }
+                Command::none()
+            }
+            Message::MemoriesExtracted(result) => {
+                self.memory_status = match result {
+                    Ok(0) => None,
+                    Ok(count) => Some(tf("memory.remembered", &[&count])),
+                    Err(error) => Some(error),
+                };
+                if self.show_memory_panel {
+                    return self.reload_memories();
+                }
+                Command::none()
+            }
+            Message::ShowMemoryPanel => {
+                self.show_memory_panel = true;
+                self.show_collections_panel = false;
+                self.show_trash_panel = false;
+                self.show_inspector_panel = false;
+                self.reload_memories()
+            }
+            Message::HideMemoryPanel => {
+                self.show_memory_panel = false;
+                self.editing_memory = None;
+                Command::none()
+            }
+            Message::MemoriesLoaded(result) => {
+                match result {
+                    Ok(memories) => self.memories = memories,
+                    Err(error) => self.memory_status = Some(error),
+                }
+                Command::none()
+            }
+            Message::EditMemory(id) => {
+                if let Some(entry) = self.memories.iter().find(|m| m.id == id) {
+                    self.memory_input = entry.text.clone();
+                    self.editing_memory = Some(id);
+                }
+                Command::none()
+            }
+            Message::MemoryInputChanged(text) => {
+                self.memory_input = text;
+                Command::none()
+            }
+            Message::CancelEditMemory => {
+                self.editing_memory = None;
+                self.memory_input.clear();
+                Command::none()
+            }
+            Message::SaveMemory => {
+                let Some(id) = self.editing_memory.take() else {
+                    return Command::none();
+                };
+                let Some(entry) = self.memories.iter().find(|m| m.id == id).cloned() else {
+                    return Command::none();
+                };
+                let text = self.memory_input.trim().to_string();
+                if text.is_empty() {
+                    return Command::none();
+                }
+                let client = self.lm_client.clone();
+                let embedding_model = self.embedding_model.clone();
+
+                Command::perform(
+                    async move {
+                        MemoryStore::new(client, &embedding_model)?
+                            .update(entry, &text)
+                            .await
+                    },
+                    Message::MemoryChanged,
+                )
+            }
+            Message::ForgetMemory(id) => {
+                let client = self.lm_client.clone();
+                let embedding_model = self.embedding_model.clone();
+
+                Command::perform(
+                    async move {
+                        MemoryStore::new(client, &embedding_model)?
+                            .forget(&id)
+                            .await
+                    },
+                    Message::MemoryChanged,
+                )
+            }
+            Message::MemoryChanged(result) => {
+                if let Err(error) = result {
+                    self.memory_status = Some(error);
+                }
+                self.reload_memories()
+            }
+            Message::CollectionChanged(result) => {
+                match result {
+                    Ok(change) => {
+                        self.collections_status = None;
+                        self.apply_collection_change(change);
+                        self.save_settings();
+                    }
+                    Err(error) => self.collections_status = Some(error),
+                }
+                self.reload_collection_stats()
+            }
+        }
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        // Panele kolekcji, pamięci i podglądu importu zastępują obszar czatu
+        let content = if !self.import_candidates.is_empty() {
+            create_import_panel(self)
+        } else if self.show_collections_panel {
+            create_collections_panel(self)
+        } else if self.show_memory_panel {
+            create_memory_panel(self)
+        } else if self.show_trash_panel {
+            create_trash_panel(self)
+        } else if self.show_inspector_panel {
+            create_inspector_panel(self)
+        } else {
+            create_chat_area(self)
+        };
+
+        // Komunikat z cofnięciem usunięcia pod obszarem treści
+        let content = match &self.undo_toast {
+            Some(name) => column![content, create_undo_toast(self.palette(), name)].into(),
+            None => content,
+        };
+
+        let main_content = row![
+            create_sidebar(self),
+            content
+        ]
+        .spacing(0);
+
+        match &self.dialog {
+            Some(dialog) => Modal::new(main_content, create_dialog(self, dialog))
+                .on_close(Message::CloseDialog)
+                .into(),
+            None => main_content.into(),
+        }
+    }
+
+    fn theme(&self) -> theme::Theme {
+        palette::iced_theme(self.settings.theme, self.system_dark)
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        // Tab przełącza fokus między polami; przy otwartym oknie tylko w jego obrębie.
+        // Skróty działają, gdy zdarzenia nie przechwyciło okno dialogowe ani pole tekstowe
+        let shortcuts = keyboard::on_key_press(|key, modifiers| match key.as_ref() {
+            keyboard::Key::Named(key::Named::Tab) if modifiers.shift() => Some(Message::FocusPrevious),
+            keyboard::Key::Named(key::Named::Tab) => Some(Message::FocusNext),
+            keyboard::Key::Named(key::Named::Escape) => Some(Message::StopGeneration),
+            keyboard::Key::Character("n") if modifiers.command() => Some(Message::NewConversation),
+            keyboard::Key::Character("s") if modifiers.command() => Some(Message::ShowSaveDialog),
+            keyboard::Key::Character("f") if modifiers.command() => Some(Message::FocusConversationSearch),
+            _ => None,
+        });
+
+        // Stan klawiszy modyfikujących, potrzebny edytorowi wiadomości
+        let modifiers = iced::event::listen_with(|event, _status| match event {
+            iced::Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                Some(Message::ModifiersChanged(modifiers))
+            }
+            _ => None,
+        });
+
+        let mut subscriptions = vec![shortcuts, modifiers];
+
+        // Zmiana motywu systemu jest sprawdzana okresowo
+        if self.settings.theme == ThemePreference::System {
+            subscriptions.push(
+                iced::time::every(std::time::Duration::from_secs(SYSTEM_THEME_POLL_SECONDS))
+                    .map(|_| Message::CheckSystemTheme),
+            );
+        }
+
+        if self.show_inspector_panel {
+            subscriptions.push(
+                iced::time::every(std::time::Duration::from_secs(INSPECTOR_REFRESH_SECONDS))
+                    .map(|_| Message::RefreshInspector),
+            );
+        }
+
+        // Odświeżanie licznika czasu i wskaźnika pisania
+        if self.generating {
+            subscriptions.push(
+                iced::time::every(std::time::Duration::from_millis(GENERATION_TICK_MILLIS))
+                    .map(Message::GenerationTick),
+            );
+        }
+
+        if !self.settings.watch_directories.is_empty() {
+            subscriptions.push(watcher::watch(
+                self.settings.watch_directories.clone(),
+                Message::WatchedFilesChanged,
+            ));
+        }
+
+        Subscription::batch(subscriptions)
+    }
+}
+
+impl ChatApp {
+    /// Kolory interfejsu dla bieżącego motywu
+    pub fn palette(&self) -> Palette {
+        Palette::for_preference(self.settings.theme, self.system_dark)
+    }
+
+    fn fetch_models(&mut self) -> Command<Message> {
+        self.models_status = Some(t("models.fetching").to_string());
+        let client = self.lm_client.clone();
+
+        Command::perform(
+            async move {
+                let models = client
+                    .list_models()
+                    .await
+                    .map_err(|e| tf("chat.error", &[&e]))?;
+                // Nie każda wersja LM Studio udostępnia informację o załadowanym modelu
+                let loaded = client.get_loaded_model().await.ok().flatten();
+                Ok((models, loaded))
+            },
+            Message::ModelsLoaded,
+        )
+    }
+
+    // Po dodaniu odpowiedzi modelu: przewinięcie, tytuł po pierwszej wymianie i zapamiętanie faktów
+    fn reply_added(&mut self) -> Command<Message> {
+        let mut commands = Vec::new();
+        if self.follow_latest {
+            commands.push(self.scroll_to_latest());
+        }
+        if self.current_conversation_name == DEFAULT_CONVERSATION_NAME && !self.generating_title {
+            commands.push(self.generate_title());
+        }
+        if self.settings.memory_enabled {
+            commands.push(self.extract_memories());
+        }
+        Command::batch(commands)
+    }
+
+    // Wysyła pytanie do porównywanych modeli: wszystkich naraz albo pierwszego z kolejki
+    fn start_comparison(&mut self, history: Vec<LMMessage>, query: String) -> Command<Message> {
+        let comparison = Comparison {
+            answers: self
+                .compare_models
+                .iter()
+                .map(|model| ComparisonAnswer { model: model.clone(), result: None })
+                .collect(),
+            sequential: self.settings.compare_sequential,
+            history,
+            query,
+        };
+        let count = comparison.answers.len();
+        let sequential = comparison.sequential;
+        self.comparison = Some(comparison);
+
+        if sequential {
+            self.request_comparison_answer(0)
+        } else {
+            Command::batch((0..count).map(|index| self.request_comparison_answer(index)))
+        }
+    }
+
+    fn request_comparison_answer(&self, index: usize) -> Command<Message> {
+        let Some(comparison) = &self.comparison else {
+            return Command::none();
+        };
+        let Some(answer) = comparison.answers.get(index) else {
+            return Command::none();
+        };
+        let generation = self.generation;
+        let session = self.session(&answer.model);
+        let query = comparison.query.clone();
+        let history = comparison.history.clone();
+
+        Command::perform(
+            async move { session.reply(&query, history).await },
+            move |result| Message::ComparisonAnswered(generation, index, result),
+        )
+    }
+
+    /// Przewija listę wiadomości na dół i wznawia podążanie za nowymi
+    fn scroll_to_latest(&mut self) -> Command<Message> {
+        self.follow_latest = true;
+        scrollable::snap_to(ui::messages_scroll_id(), scrollable::RelativeOffset::END)
+    }
+
+    /// Przerywa oczekiwanie na odpowiedź; spóźniony wynik zostanie pominięty
+    fn stop_generation(&mut self) {
+        if self.generating {
+            self.generating = false;
+            self.generation += 1;
+        }
+    }
+
+    // Zapamiętuje wysłane polecenie do przywołania strzałką w górę
+    fn remember_prompt(&mut self, prompt: String) {
+        if self.input_history.last() != Some(&prompt) {
+            self.input_history.push(prompt);
+            if self.input_history.len() > INPUT_HISTORY_LIMIT {
+                self.input_history.remove(0);
+            }
+        }
+        self.history_position = None;
+    }
+
+    // Wykonuje polecenie z ukośnikiem; błędne zostaje w polu, żeby można je było poprawić
+    fn run_command(&mut self, typed: String, command: Result<SlashCommand, String>) -> Command<Message> {
+        let command = match command {
+            Ok(command) => command,
+            Err(error) => {
+                self.command_status = Some(error);
+                return Command::none();
+            }
+        };
+        self.input_editor = text_editor::Content::new();
+        self.remember_prompt(typed);
+        self.command_status = None;
+
+        match command {
+            SlashCommand::Model(model) => {
+                if !self.available_models.is_empty() && !self.available_models.contains(&model) {
+                    self.command_status = Some(tf("commands.unknown_model", &[&model]));
+                    return Command::none();
+                }
+                self.command_status = Some(tf("commands.model_set", &[&model]));
+                self.update(Message::SelectModel(model))
+            }
+            SlashCommand::System(prompt) => self.update(Message::SetSystemPrompt(prompt)),
+            SlashCommand::Temperature(temperature) => self.update(Message::SetTemperature(Some(temperature))),
+            SlashCommand::Save(None) => self.update(Message::ShowSaveDialog),
+            SlashCommand::Save(Some(name)) => {
+                if self.messages.is_empty() {
+                    self.command_status = Some(t("commands.nothing_to_save").to_string());
+                    return Command::none();
+                }
+                self.command_status = Some(tf("commands.saved", &[&name]));
+                self.save_name_input = name;
+                self.update(Message::ConfirmSave)
+            }
+            SlashCommand::Clear => self.update(Message::ClearChat),
+            SlashCommand::Search(query) => self.update(Message::ConversationSearchChanged(query)),
+            SlashCommand::Ingest(path) => self.update(Message::IngestPath(path)),
+            SlashCommand::Export(format) => {
+                let saved = self
+                    .saved_conversations
+                    .iter()
+                    .position(|c| c.name == self.current_conversation_name);
+                match saved {
+                    Some(index) => self.update(Message::ExportConversations(Some(index), format)),
+                    None => {
+                        self.command_status = Some(t("commands.export_unsaved").to_string());
+                        Command::none()
+                    }
+                }
+            }
+        }
+    }
+
+    fn temperature(&self) -> f32 {
+        self.temperature.unwrap_or(lm_studio_client::DEFAULT_TEMPERATURE)
+    }
+
+    // Dopisuje tekst za wpisanym już szkicem i ustawia kursor na końcu
+    fn append_to_input(&mut self, addition: &str) {
+        let draft = self.input_editor.text();
+        let draft = draft.trim_end();
+        let text = if draft.is_empty() {
+            addition.to_string()
+        } else {
+            format!("{}\n\n{}", draft, addition)
+        };
+        self.input_editor = text_editor::Content::with_text(&text);
+        self.input_editor.perform(text_editor::Action::Move(text_editor::Motion::DocumentEnd));
+        self.history_position = None;
+    }
+
+    // Wstawia do edytora starsze (`older`) albo nowsze wysłane polecenie;
+    // za najnowszym wraca tekst wpisany przed przeglądaniem historii
+    fn recall_prompt(&mut self, older: bool) {
+        let position = match (self.history_position, older) {
+            (None, true) => {
+                self.history_draft = self.input_editor.text().trim_end().to_string();
+                Some(self.input_history.len() - 1)
+            }
+            (Some(position), true) => Some(position.saturating_sub(1)),
+            (Some(position), false) if position + 1 < self.input_history.len() => Some(position + 1),
+            (_, false) => None,
+        };
+
+        let text = match position {
+            Some(position) => &self.input_history[position],
+            None => &self.history_draft,
+        };
+        self.input_editor = text_editor::Content::with_text(text);
+        self.input_editor.perform(text_editor::Action::Move(text_editor::Motion::DocumentEnd));
+        self.history_position = position;
+    }
+
+    /// Nazwa bieżącej rozmowy; domyślna jest wyświetlana w języku interfejsu
+    pub fn conversation_display_name(&self) -> &str {
+        if self.current_conversation_name == DEFAULT_CONVERSATION_NAME {
+            t("conversation.default_name")
+        } else {
+            &self.current_conversation_name
+        }
+    }
+
+    fn save_conversations(&self) {
+        storage::save_conversations(&self.saved_conversations);
+    }
+
+    // Tag, którego nie ma już żadna rozmowa, znika z listy i z filtra
+    fn remove_unused_tags(&mut self) {
+        let conversations = &self.saved_conversations;
+        let before = self.settings.tags.len();
+        self.settings.tags.retain(|tag| conversations.iter().any(|c| c.tags.contains(&tag.name)));
+
+        if self.settings.tags.len() != before {
+            if let Some(filter) = &self.tag_filter
+                && !self.settings.tags.iter().any(|t| t.name == *filter)
+            {
+                self.tag_filter = None;
+            }
+            self.save_settings();
+        }
+    }
+
+    fn start_api_server(&mut self) {
+        match ApiServer::start(self.settings.api_server_port, self.lm_client.base_url()) {
+            Ok(server) => {
+                self.api_server_status = Some(tf("settings.api_running", &[&server.address]));
+                self.api_server = Some(server);
+            }
+            Err(error) => {
+                self.api_server_status = Some(error);
+            }
+        }
+    }
+
+    fn save_settings(&self) {
+        storage::save_settings(&self.settings);
+    }
+
+    fn load_settings(&mut self) {
+        self.settings = storage::load_settings();
+        if let Some(model) = &self.settings.chat_model {
+            self.model = model.clone();
+        }
+        i18n::set_language(self.settings.language);
+    }
+
+    // Uruchamia przyrostową synchronizację, a jeśli trwa inna - kolejkuje następną
+    fn sync_watched_directories(&mut self) -> Command<Message> {
+        if self.settings.watch_directories.is_empty() {
+            return Command::none();
+        }
+        if self.indexing {
+            self.resync_pending = true;
+            return Command::none();
+        }
+
+        self.indexing = true;
+        let client = self.lm_client.clone();
+        let collection = self.settings.watch_collection.clone();
+        let embedding_model = self.collection_settings(&collection).embedding_model;
+        let directories = self.settings.watch_directories.clone();
+
+        Command::perform(
+            async move {
+                let kb = KnowledgeBase::new(client, &embedding_model)?;
+                kb.sync_directories(&collection, &directories).await
+            },
+            Message::SyncFinished,
+        )
+    }
+
+    fn collection_settings(&self, name: &str) -> CollectionSettings {
+        self.settings.collection(name)
+    }
+
+    // Rozmowa z modelem według ustawień bieżącej rozmowy
+    fn session(&self, model: &str) -> Session {
+        Session::new(self.lm_client.clone(), model, self.context_options(), self.temperature())
+    }
+
+    // Źródła kontekstu dla bieżącej rozmowy
+    fn context_options(&self) -> ContextOptions {
+        let collections = if self.knowledge_enabled {
+            self.conversation_collections
+                .iter()
+                .map(|name| self.collection_settings(name))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        ContextOptions {
+            collections,
+            use_memory: self.settings.memory_enabled,
+            embedding_model: self.embedding_model.clone(),
+        }
+    }
+
+    fn reload_collection_stats(&self) -> Command<Message> {
+        let Some(name) = self.selected_collection.clone() else {
+            return Command::none();
+        };
+        if !self.show_collections_panel {
+            return Command::none();
+        }
+        let client = self.lm_client.clone();
+        let embedding_model = self.collection_settings(&name).embedding_model;
+
+        Command::perform(
+            async move {
+                KnowledgeBase::new(client, &embedding_model)?
+                    .collection_stats(&name)
+                    .await
+            },
+            Message::CollectionStatsLoaded,
+        )
+    }
+
+    // Przywrócona rozmowa wraca na listę; zajętą nazwę uzupełnia numer
+    fn restore_from_trash(&mut self, index: usize) {
+        let mut conversation = self.trash.remove(index).conversation;
+        conversation.name = conversation::unique_name(&self.saved_conversations, &conversation.name, "");
+        self.saved_conversations.push(conversation);
+        self.save_conversations();
+        storage::save_trash(&self.trash);
+    }
+
+    fn generate_title(&mut self) -> Command<Message> {
+        // Tytuł powstaje z pierwszej wymiany, więc kolejne wiadomości go nie zmieniają
+        let opening: Vec<ChatMessage> = self.messages.iter().take(2).cloned().collect();
+        let session = self.session(&self.model);
+        self.title_request += 1;
+        let request = self.title_request;
+        self.generating_title = true;
+
+        Command::perform(
+            async move { session.title(&opening).await },
+            move |result| Message::TitleGenerated(request, result),
+        )
+    }
+
+    // Tytuł zamówiony dla poprzedniej zawartości czatu nie zostanie już zastosowany
+    fn cancel_title(&mut self) {
+        self.title_request += 1;
+        self.generating_title = false;
+        self.title_error = None;
+    }
+
+    // Wyodrębnia fakty z ostatniej wymiany (pytanie + odpowiedź) i zapisuje je w pamięci
+    fn extract_memories(&self) -> Command<Message> {
+        let start = self.messages.len().saturating_sub(2);
+        let exchange = conversation::to_lm_history(&self.messages[start..]);
+        let session = self.session(&self.model);
+        let source = self.current_conversation_name.clone();
+
+        Command::perform(
+            async move { session.remember(&exchange, &source).await },
+            Message::MemoriesExtracted,
+        )
+    }
+
+    fn reload_memories(&self) -> Command<Message> {
+        let client = self.lm_client.clone();
+        let embedding_model = self.embedding_model.clone();
+
+        Command::perform(
+            async move {
+                MemoryStore::new(client, &embedding_model)?
+                    .list()
+                    .await
+            },
+            Message::MemoriesLoaded,
+        )
+    }
+
+    fn apply_collection_change(&mut self, change: CollectionChange) {
+        match change {
+            CollectionChange::Created(collection) => {
+                self.settings.collections.retain(|c| c.name != collection.name);
+                self.selected_collection = Some(collection.name.clone());
+                self.settings.collections.push(collection);
+                self.new_collection_name.clear();
+            }
+            CollectionChange::Renamed(from, to) => {
+                for collection in &mut self.settings.collections {
+                    if collection.name == from {
+                        collection.name = to.clone();
+                    }
+                }
+                for name in &mut self.conversation_collections {
+                    if *name == from {
+                        *name = to.clone();
+                    }
+                }
+                for conversation in &mut self.saved_conversations {
+                    for name in &mut conversation.collections {
+                        if *name == from {
+                            *name = to.clone();
+                        }
+                    }
+                }
+                if self.settings.watch_collection == from {
+                    self.settings.watch_collection = to.clone();
+                }
+                self.save_conversations();
+                self.selected_collection = Some(to);
+            }
+            CollectionChange::Deleted(name) => {
+                self.settings.collections.retain(|c| c.name != name);
+                self.conversation_collections.retain(|c| *c != name);
+                if self.settings.watch_collection == name {
+                    self.settings.watch_collection = knowledge::DEFAULT_COLLECTION.to_string();
+                }
+                if self.selected_collection.as_ref() == Some(&name) {
+                    self.selected_collection = None;
+                    self.collection_stats = None;
+                }
+            }
+            CollectionChange::DocumentRemoved => {}
+        }
+    }
+
+    fn load_conversations(&mut self) {
+        self.saved_conversations = storage::load_conversations();
+    }
+}
+
+// Indeksuje pliki i opisuje wynik; pliki z błędem są wymienione pod podsumowaniem
+async fn ingest(
+    client: LMStudioClient,
+    embedding_model: String,
+    collection: String,
+    files: Vec<std::path::PathBuf>,
+) -> Result<String, String> {
+    let results = KnowledgeBase::new(client, &embedding_model)?
+        .ingest_files(&collection, &files)
+        .await?;
+
+    let indexed: Vec<usize> = results.iter().filter_map(|(_, r)| r.as_ref().ok().copied()).collect();
+    let mut status = tf("knowledge.indexed", &[&indexed.len(), &indexed.iter().sum::<usize>()]);
+    for (file, result) in &results {
+        if let Err(error) = result {
+            status.push_str(&format!("\n{}: {}", file.display(), error));
+        }
+    }
+    if indexed.is_empty() {
+        Err(status)
+    } else {
+        Ok(status)
+    }
+}
+
+fn main() -> iced::Result {
+    // Strażnik musi żyć do końca programu, żeby zaległe wpisy trafiły do pliku
+    let log_guard = match logging::init() {
+        Ok(guard) => Some(guard),
+        Err(error) => {
+            eprintln!("{}", error);
+            None
+        }
+    };
+    tracing::info!(version = env!("CARGO_PKG_VERSION"), "uruchomienie aplikacji");
+    inspector::set_log_bodies(storage::load_settings().log_request_bodies);
+
+    // Z argumentami aplikacja działa w trybie wiersza poleceń, bez otwierania okna
+    if std::env::args().len() > 1 {
+        let code = cli::run();
+        drop(log_guard);
+        std::process::exit(code);
+    }
 
-fn main() -> iced::Result {
     ChatApp::run(Settings {
         window: window::Settings {
             size: Size::new(1200.0, 800.0),
@@ -0,0 +1,263 @@
+use chrono::Local;
+use qdrant_client::qdrant::{
+    CreateCollectionBuilder, DeletePointsBuilder, Distance, PointId, PointStruct,
+    ScrollPointsBuilder, SearchPointsBuilder, UpsertPointsBuilder, VectorParamsBuilder,
+};
+use qdrant_client::Qdrant;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{t, tf};
+use crate::knowledge;
+use crate::lm_studio_client::{LMStudioClient, Message as LMMessage};
+
+// Kolekcja Qdrant z faktami zapamiętanymi z rozmów
+pub const MEMORY_COLLECTION: &str = "memories";
+
+// Minimalne podobieństwo, od którego wspomnienie trafia do kontekstu
+const RECALL_THRESHOLD: f32 = 0.5;
+// Powyżej tego podobieństwa nowy fakt uznajemy za powtórzenie istniejącego
+const DUPLICATE_THRESHOLD: f32 = 0.92;
+
+const EXTRACTION_PROMPT: &str = "Przeanalizuj poniższy fragment rozmowy i wypisz trwałe fakty \
+warte zapamiętania na przyszłe rozmowy: preferencje użytkownika, informacje o nim, jego \
+projektach i ustaleniach. Pomiń pytania, bieżące prośby i wiedzę ogólną. Każdy fakt zapisz \
+w osobnej linii zaczynającej się od \"- \", jako pełne zdanie. Jeśli nie ma nic do \
+zapamiętania, odpowiedz jednym słowem: BRAK.";
+
+// Zapamiętany fakt wraz z jego pochodzeniem
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryEntry {
+    pub id: String,
+    pub text: String,
+    pub source: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+// Pamięć długoterminowa przechowywana jako wektory w Qdrant
+pub struct MemoryStore {
+    qdrant: Qdrant,
+    lm_client: LMStudioClient,
+    embedding_model: String,
+}
+
+impl MemoryStore {
+    pub fn new(lm_client: LMStudioClient, embedding_model: &str) -> Result<Self, String> {
+        Ok(MemoryStore {
+            qdrant: knowledge::connect_qdrant()?,
+            lm_client,
+            embedding_model: embedding_model.to_string(),
+        })
+    }
+
+    /// Prosi model o wypisanie trwałych faktów z fragmentu rozmowy
+    pub async fn extract_facts(&self, chat_model: &str, exchange: &[LMMessage]) -> Result<Vec<String>, String> {
+        let transcript: Vec<String> = exchange
+            .iter()
+            .map(|m| format!("{}: {}", m.role, m.content))
+            .collect();
+
+        let response = self
+            .lm_client
+            .send_message(
+                chat_model,
+                vec![
+                    LMMessage {
+                        role: "system".to_string(),
+                        content: EXTRACTION_PROMPT.to_string(),
+                    },
+                    LMMessage {
+                        role: "user".to_string(),
+                        content: transcript.join("\n\n"),
+                    },
+                ],
+            )
+            .await
+            .map_err(|e| tf("memory.extract_failed", &[&e]))?;
+
+        Ok(response
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("- "))
+            .map(|fact| fact.trim().to_string())
+            .filter(|fact| !fact.is_empty())
+            .collect())
+    }
+
+    /// Zapisuje fakty; powtórzenia odświeżają istniejące wspomnienie zamiast tworzyć nowe
+    pub async fn remember(&self, facts: Vec<String>, source: &str) -> Result<usize, String> {
+        let mut stored = 0;
+
+        for fact in facts {
+            let vector = self.embed(&fact).await?;
+            self.ensure_collection(vector.len() as u64).await?;
+
+            let now = Local::now().format("%Y-%m-%d %H:%M").to_string();
+            let duplicate = self
+                .search_vector(vector.clone(), 1)
+                .await?
+                .into_iter()
+                .find(|(_, score)| *score >= DUPLICATE_THRESHOLD)
+                .map(|(entry, _)| entry);
+
+            let entry = match duplicate {
+                Some(existing) => MemoryEntry {
+                    text: fact,
+                    updated_at: now,
+                    ..existing
+                },
+                None => MemoryEntry {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    text: fact,
+                    source: source.to_string(),
+                    created_at: now.clone(),
+                    updated_at: now,
+                },
+            };
+
+            self.upsert(entry, vector).await?;
+            stored += 1;
+        }
+
+        Ok(stored)
+    }
+
+    /// Wspomnienia związane z pytaniem, od najbardziej podobnego
+    pub async fn recall(&self, query: &str, limit: u64) -> Result<Vec<MemoryEntry>, String> {
+        if !self.collection_exists().await? {
+            return Ok(Vec::new());
+        }
+
+        let vector = self.embed(query).await?;
+        Ok(self
+            .search_vector(vector, limit)
+            .await?
+            .into_iter()
+            .filter(|(_, score)| *score >= RECALL_THRESHOLD)
+            .map(|(entry, _)| entry)
+            .collect())
+    }
+
+    /// Wszystkie wspomnienia, od najnowszych
+    pub async fn list(&self) -> Result<Vec<MemoryEntry>, String> {
+        if !self.collection_exists().await? {
+            return Ok(Vec::new());
+        }
+
+        let mut entries = Vec::new();
+        let mut offset = None;
+        loop {
+            let mut request = ScrollPointsBuilder::new(MEMORY_COLLECTION)
+                .limit(256)
+                .with_payload(true)
+                .with_vectors(false);
+            if let Some(offset) = offset.take() {
+                request = request.offset(offset);
+            }
+
+            let response = self
+                .qdrant
+                .scroll(request)
+                .await
+                .map_err(|e| tf("memory.read_failed", &[&e]))?;
+
+            entries.extend(response.result.into_iter().filter_map(|point| {
+                serde_json::from_value(serde_json::Value::Object(knowledge::payload_to_json(point.payload))).ok()
+            }));
+
+            match response.next_page_offset {
+                Some(next) => offset = Some(next),
+                None => break,
+            }
+        }
+
+        entries.sort_by(|a: &MemoryEntry, b: &MemoryEntry| b.updated_at.cmp(&a.updated_at));
+        Ok(entries)
+    }
+
+    /// Zmienia treść wspomnienia (z ponownym osadzeniem)
+    pub async fn update(&self, mut entry: MemoryEntry, text: &str) -> Result<(), String> {
+        let vector = self.embed(text).await?;
+        entry.text = text.to_string();
+        entry.updated_at = Local::now().format("%Y-%m-%d %H:%M").to_string();
+        self.upsert(entry, vector).await
+    }
+
+    pub async fn forget(&self, id: &str) -> Result<(), String> {
+        self.qdrant
+            .delete_points(
+                DeletePointsBuilder::new(MEMORY_COLLECTION)
+                    .points(vec![PointId::from(id.to_string())])
+                    .wait(true),
+            )
+            .await
+            .map_err(|e| tf("memory.delete_failed", &[&e]))?;
+        Ok(())
+    }
+
+    async fn upsert(&self, entry: MemoryEntry, vector: Vec<f32>) -> Result<(), String> {
+        let point = PointStruct::new(entry.id.clone(), vector, knowledge::to_payload(&entry)?);
+        self.qdrant
+            .upsert_points(UpsertPointsBuilder::new(MEMORY_COLLECTION, vec![point]).wait(true))
+            .await
+            .map_err(|e| tf("memory.save_failed", &[&e]))?;
+        Ok(())
+    }
+
+    async fn search_vector(&self, vector: Vec<f32>, limit: u64) -> Result<Vec<(MemoryEntry, f32)>, String> {
+        let response = self
+            .qdrant
+            .search_points(SearchPointsBuilder::new(MEMORY_COLLECTION, vector, limit).with_payload(true))
+            .await
+            .map_err(|e| tf("memory.search_failed", &[&e]))?;
+
+        Ok(response
+            .result
+            .into_iter()
+            .filter_map(|point| {
+                let json = serde_json::Value::Object(knowledge::payload_to_json(point.payload));
+                serde_json::from_value(json).ok().map(|entry| (entry, point.score))
+            })
+            .collect())
+    }
+
+    async fn collection_exists(&self) -> Result<bool, String> {
+        self.qdrant
+            .collection_exists(MEMORY_COLLECTION)
+            .await
+            .map_err(|e| tf("knowledge.connect_failed", &[&e]))
+    }
+
+    async fn ensure_collection(&self, vector_size: u64) -> Result<(), String> {
+        if self.collection_exists().await? {
+            return Ok(());
+        }
+
+        self.qdrant
+            .create_collection(
+                CreateCollectionBuilder::new(MEMORY_COLLECTION)
+                    .vectors_config(VectorParamsBuilder::new(vector_size, Distance::Cosine)),
+            )
+            .await
+            .map_err(|e| tf("memory.create_failed", &[&e]))?;
+        Ok(())
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>, String> {
+        self.lm_client
+            .embed(&self.embedding_model, vec![text.to_string()])
+            .await
+            .map_err(|e| tf("knowledge.embedding_failed", &[&e]))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| t("memory.no_embedding").to_string())
+    }
+}
+
+/// Treść wiadomości systemowej z przypomnianymi faktami
+pub fn format_memories(entries: &[MemoryEntry]) -> String {
+    let mut context = String::from("Fakty zapamiętane z wcześniejszych rozmów z użytkownikiem:\n");
+    for entry in entries {
+        context.push_str(&format!("- {} (zapisano {})\n", entry.text, entry.updated_at));
+    }
+    context
+}
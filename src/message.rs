@@ -0,0 +1,232 @@
+// Komunikaty aplikacji oraz stan okien dialogowych i porównania modeli
+use iced::keyboard;
+use iced::time::Instant;
+use iced::widget::{scrollable, text_editor};
+
+use knowledge_app::conversation::PendingAttachment;
+use knowledge_app::export::ExportFormat;
+use knowledge_app::i18n::Language;
+use knowledge_app::import::{ImportSource, ImportedConversation};
+use knowledge_app::knowledge::{CollectionSettings, CollectionStats, SyncReport};
+use knowledge_app::lm_studio_client::{CompletionStats, Message as LMMessage};
+use knowledge_app::memory::MemoryEntry;
+use knowledge_app::settings::ThemePreference;
+use knowledge_app::templates::PromptTemplate;
+
+// Okno dialogowe wyświetlane nad główną zawartością
+#[derive(Debug, Clone)]
+pub enum Dialog {
+    Save,
+    Confirm(Confirmation),
+    Settings,
+    Models,
+    Templates,
+    // Formularz zmiennych szablonu o danym indeksie
+    FillTemplate(usize),
+    // Edycja istniejącego szablonu albo, dla None, nowy szablon
+    EditTemplate(Option<usize>),
+}
+
+// Pytanie o potwierdzenie nieodwracalnej operacji
+#[derive(Debug, Clone)]
+pub struct Confirmation {
+    pub text: String,
+    pub confirm_label: String,
+    pub action: Box<Message>,
+}
+
+impl Confirmation {
+    /// Komunikat otwierający okno potwierdzenia, które po akceptacji wyśle `action`
+    pub fn request(text: impl Into<String>, confirm_label: impl Into<String>, action: Message) -> Message {
+        Message::RequestConfirmation(Confirmation {
+            text: text.into(),
+            confirm_label: confirm_label.into(),
+            action: Box::new(action),
+        })
+    }
+}
+
+// Jedno pytanie wysłane do kilku modeli; odpowiedzi czekają, aż użytkownik wybierze jedną
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub answers: Vec<ComparisonAnswer>,
+    // Modele pytane po kolei, gdy w pamięci mieści się tylko jeden
+    pub sequential: bool,
+    pub(crate) history: Vec<LMMessage>,
+    pub(crate) query: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ComparisonAnswer {
+    pub model: String,
+    // Brak wyniku - odpowiedź w drodze, czeka w kolejce albo została zatrzymana
+    pub result: Option<Result<(String, CompletionStats), String>>,
+}
+
+// Zakończona operacja na kolekcji wiedzy
+#[derive(Debug, Clone)]
+pub enum CollectionChange {
+    Created(CollectionSettings),
+    Renamed(String, String),
+    Deleted(String),
+    DocumentRemoved,
+}
+
+// Enum dla komunikatów w aplikacji
+#[derive(Debug, Clone)]
+pub enum Message {
+    EditorAction(text_editor::Action),
+    ModifiersChanged(keyboard::Modifiers),
+    SendMessage,
+    StopGeneration,
+    ConversationSearchChanged(String),
+    FocusConversationSearch,
+    NewConversation,
+    LoadConversation(usize),
+    DeleteConversation(usize),
+    ShowSaveDialog,
+    CloseDialog,
+    RequestConfirmation(Confirmation),
+    Confirm,
+    ShowSettings,
+    ApiPortChanged(String),
+    ShowModelManager,
+    ModelsLoaded(Result<(Vec<String>, Option<String>), String>),
+    SelectModel(String),
+    ReloadModel,
+    ModelReloaded(Result<(), String>),
+    FocusNext,
+    FocusPrevious,
+    SetLanguage(Language),
+    SetThemePreference(ThemePreference),
+    CheckSystemTheme,
+    SaveNameChanged(String),
+    ConfirmSave,
+    ClearChat,
+    MessageHovered(usize, bool),
+    MessagesScrolled(scrollable::Viewport),
+    JumpToLatest,
+    ToggleCompareMode(bool),
+    ToggleCompareModel(String, bool),
+    ToggleCompareSequential(bool),
+    // Numer żądania, indeks modelu w porównaniu i jego odpowiedź
+    ComparisonAnswered(u64, usize, Result<(String, CompletionStats), String>),
+    KeepComparisonAnswer(usize),
+    // Wstawia wybraną podpowiedź polecenia do pola wiadomości
+    ApplySuggestion(String),
+    SetSystemPrompt(Option<String>),
+    SetTemperature(Option<f32>),
+    IngestPath(String),
+    ShowTemplates,
+    UseTemplate(usize),
+    TemplateValueChanged(String, String),
+    TemplateClipboardRead(Option<String>),
+    PickTemplateFile,
+    TemplateFilePicked(Option<Result<String, String>>),
+    // Wstawia wypełniony szablon do pola wiadomości; `true` - od razu wysyła
+    ApplyTemplate(bool),
+    NewTemplate,
+    EditTemplate(usize),
+    TemplateNameChanged(String),
+    TemplateDescriptionChanged(String),
+    TemplateEditorAction(text_editor::Action),
+    SaveTemplate,
+    DeleteTemplate(usize),
+    ImportTemplates,
+    TemplatesImported(Option<Result<Vec<PromptTemplate>, String>>),
+    ExportTemplates,
+    TemplatesExported(Option<Result<String, String>>),
+    // Kopiuje treść wiadomości; `true` - bez składni Markdown
+    CopyMessage(usize, bool),
+    QuoteMessage(usize),
+    DeleteMessage(usize),
+    TogglePinMessage(usize),
+    // Numer żądania i odpowiedź modelu
+    MessageReceived(u64, Result<(String, CompletionStats), String>),
+    GenerationTick(Instant),
+    AttachFile,
+    FileAttached(Option<Result<PendingAttachment, String>>),
+    RemoveAttachment(usize),
+    ToggleKnowledgeBase(bool),
+    IngestDocuments(String),
+    DocumentsIngested(Option<Result<String, String>>),
+    AddWatchDirectory,
+    WatchDirectoryPicked(Option<String>),
+    RemoveWatchDirectory(usize),
+    SelectWatchCollection(String),
+    WatchedFilesChanged,
+    SyncFinished(Result<SyncReport, String>),
+    ShowCollectionsPanel,
+    HideCollectionsPanel,
+    CollectionsListed(Result<Vec<String>, String>),
+    NewCollectionNameChanged(String),
+    NewCollectionModelChanged(String),
+    CreateCollection,
+    SelectCollection(String),
+    CollectionStatsLoaded(Result<CollectionStats, String>),
+    StartRenameCollection(String),
+    RenameCollectionInputChanged(String),
+    ConfirmRenameCollection,
+    CancelRenameCollection,
+    DeleteCollection(String),
+    RemoveDocument(String, String),
+    ToggleConversationCollection(String, bool),
+    SetCollectionHybrid(String, bool),
+    SetCollectionRerank(String, bool),
+    ToggleMemory(bool),
+    MemoriesExtracted(Result<usize, String>),
+    ShowMemoryPanel,
+    HideMemoryPanel,
+    MemoriesLoaded(Result<Vec<MemoryEntry>, String>),
+    EditMemory(String),
+    MemoryInputChanged(String),
+    SaveMemory,
+    CancelEditMemory,
+    ForgetMemory(String),
+    MemoryChanged(Result<(), String>),
+    CollectionChanged(Result<CollectionChange, String>),
+    ToggleApiServer(bool),
+    ToggleBodyLogging(bool),
+    ToggleConversationMenu(usize),
+    // Eksport jednej rozmowy albo, dla None, wszystkich zapisanych
+    ExportConversations(Option<usize>, ExportFormat),
+    ConversationsExported(Option<Result<String, String>>),
+    ImportConversations,
+    ImportFilesRead(Option<Result<Vec<(ImportSource, ImportedConversation)>, String>>),
+    ToggleImportCandidate(usize, bool),
+    ConfirmImport,
+    CancelImport,
+    StartRenameConversation(usize),
+    RenameConversationInputChanged(String),
+    ConfirmRenameConversation,
+    CancelRenameConversation,
+    TogglePinConversation(usize),
+    MoveConversationToFolder(usize, Option<String>),
+    NewFolderInputChanged(String),
+    CreateFolder,
+    DeleteFolder(String),
+    ToggleConversationTag(usize, String),
+    NewTagInputChanged(String),
+    AddConversationTag(usize),
+    CycleTagColor(String),
+    SetTagFilter(Option<String>),
+    RegenerateTitle,
+    // Numer zlecenia tytułu (`title_request`) i wygenerowany tytuł
+    TitleGenerated(u64, Result<String, String>),
+    UndoDelete,
+    HideUndoToast(u64),
+    DismissUndoToast,
+    ShowTrashPanel,
+    HideTrashPanel,
+    RestoreConversation(usize),
+    PurgeConversation(usize),
+    EmptyTrash,
+    TrashRetentionChanged(String),
+    ShowInspectorPanel,
+    HideInspectorPanel,
+    RefreshInspector,
+    ClearInspector,
+    SelectRequest(u64),
+    CopyRequestAsCurl(u64),
+    CopyResponse(u64),
+}
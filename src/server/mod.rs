@@ -0,0 +1,455 @@
+use std::io::{Cursor, Read};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use serde::Serialize;
+use tiny_http::{Header, Method, Request, Response, Server, StatusCode};
+use tokio::runtime::Runtime;
+
+use crate::chat::{ContextOptions, Session};
+use crate::i18n::tf;
+use crate::knowledge::{self, KnowledgeBase};
+use crate::lm_studio_client::{self, LMStudioClient, Message as LMMessage};
+use crate::storage;
+
+// Domyślny port lokalnego API
+pub const DEFAULT_PORT: u16 = 8765;
+
+// Serwer nasłuchuje wyłącznie na interfejsie lokalnym
+const BIND_ADDRESS: &str = "127.0.0.1";
+
+#[derive(Serialize)]
+struct ConversationSummary {
+    name: String,
+    messages: usize,
+}
+
+#[derive(Serialize)]
+struct SearchHit {
+    citation: String,
+    score: f32,
+    text: String,
+}
+
+// Uruchomiony serwer HTTP; zatrzymuje się przy `stop` lub po upuszczeniu
+pub struct ApiServer {
+    server: Arc<Server>,
+    handle: Option<JoinHandle<()>>,
+    pub address: String,
+}
+
+impl std::fmt::Debug for ApiServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiServer").field("address", &self.address).finish()
+    }
+}
+
+impl ApiServer {
+    /// Uruchamia serwer w osobnym wątku. Serwer ma własnego klienta LM Studio pod `lm_studio_url`
+    /// i jedno środowisko asynchroniczne, na którym działają połączenia wszystkich żądań
+    pub fn start(port: u16, lm_studio_url: &str) -> Result<Self, String> {
+        let address = format!("{}:{}", BIND_ADDRESS, port);
+        let server = Arc::new(
+            Server::http(&address)
+                .map_err(|e| tf("settings.api_start_failed", &[&address, &e]))?,
+        );
+        // Port 0 oznacza dowolny wolny port
+        let port = server.server_addr().to_ip().map_or(port, |address| address.port());
+        let address = format!("{}:{}", BIND_ADDRESS, port);
+        let runtime = Arc::new(runtime()?);
+        let lm_client = LMStudioClient::with_base_url(lm_studio_url);
+
+        let listener = server.clone();
+        let handle = thread::spawn(move || {
+            for request in listener.incoming_requests() {
+                let client = lm_client.clone();
+                let runtime = runtime.clone();
+                // Odpowiedzi modelu trwają długo, więc każde żądanie ma własny wątek
+                thread::spawn(move || handle_request(request, &client, &runtime, port));
+            }
+        });
+
+        Ok(ApiServer {
+            server,
+            handle: Some(handle),
+            address,
+        })
+    }
+
+    /// Blokuje bieżący wątek do czasu zatrzymania serwera
+    pub fn wait(mut self) {
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+
+    pub fn stop(self) {
+        drop(self);
+    }
+}
+
+impl Drop for ApiServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+    }
+}
+
+fn handle_request(mut request: Request, client: &LMStudioClient, runtime: &Arc<Runtime>, port: u16) {
+    // Strona z obcej domeny przepiętej na 127.0.0.1 (DNS rebinding) wysyła własną nazwę w nagłówku Host
+    if !is_local_host(&request, port) {
+        let _ = request.respond(error_response(403, "Host header not allowed"));
+        return;
+    }
+
+    let url = request.url().to_string();
+    let (path, query) = match url.split_once('?') {
+        Some((path, query)) => (path.to_string(), query.to_string()),
+        None => (url.clone(), String::new()),
+    };
+    let segments: Vec<String> = path
+        .trim_matches('/')
+        .split('/')
+        .map(percent_decode)
+        .collect();
+    let segments: Vec<&str> = segments.iter().map(|s| s.as_str()).collect();
+    let method = request.method().clone();
+
+    let result = match (&method, segments.as_slice()) {
+        (Method::Post, ["v1", "chat", "completions"]) => {
+            let mut body = String::new();
+            if request.as_reader().read_to_string(&mut body).is_err() {
+                let _ = request.respond(error_response(400, "Could not read the request body"));
+                return;
+            }
+            // Odpowiedź z LM Studio (także strumieniowa) jest przekazywana bez buforowania
+            match chat_completions(client, runtime, &body) {
+                Ok(upstream) => {
+                    let status = upstream.status().as_u16();
+                    let content_type = upstream
+                        .headers()
+                        .get("content-type")
+                        .and_then(|v| v.to_str().ok())
+                        .unwrap_or("application/json")
+                        .to_string();
+                    let response = Response::new(
+                        StatusCode(status),
+                        vec![header("Content-Type", &content_type)],
+                        UpstreamBody::new(upstream, runtime.clone()),
+                        None,
+                        None,
+                    );
+                    let _ = request.respond(response);
+                    return;
+                }
+                Err(error) => Err((502, error)),
+            }
+        }
+        (Method::Get, ["v1", "models"]) => list_models(client, runtime),
+        (Method::Get, ["api", "conversations"]) => {
+            let summaries: Vec<ConversationSummary> = storage::load_conversations()
+                .into_iter()
+                .map(|c| ConversationSummary {
+                    name: c.name,
+                    messages: c.messages.len(),
+                })
+                .collect();
+            to_json(&summaries)
+        }
+        (Method::Get, ["api", "conversations", name]) => storage::load_conversations()
+            .into_iter()
+            .find(|c| c.name == *name)
+            .ok_or((404, format!("Conversation not found: {}", name)))
+            .and_then(|c| to_json(&c)),
+        (Method::Get, ["api", "collections"]) => to_json(&storage::load_settings().collections),
+        (Method::Get, ["api", "collections", name, "search"]) => search(client, runtime, name, &query),
+        _ => Err((404, format!("Unknown endpoint: {} {}", method, path))),
+    };
+
+    let response = match result {
+        Ok(json) => Response::from_string(json).with_header(header("Content-Type", "application/json")),
+        Err((status, message)) => error_response(status, &message),
+    };
+    let _ = request.respond(response);
+}
+
+// Akceptuje wyłącznie adresy lokalne z portem serwera
+fn is_local_host(request: &Request, port: u16) -> bool {
+    let Some(host) = request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv("Host"))
+        .map(|h| h.value.as_str().to_ascii_lowercase())
+    else {
+        return false;
+    };
+    ["localhost", BIND_ADDRESS]
+        .iter()
+        .any(|name| host == format!("{}:{}", name, port))
+}
+
+// Proxy zgodne z OpenAI: przed przekazaniem do LM Studio dokleja kontekst z bazy wiedzy i pamięci.
+// Dodatkowe pole "collections" wybiera kolekcje (domyślnie kolekcja podstawowa).
+fn chat_completions(client: &LMStudioClient, runtime: &Runtime, body: &str) -> Result<reqwest::Response, String> {
+    let mut request: serde_json::Value =
+        serde_json::from_str(body).map_err(|e| format!("Invalid JSON: {}", e))?;
+
+    let settings = storage::load_settings();
+    let collections: Vec<String> = request
+        .get("collections")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_else(|| vec![knowledge::DEFAULT_COLLECTION.to_string()]);
+    if let Some(object) = request.as_object_mut() {
+        object.remove("collections");
+    }
+
+    let model = request
+        .get("model")
+        .and_then(|v| v.as_str())
+        .unwrap_or(lm_studio_client::DEFAULT_MODEL)
+        .to_string();
+    let history: Vec<LMMessage> = request
+        .get("messages")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .ok_or_else(|| "Missing messages field".to_string())?;
+    let query = history
+        .iter()
+        .rev()
+        .find(|m| m.role == "user")
+        .map(|m| m.content.clone())
+        .unwrap_or_default();
+
+    let options = ContextOptions {
+        collections: collections.iter().map(|name| settings.collection(name)).collect(),
+        use_memory: settings.memory_enabled,
+        embedding_model: knowledge::DEFAULT_EMBEDDING_MODEL.to_string(),
+    };
+    let session = Session::new(client.clone(), &model, options, lm_studio_client::DEFAULT_TEMPERATURE);
+    let history = runtime.block_on(session.context(&query, history))?;
+
+    request["model"] = serde_json::Value::String(model);
+    request["messages"] = serde_json::to_value(history).map_err(|e| e.to_string())?;
+
+    runtime
+        .block_on(client.forward_chat_completion(&request))
+        .map_err(|e| format!("LM Studio request failed: {}", e))
+}
+
+fn list_models(client: &LMStudioClient, runtime: &Runtime) -> Result<String, (u16, String)> {
+    let models = runtime
+        .block_on(client.list_models())
+        .map_err(|e| (502, format!("LM Studio request failed: {}", e)))?;
+    let data: Vec<serde_json::Value> = models
+        .into_iter()
+        .map(|id| serde_json::json!({ "id": id, "object": "model" }))
+        .collect();
+    to_json(&serde_json::json!({ "object": "list", "data": data }))
+}
+
+fn search(client: &LMStudioClient, runtime: &Runtime, collection: &str, query: &str) -> Result<String, (u16, String)> {
+    let mut text = String::new();
+    let mut limit = 5;
+    for pair in query.split('&') {
+        match pair.split_once('=') {
+            // W wartościach zapytania "+" oznacza spację, w ścieżce pozostaje znakiem
+            Some(("q", value)) => text = percent_decode(&value.replace('+', " ")),
+            Some(("limit", value)) => limit = value.parse().unwrap_or(limit),
+            _ => {}
+        }
+    }
+    if text.trim().is_empty() {
+        return Err((400, "Missing q parameter".to_string()));
+    }
+
+    let settings = storage::load_settings().collection(collection);
+    let chunks = KnowledgeBase::new(client.clone(), &settings.embedding_model)
+        .and_then(|kb| runtime.block_on(kb.hybrid_search(&settings, &text, limit)))
+        .map_err(|e| (502, e))?;
+
+    let hits: Vec<SearchHit> = chunks
+        .iter()
+        .map(|chunk| SearchHit {
+            citation: chunk.citation(),
+            score: chunk.score,
+            text: chunk.payload.text.clone(),
+        })
+        .collect();
+    to_json(&hits)
+}
+
+// Treść odpowiedzi LM Studio czytana fragmentami na środowisku serwera, w którym działa połączenie
+struct UpstreamBody {
+    response: reqwest::Response,
+    runtime: Arc<Runtime>,
+    pending: Vec<u8>,
+}
+
+impl UpstreamBody {
+    fn new(response: reqwest::Response, runtime: Arc<Runtime>) -> Self {
+        UpstreamBody {
+            response,
+            runtime,
+            pending: Vec::new(),
+        }
+    }
+}
+
+impl Read for UpstreamBody {
+    fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        if self.pending.is_empty() {
+            match self.runtime.block_on(self.response.chunk()) {
+                Ok(Some(bytes)) => self.pending.extend_from_slice(&bytes),
+                Ok(None) => return Ok(0),
+                Err(e) => return Err(std::io::Error::other(e)),
+            }
+        }
+        let count = buffer.len().min(self.pending.len());
+        buffer[..count].copy_from_slice(&self.pending[..count]);
+        self.pending.drain(..count);
+        Ok(count)
+    }
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, (u16, String)> {
+    serde_json::to_string(value).map_err(|e| (500, e.to_string()))
+}
+
+fn error_response(status: u16, message: &str) -> Response<Cursor<Vec<u8>>> {
+    let body = serde_json::json!({ "error": { "message": message } }).to_string();
+    Response::from_string(body)
+        .with_status_code(status)
+        .with_header(header("Content-Type", "application/json"))
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("Niepoprawny nagłówek HTTP")
+}
+
+// Dekoduje znaki zapisane w adresie jako %XX
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'%' if index + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[index + 1..index + 3]).unwrap_or("");
+                match u8::from_str_radix(hex, 16) {
+                    Ok(byte) => {
+                        decoded.push(byte);
+                        index += 3;
+                        continue;
+                    }
+                    Err(_) => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        index += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+// Wątki robocze środowiska obsługują połączenia HTTP także wtedy, gdy wątek żądania czeka na treść
+fn runtime() -> Result<Runtime, String> {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_all()
+        .build()
+        .map_err(|e| tf("settings.api_runtime_failed", &[&e]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    // Treść strumienia SSE wysyłana w kilku porcjach z przerwami, jak odpowiedź generowana na bieżąco
+    struct SlowBody {
+        parts: Vec<&'static str>,
+    }
+
+    impl Read for SlowBody {
+        fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+            if self.parts.is_empty() {
+                return Ok(0);
+            }
+            thread::sleep(Duration::from_millis(50));
+            let part = self.parts.remove(0).as_bytes();
+            buffer[..part.len()].copy_from_slice(part);
+            Ok(part.len())
+        }
+    }
+
+    const STREAM: [&str; 3] = [
+        "data: {\"choices\":[{\"delta\":{\"content\":\"Dzień \"}}]}\n\n",
+        "data: {\"choices\":[{\"delta\":{\"content\":\"dobry\"}}]}\n\n",
+        "data: [DONE]\n\n",
+    ];
+
+    fn mock_lm_studio() -> String {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", server.server_addr());
+        thread::spawn(move || {
+            let request = server.recv().unwrap();
+            assert_eq!(request.url(), "/v1/chat/completions");
+            let response = Response::new(
+                StatusCode(200),
+                vec![header("Content-Type", "text/event-stream")],
+                SlowBody { parts: STREAM.to_vec() },
+                None,
+                None,
+            );
+            let _ = request.respond(response);
+        });
+        address
+    }
+
+    #[test]
+    fn proxies_streamed_completion_in_full() {
+        let server = ApiServer::start(0, &mock_lm_studio()).unwrap();
+        let url = format!("http://{}/v1/chat/completions", server.address);
+        let body = serde_json::json!({
+            "model": "mock",
+            "stream": true,
+            "collections": [],
+            "messages": [{ "role": "user", "content": "Cześć" }]
+        });
+
+        let runtime = runtime().unwrap();
+        let (status, text) = runtime.block_on(async {
+            let response = reqwest::Client::new().post(&url).json(&body).send().await.unwrap();
+            (response.status().as_u16(), response.text().await.unwrap())
+        });
+
+        assert_eq!(status, 200, "{}", text);
+        assert_eq!(text, STREAM.concat());
+    }
+
+    #[test]
+    fn keeps_plus_signs_in_path_segments() {
+        assert_eq!(percent_decode("C%2B%2B+notatki"), "C+++notatki");
+        assert_eq!(percent_decode("Nowa%20rozmowa"), "Nowa rozmowa");
+    }
+
+    #[test]
+    fn rejects_foreign_host_header() {
+        let server = ApiServer::start(0, "http://127.0.0.1:9").unwrap();
+        let url = format!("http://{}/api/collections", server.address);
+
+        let status = runtime()
+            .unwrap()
+            .block_on(async {
+                reqwest::Client::new()
+                    .get(&url)
+                    .header("Host", "evil.example:80")
+                    .send()
+                    .await
+                    .unwrap()
+                    .status()
+            });
+
+        assert_eq!(status.as_u16(), 403);
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{self, Language};
+use crate::knowledge::CollectionSettings;
+
+// Ustawienia aplikacji zapisywane w settings.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppSettings {
+    #[serde(default)]
+    pub watch_directories: Vec<String>,
+    // Kolekcja, do której trafiają pliki z obserwowanych folderów
+    #[serde(default = "default_watch_collection")]
+    pub watch_collection: String,
+    #[serde(default)]
+    pub collections: Vec<CollectionSettings>,
+    // Automatyczne zapamiętywanie faktów i przypominanie ich w nowych rozmowach
+    #[serde(default)]
+    pub memory_enabled: bool,
+    // Lokalny serwer HTTP z API rozmów i czatu z kontekstem
+    #[serde(default)]
+    pub api_server_enabled: bool,
+    #[serde(default = "default_api_port")]
+    pub api_server_port: u16,
+    // Foldery i tagi rozmów zdefiniowane przez użytkownika
+    #[serde(default)]
+    pub folders: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<TagSettings>,
+    #[serde(default)]
+    pub theme: ThemePreference,
+    // Model czatu wybrany w menedżerze modeli
+    #[serde(default)]
+    pub chat_model: Option<String>,
+    // Liczba dni, po których rozmowy z kosza są usuwane na zawsze (0 - nigdy)
+    #[serde(default = "default_trash_retention")]
+    pub trash_retention_days: u32,
+    // Język interfejsu; przy pierwszym uruchomieniu zgodny z ustawieniami regionalnymi systemu
+    #[serde(default)]
+    pub language: Language,
+    // Porównywanie modeli: pytanie po kolei zamiast równocześnie i liczba wybranych odpowiedzi każdego modelu
+    #[serde(default)]
+    pub compare_sequential: bool,
+    #[serde(default)]
+    pub comparison_votes: BTreeMap<String, u32>,
+    // Zapis treści żądań do LM Studio w pliku logu
+    #[serde(default)]
+    pub log_request_bodies: bool,
+}
+
+// Motyw interfejsu; domyślnie zgodny z ustawieniem systemu
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ThemePreference {
+    #[default]
+    System,
+    Light,
+    Dark,
+    HighContrast,
+}
+
+impl ThemePreference {
+    pub const ALL: [ThemePreference; 4] = [
+        ThemePreference::System,
+        ThemePreference::Light,
+        ThemePreference::Dark,
+        ThemePreference::HighContrast,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ThemePreference::System => i18n::t("theme.system"),
+            ThemePreference::Light => i18n::t("theme.light"),
+            ThemePreference::Dark => i18n::t("theme.dark"),
+            ThemePreference::HighContrast => i18n::t("theme.high_contrast"),
+        }
+    }
+}
+
+// Tag rozmowy z kolorem z palety panelu bocznego
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagSettings {
+    pub name: String,
+    #[serde(default)]
+    pub color: usize,
+}
+
+fn default_watch_collection() -> String {
+    crate::knowledge::DEFAULT_COLLECTION.to_string()
+}
+
+fn default_api_port() -> u16 {
+    crate::server::DEFAULT_PORT
+}
+
+fn default_trash_retention() -> u32 {
+    30
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        AppSettings {
+            watch_directories: Vec::new(),
+            watch_collection: default_watch_collection(),
+            collections: Vec::new(),
+            memory_enabled: false,
+            api_server_enabled: false,
+            api_server_port: default_api_port(),
+            folders: Vec::new(),
+            tags: Vec::new(),
+            theme: ThemePreference::default(),
+            chat_model: None,
+            trash_retention_days: default_trash_retention(),
+            language: Language::default(),
+            compare_sequential: false,
+            comparison_votes: BTreeMap::new(),
+            log_request_bodies: false,
+        }
+    }
+}
+
+impl AppSettings {
+    /// Indeks koloru tagu; nieznany tag dostaje pierwszy kolor palety
+    pub fn tag_color(&self, name: &str) -> usize {
+        self.tags.iter().find(|t| t.name == name).map(|t| t.color).unwrap_or(0)
+    }
+
+    /// Ustawienia kolekcji z rejestru, a dla nieznanej - z domyślnym modelem osadzeń
+    pub fn collection(&self, name: &str) -> CollectionSettings {
+        self.collections
+            .iter()
+            .find(|c| c.name == name)
+            .cloned()
+            .unwrap_or_else(|| CollectionSettings {
+                name: name.to_string(),
+                ..Default::default()
+            })
+    }
+}
@@ -0,0 +1,82 @@
+use std::fs;
+use std::path::Path;
+
+use crate::knowledge::CollectionSettings;
+use crate::conversation::{SavedConversation, TrashedConversation};
+use crate::settings::AppSettings;
+use crate::templates::PromptTemplate;
+
+// Pliki danych współdzielone przez okno aplikacji i tryb wiersza poleceń
+pub const CONVERSATIONS_FILE: &str = "conversations.json";
+pub const SETTINGS_FILE: &str = "settings.json";
+pub const TRASH_FILE: &str = "trash.json";
+pub const TEMPLATES_FILE: &str = "templates.json";
+
+pub fn load_conversations() -> Vec<SavedConversation> {
+    if Path::new(CONVERSATIONS_FILE).exists()
+        && let Ok(content) = fs::read_to_string(CONVERSATIONS_FILE)
+        && let Ok(conversations) = serde_json::from_str(&content)
+    {
+        return conversations;
+    }
+    Vec::new()
+}
+
+pub fn save_conversations(conversations: &[SavedConversation]) {
+    if let Ok(json) = serde_json::to_string_pretty(conversations) {
+        let _ = fs::write(CONVERSATIONS_FILE, json);
+    }
+}
+
+/// Wczytuje kosz, od razu usuwając rozmowy starsze niż okres przechowywania
+pub fn load_trash(retention_days: u32) -> Vec<TrashedConversation> {
+    let mut trash: Vec<TrashedConversation> = fs::read_to_string(TRASH_FILE)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+
+    let count = trash.len();
+    trash.retain(|item| !item.is_expired(retention_days));
+    if trash.len() != count {
+        save_trash(&trash);
+    }
+    trash
+}
+
+pub fn save_trash(trash: &[TrashedConversation]) {
+    if let Ok(json) = serde_json::to_string_pretty(trash) {
+        let _ = fs::write(TRASH_FILE, json);
+    }
+}
+
+pub fn load_templates() -> Vec<PromptTemplate> {
+    fs::read_to_string(TEMPLATES_FILE)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_templates(templates: &[PromptTemplate]) {
+    if let Ok(json) = serde_json::to_string_pretty(templates) {
+        let _ = fs::write(TEMPLATES_FILE, json);
+    }
+}
+
+pub fn load_settings() -> AppSettings {
+    let mut settings: AppSettings = fs::read_to_string(SETTINGS_FILE)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+
+    // Domyślna kolekcja jest zawsze dostępna
+    if settings.collections.is_empty() {
+        settings.collections.push(CollectionSettings::default());
+    }
+    settings
+}
+
+pub fn save_settings(settings: &AppSettings) {
+    if let Ok(json) = serde_json::to_string_pretty(settings) {
+        let _ = fs::write(SETTINGS_FILE, json);
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::i18n::tf;
+
+// Zmienne wypełniane przez aplikację: schowek, dzisiejsza data i treść wybranego pliku
+pub const CLIPBOARD_VARIABLE: &str = "clipboard";
+pub const DATE_VARIABLE: &str = "date";
+pub const SELECTED_FILE_VARIABLE: &str = "selected_file";
+
+// Szablon polecenia; `{{nazwa}}` w treści to zmienna uzupełniana przed wysłaniem
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub content: String,
+}
+
+impl PromptTemplate {
+    /// Nazwy zmiennych w kolejności pierwszego wystąpienia, bez powtórzeń
+    pub fn variables(&self) -> Vec<String> {
+        let mut variables: Vec<String> = Vec::new();
+        let mut rest = self.content.as_str();
+
+        while let Some(start) = rest.find("{{") {
+            let after = &rest[start + 2..];
+            let Some(end) = after.find("}}") else { break };
+            let name = after[..end].trim();
+            if is_variable_name(name) && !variables.iter().any(|v| v == name) {
+                variables.push(name.to_string());
+            }
+            rest = &after[end + 2..];
+        }
+
+        variables
+    }
+
+    /// Treść z podstawionymi wartościami; zmienne bez wartości zostają bez zmian
+    pub fn render(&self, values: &HashMap<String, String>) -> String {
+        let mut result = String::with_capacity(self.content.len());
+        let mut rest = self.content.as_str();
+
+        while let Some(start) = rest.find("{{") {
+            result.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            let Some(end) = after.find("}}") else {
+                rest = &rest[start..];
+                break;
+            };
+            match values.get(after[..end].trim()) {
+                Some(value) => result.push_str(value),
+                None => result.push_str(&rest[start..start + end + 4]),
+            }
+            rest = &after[end + 2..];
+        }
+
+        result.push_str(rest);
+        result
+    }
+}
+
+/// Czy zmienną wypełnia aplikacja, a nie użytkownik
+pub fn is_special(variable: &str) -> bool {
+    matches!(variable, CLIPBOARD_VARIABLE | DATE_VARIABLE | SELECTED_FILE_VARIABLE)
+}
+
+fn is_variable_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Plik z szablonami do udostępnienia innym
+pub fn export(templates: &[PromptTemplate]) -> Result<String, String> {
+    serde_json::to_string_pretty(templates).map_err(|e| tf("templates.export_failed", &[&e]))
+}
+
+/// Odczytuje plik z tablicą szablonów albo pojedynczym szablonem
+pub fn import(content: &str) -> Result<Vec<PromptTemplate>, String> {
+    let value: Value =
+        serde_json::from_str(content).map_err(|e| tf("templates.invalid_json", &[&e]))?;
+    let templates = match value {
+        Value::Array(_) => serde_json::from_value(value),
+        _ => serde_json::from_value(value).map(|template| vec![template]),
+    };
+    templates.map_err(|e| tf("templates.no_templates", &[&e]))
+}
+
+/// Dopisuje szablony; taki sam szablon jest pomijany, a zajęta nazwa dostaje numer.
+/// Zwraca liczbę dodanych
+pub fn merge(existing: &mut Vec<PromptTemplate>, imported: Vec<PromptTemplate>) -> usize {
+    let mut added = 0;
+
+    for mut template in imported {
+        if existing.contains(&template) {
+            continue;
+        }
+        let taken = |name: &str| existing.iter().any(|t| t.name == name);
+        if taken(&template.name) {
+            template.name = (2..)
+                .map(|n| format!("{} ({})", template.name, n))
+                .find(|candidate| !taken(candidate))
+                .unwrap_or(template.name);
+        }
+        existing.push(template);
+        added += 1;
+    }
+
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(name: &str, content: &str) -> PromptTemplate {
+        PromptTemplate {
+            name: name.to_string(),
+            description: String::new(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn lists_variables_once_in_order() {
+        let template = template("t", "Przetłumacz {{ tekst }} na {{język}}. {{tekst}} {{date}} {{}} {{a b}} {{niedomknięta");
+
+        assert_eq!(template.variables(), vec!["tekst", "język", "date"]);
+        assert!(is_special("date") && is_special(CLIPBOARD_VARIABLE) && !is_special("tekst"));
+    }
+
+    #[test]
+    fn renders_known_values_and_keeps_the_rest() {
+        let template = template("t", "Streść {{ plik }} w {{liczba}} zdaniach: {{plik}} {{nieznana}} {{koniec");
+        let values = HashMap::from([
+            ("plik".to_string(), "raport.txt".to_string()),
+            ("liczba".to_string(), "3".to_string()),
+        ]);
+
+        assert_eq!(
+            template.render(&values),
+            "Streść raport.txt w 3 zdaniach: raport.txt {{nieznana}} {{koniec"
+        );
+    }
+
+    #[test]
+    fn import_accepts_a_single_template_and_merge_renames_duplicates() {
+        let imported = import(r#"{"name": "Recenzja", "content": "Oceń {{kod}}"}"#).unwrap();
+        let mut existing = vec![template("Recenzja", "Inna treść")];
+
+        assert_eq!(merge(&mut existing, imported), 1);
+        assert_eq!(existing[1].name, "Recenzja (2)");
+        assert_eq!(merge(&mut existing, vec![template("Recenzja", "Inna treść")]), 0);
+        assert!(import("[{\"name\": 1}]").is_err());
+    }
+}
@@ -0,0 +1,2136 @@
+use iced::{
+    widget::{
+        button, checkbox, column, container, mouse_area, pick_list, row, scrollable, text, text_editor, text_input,
+        Space, Tooltip,
+    },
+    alignment::{Horizontal},
+    Element,
+    Length,
+    Color,
+    Background,
+    Border,
+    theme,
+};
+use crate::{ChatApp, ChatMessage, Comparison, Confirmation, Dialog, Message, SavedConversation}; // Importuj potrzebne typy z głównego modułu
+use crate::knowledge;
+use crate::templates;
+use crate::commands;
+use crate::{logging, RequestRecord};
+use super::markdown;
+use super::palette::Palette;
+use crate::ThemePreference;
+use crate::ExportFormat;
+use crate::i18n::{format_timestamp, t, tf, Language};
+use crate::CompletionStats;
+
+// Funkcje pomocnicze do tworzenia UI
+pub fn create_sidebar(app: &ChatApp) -> Element<'_, Message> {
+    let palette = app.palette();
+
+    let mut sidebar_content = column![
+        container(
+            text(t("sidebar.conversations"))
+                .size(18)
+                .style(palette.on_header)
+        )
+        .padding(15)
+        .style(container::Appearance {
+            background: Some(Background::Color(palette.header)),
+            ..Default::default()
+        }),
+        
+        container(
+            button(t("sidebar.new_conversation"))
+                .width(Length::Fill)
+                .on_press(Message::NewConversation)
+        )
+        .padding(10),
+    ]
+    .spacing(5);
+
+    sidebar_content = sidebar_content.push(
+        container(
+            text_input(t("sidebar.search"), &app.conversation_search)
+                .id(conversation_search_id())
+                .on_input(Message::ConversationSearchChanged)
+                .size(12)
+        )
+        .padding([0, 10])
+    );
+
+    sidebar_content = sidebar_content.push(create_tag_filter(app));
+
+    // Przypięte rozmowy, potem foldery, potem rozmowy bez folderu
+    let visible = visible_conversations(app);
+    let mut list = column![].spacing(5);
+
+    let pinned: Vec<_> = visible.iter().filter(|(_, c)| c.pinned).collect();
+    if !pinned.is_empty() {
+        list = list.push(group_header(palette, t("sidebar.pinned"), None));
+        for (index, conversation) in pinned {
+            list = list.push(create_conversation_item(app, *index, conversation));
+        }
+    }
+
+    for folder in &app.settings.folders {
+        list = list.push(group_header(palette, &format!("📁 {}", folder), Some(folder.clone())));
+        for (index, conversation) in visible
+            .iter()
+            .filter(|(_, c)| !c.pinned && c.folder.as_ref() == Some(folder))
+        {
+            list = list.push(create_conversation_item(app, *index, conversation));
+        }
+    }
+
+    let unfiled: Vec<_> = visible
+        .iter()
+        .filter(|(_, c)| {
+            !c.pinned && c.folder.as_ref().is_none_or(|folder| !app.settings.folders.contains(folder))
+        })
+        .collect();
+    if !app.settings.folders.is_empty() && !unfiled.is_empty() {
+        list = list.push(group_header(palette, t("sidebar.unfiled"), None));
+    }
+    for (index, conversation) in unfiled {
+        list = list.push(create_conversation_item(app, *index, conversation));
+    }
+
+    list = list.push(
+        container(
+            row![
+                text_input(t("sidebar.new_folder"), &app.new_folder_input)
+                    .on_input(Message::NewFolderInputChanged)
+                    .on_submit(Message::CreateFolder)
+                    .size(12)
+                    .width(Length::Fill),
+                button(text("+").size(12))
+                    .padding([2, 6])
+                    .on_press(Message::CreateFolder),
+            ]
+            .spacing(5)
+            .align_items(iced::Alignment::Center)
+        )
+        .padding([0, 10])
+    );
+
+    sidebar_content = sidebar_content.push(scrollable(list).height(Length::Fill));
+
+    sidebar_content = sidebar_content.push(
+        container(
+            row![
+                button(t("sidebar.import"))
+                    .width(Length::Fill)
+                    .on_press(Message::ImportConversations),
+                button(text(format!("🗑 {}", app.trash.len())))
+                    .on_press(Message::ShowTrashPanel),
+                Tooltip::new(
+                    button("🔍").on_press(Message::ShowInspectorPanel),
+                    t("inspector.title"),
+                    iced::widget::tooltip::Position::Top
+                ),
+            ]
+            .spacing(5)
+        )
+        .padding([0, 10])
+    );
+
+    if let Some(status) = app.export_status.as_ref().or(app.import_status.as_ref()) {
+        sidebar_content = sidebar_content.push(
+            container(
+                text(status)
+                    .size(12)
+                    .style(palette.muted_text)
+            )
+            .padding([0, 10])
+        );
+    }
+
+    sidebar_content = sidebar_content.push(create_knowledge_section(app));
+
+    container(sidebar_content)
+        .width(250)
+        .height(Length::Fill)
+        .style(container::Appearance {
+            background: Some(Background::Color(palette.sidebar)),
+            border: Border::with_radius(1.0),
+            ..Default::default()
+        })
+        .into()
+}
+
+// Paleta kolorów tagów; tag zapisuje indeks koloru
+const TAG_COLORS: [Color; 6] = [
+    Color { r: 0.2, g: 0.5, b: 0.9, a: 1.0 },
+    Color { r: 0.2, g: 0.7, b: 0.3, a: 1.0 },
+    Color { r: 0.9, g: 0.6, b: 0.1, a: 1.0 },
+    Color { r: 0.85, g: 0.25, b: 0.25, a: 1.0 },
+    Color { r: 0.6, g: 0.3, b: 0.8, a: 1.0 },
+    Color { r: 0.5, g: 0.5, b: 0.5, a: 1.0 },
+];
+
+fn tag_color(index: usize) -> Color {
+    TAG_COLORS[index % TAG_COLORS.len()]
+}
+
+pub const TAG_COLOR_COUNT: usize = TAG_COLORS.len();
+
+// Rozmowy pasujące do filtra tagu, od ostatnio zmienionej
+fn visible_conversations(app: &ChatApp) -> Vec<(usize, &SavedConversation)> {
+    let query = app.conversation_search.trim().to_lowercase();
+    let mut conversations: Vec<(usize, &SavedConversation)> = app
+        .saved_conversations
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| app.tag_filter.as_ref().is_none_or(|tag| c.tags.contains(tag)))
+        .filter(|(_, c)| query.is_empty() || matches_search(c, &query))
+        .collect();
+    conversations.sort_by(|(_, a), (_, b)| b.updated_at.cmp(&a.updated_at));
+    conversations
+}
+
+// Wyszukiwanie po nazwie, tagach i treści wiadomości, bez rozróżniania wielkości liter
+fn matches_search(conversation: &SavedConversation, query: &str) -> bool {
+    conversation.name.to_lowercase().contains(query)
+        || conversation.tags.iter().any(|tag| tag.to_lowercase().contains(query))
+        || conversation.messages.iter().any(|m| m.content.to_lowercase().contains(query))
+}
+
+fn group_header(palette: Palette, title: &str, folder: Option<String>) -> Element<'static, Message> {
+    let mut header = row![
+        text(title.to_string())
+            .size(12)
+            .style(palette.muted_text)
+            .width(Length::Fill),
+    ]
+    .align_items(iced::Alignment::Center);
+
+    if let Some(folder) = folder {
+        header = header.push(
+            button(text("✕").size(10))
+                .padding([0, 4])
+                .on_press(Message::DeleteFolder(folder))
+        );
+    }
+
+    container(header).padding([5, 10, 0, 10]).into()
+}
+
+fn create_tag_filter(app: &ChatApp) -> Element<'_, Message> {
+    if app.settings.tags.is_empty() {
+        return column![].into();
+    }
+
+    let all = button(text(t("sidebar.all_tags")).size(12))
+        .padding([2, 5])
+        .style(if app.tag_filter.is_none() { theme::Button::Primary } else { theme::Button::Secondary })
+        .on_press(Message::SetTagFilter(None));
+
+    let filter = app.settings.tags.iter().fold(row![all].spacing(5), |filter, tag| {
+        let active = app.tag_filter.as_ref() == Some(&tag.name);
+        filter.push(
+            button(
+                row![
+                    text("●").size(12).style(tag_color(tag.color)),
+                    text(&tag.name).size(12),
+                ]
+                .spacing(3)
+            )
+            .padding([2, 5])
+            .style(if active { theme::Button::Primary } else { theme::Button::Secondary })
+            .on_press(Message::SetTagFilter(Some(tag.name.clone())))
+        )
+    });
+
+    container(scrollable(filter).direction(scrollable::Direction::Horizontal(scrollable::Properties::default())))
+        .padding([0, 10])
+        .into()
+}
+
+fn create_conversation_item<'a>(app: &'a ChatApp, index: usize, conversation: &'a SavedConversation) -> Element<'a, Message> {
+    let item: Element<Message> = if app.renaming_conversation == Some(index) {
+        row![
+            text_input(t("sidebar.rename_placeholder"), &app.rename_conversation_input)
+                .on_input(Message::RenameConversationInputChanged)
+                .on_submit(Message::ConfirmRenameConversation)
+                .size(14)
+                .width(Length::Fill),
+            button(text("✓")).padding([0, 5]).on_press(Message::ConfirmRenameConversation),
+            button(text("✕")).padding([0, 5]).on_press(Message::CancelRenameConversation),
+        ]
+        .spacing(3)
+        .align_items(iced::Alignment::Center)
+        .into()
+    } else {
+        let label = conversation.tags.iter().fold(
+            row![text(&conversation.name).size(14)].spacing(3),
+            |label, tag| label.push(text("●").size(10).style(tag_color(app.settings.tag_color(tag)))),
+        );
+
+        row!(
+            button(label) // Użyj conversation.name
+                .width(Length::Fill)
+                .on_press(Message::LoadConversation(index)),
+            button(text("⋯"))
+                .width(Length::Shrink)
+                .padding([0, 5]).on_press(Message::ToggleConversationMenu(index)),
+            button(text(t("common.delete")))
+                .width(Length::Shrink)
+                .padding([0, 5]).on_press(Message::DeleteConversation(index)),
+        )
+        .into()
+    };
+
+    let mut item = column![container(item).padding([0, 10])].spacing(5);
+    if app.conversation_menu == Some(index) {
+        item = item.push(create_conversation_menu(app, index, conversation));
+    }
+    item.into()
+}
+
+// Menu kontekstowe rozmowy: nazwa, przypięcie, folder, tagi i eksport
+fn create_conversation_menu<'a>(app: &'a ChatApp, index: usize, conversation: &'a SavedConversation) -> Element<'a, Message> {
+    let palette = app.palette();
+
+    let format_buttons = |target: Option<usize>| {
+        ExportFormat::ALL
+            .into_iter()
+            .fold(row![].spacing(5), |buttons, format| {
+                buttons.push(
+                    button(text(format.label()).size(12))
+                        .padding([2, 5])
+                        .on_press(Message::ExportConversations(target, format))
+                )
+            })
+    };
+
+    let actions = row![
+        button(text(t("sidebar.rename")).size(12))
+            .padding([2, 5])
+            .on_press(Message::StartRenameConversation(index)),
+        button(text(if conversation.pinned { t("sidebar.unpin") } else { t("sidebar.pin") }).size(12))
+            .padding([2, 5])
+            .on_press(Message::TogglePinConversation(index)),
+    ]
+    .spacing(5);
+
+    let folders = app.settings.folders.iter().fold(
+        column![
+            button(text(if conversation.folder.is_none() { t("sidebar.unfiled_selected") } else { t("sidebar.unfiled") }).size(12))
+                .padding([2, 5])
+                .on_press(Message::MoveConversationToFolder(index, None)),
+        ]
+        .spacing(3),
+        |folders, folder| {
+            let current = conversation.folder.as_ref() == Some(folder);
+            folders.push(
+                button(text(if current { format!("✓ {}", folder) } else { folder.clone() }).size(12))
+                    .padding([2, 5])
+                    .on_press(Message::MoveConversationToFolder(index, Some(folder.clone())))
+            )
+        },
+    );
+
+    let tags = app.settings.tags.iter().fold(column![].spacing(3), |tags, tag| {
+        let assigned = conversation.tags.contains(&tag.name);
+        tags.push(
+            row![
+                button(text("●").size(12).style(tag_color(tag.color)))
+                    .padding([2, 5])
+                    .style(theme::Button::Text)
+                    .on_press(Message::CycleTagColor(tag.name.clone())),
+                checkbox(&tag.name, assigned)
+                    .size(14)
+                    .text_size(12)
+                    .on_toggle(move |_| Message::ToggleConversationTag(index, tag.name.clone())),
+            ]
+            .spacing(3)
+            .align_items(iced::Alignment::Center)
+        )
+    });
+
+    let new_tag = row![
+        text_input(t("sidebar.new_tag"), &app.new_tag_input)
+            .on_input(Message::NewTagInputChanged)
+            .on_submit(Message::AddConversationTag(index))
+            .size(12)
+            .width(Length::Fill),
+        button(text("+").size(12))
+            .padding([2, 6])
+            .on_press(Message::AddConversationTag(index)),
+    ]
+    .spacing(5)
+    .align_items(iced::Alignment::Center);
+
+    container(
+        column![
+            actions,
+            text(t("sidebar.folder")).size(12),
+            folders,
+            text(t("sidebar.tags")).size(12),
+            tags,
+            new_tag,
+            text(t("sidebar.export_conversation")).size(12),
+            format_buttons(Some(index)),
+            text(t("sidebar.export_all")).size(12),
+            format_buttons(None),
+        ]
+        .spacing(5)
+    )
+    .padding(8)
+    .width(Length::Fill)
+    .style(container::Appearance {
+        background: Some(Background::Color(palette.surface)),
+        border: Border {
+            color: palette.border,
+            width: 1.0,
+            radius: 5.0.into(),
+        },
+        ..Default::default()
+    })
+    .into()
+}
+
+fn create_knowledge_section(app: &ChatApp) -> Element<'_, Message> {
+    let palette = app.palette();
+
+    let mut section = column![
+        text(t("knowledge.title")).size(16),
+        checkbox(t("knowledge.use_in_answers"), app.knowledge_enabled)
+            .on_toggle(Message::ToggleKnowledgeBase),
+        button(t("knowledge.add_documents"))
+            .width(Length::Fill)
+            .on_press(Message::IngestDocuments(knowledge::DEFAULT_COLLECTION.to_string())),
+        button(t("knowledge.manage_collections"))
+            .width(Length::Fill)
+            .on_press(Message::ShowCollectionsPanel),
+        text(t("memory.title")).size(16),
+        checkbox(t("memory.remember_facts"), app.settings.memory_enabled)
+            .on_toggle(Message::ToggleMemory),
+        button(t("memory.browse"))
+            .width(Length::Fill)
+            .on_press(Message::ShowMemoryPanel),
+        row![
+            button(t("models.button"))
+                .width(Length::Fill)
+                .on_press(Message::ShowModelManager),
+            button(t("settings.button"))
+                .width(Length::Fill)
+                .on_press(Message::ShowSettings),
+        ]
+        .spacing(5),
+    ]
+    .spacing(8);
+
+    if let Some(status) = &app.memory_status {
+        section = section.push(
+            text(status)
+                .size(12)
+                .style(palette.muted_text)
+        );
+    }
+
+    if let Some(status) = &app.knowledge_status {
+        section = section.push(
+            text(status)
+                .size(12)
+                .style(palette.muted_text)
+        );
+    }
+
+    // Obserwowane foldery
+    for (index, directory) in app.settings.watch_directories.iter().enumerate() {
+        section = section.push(
+            row![
+                text(format!("📁 {}", directory))
+                    .size(12)
+                    .width(Length::Fill),
+                button(text("✕").size(10))
+                    .padding([0, 4])
+                    .on_press(Message::RemoveWatchDirectory(index)),
+            ]
+            .spacing(5)
+            .align_items(iced::Alignment::Center)
+        );
+    }
+
+    section = section.push(
+        button(t("knowledge.watch_folder"))
+            .width(Length::Fill)
+            .on_press(Message::AddWatchDirectory)
+    );
+
+    if !app.settings.watch_directories.is_empty() {
+        let mut collections: Vec<String> = app.settings.collections.iter().map(|c| c.name.clone()).collect();
+        if !collections.contains(&app.settings.watch_collection) {
+            collections.insert(0, app.settings.watch_collection.clone());
+        }
+        section = section.push(
+            row![
+                text(t("knowledge.watch_collection")).size(12),
+                pick_list(collections, Some(app.settings.watch_collection.clone()), Message::SelectWatchCollection)
+                    .text_size(12)
+                    .width(Length::Fill),
+            ]
+            .spacing(5)
+            .align_items(iced::Alignment::Center)
+        );
+    }
+
+    // Wskaźnik stanu indeksowania
+    let index_status = if app.indexing {
+        Some(t("knowledge.indexing_changes").to_string())
+    } else {
+        app.index_status.clone()
+    };
+    if let Some(status) = index_status {
+        section = section.push(
+            text(status)
+                .size(12)
+                .style(palette.muted_text)
+        );
+    }
+
+    container(section)
+        .padding(10)
+        .into()
+}
+
+pub fn create_chat_area(app: &ChatApp) -> Element<'_, Message> {
+    let palette = app.palette();
+
+    let header = container(
+        row![
+            text(app.conversation_display_name())
+                .size(16),
+
+            Tooltip::new(
+                button(text(if app.generating_title { "⏳" } else { "↻" }).size(14))
+                    .padding([2, 6])
+                    .style(theme::Button::Text)
+                    .on_press_maybe(
+                        (!app.messages.is_empty() && !app.generating_title).then_some(Message::RegenerateTitle)
+                    ),
+                t("chat.regenerate_title"),
+                iced::widget::tooltip::Position::Bottom
+            ),
+
+            text(app.title_error.as_deref().unwrap_or_default())
+                .size(12)
+                .style(palette.danger)
+                .width(Length::Fill),
+
+            Tooltip::new(
+                button(text(t("compare.button")).size(14))
+                    .style(if app.compare_mode { theme::Button::Primary } else { theme::Button::Secondary })
+                    .on_press(Message::ToggleCompareMode(!app.compare_mode)),
+                t("compare.tooltip"),
+                iced::widget::tooltip::Position::Bottom
+            ),
+
+            Space::with_width(10),
+            
+            Tooltip::new(
+                button(
+                    container(text("").size(14))
+                        .width(Length::Fixed(50.0)) // Przykład stałej szerokości
+                        .height(Length::Fixed(30.0))
+                        .style(container::Appearance {
+                            background: Some(Background::Color(palette.accent)),
+                            ..Default::default()
+                        })
+                )
+                .on_press(Message::ShowSaveDialog),
+                t("chat.save"),
+                iced::widget::tooltip::Position::Bottom
+            ),
+            
+            Space::with_width(10),
+            
+            Tooltip::new(
+                button(
+                    container(text("").size(14))
+                        .width(Length::Fixed(50.0)) // Przykład stałej szerokości
+                        .height(Length::Fixed(30.0))
+                        .style(container::Appearance {
+                            background: Some(Background::Color(palette.secondary_accent)),
+                            ..Default::default()
+                        })
+                )
+                .on_press(Message::ClearChat),
+                t("chat.clear"),
+                iced::widget::tooltip::Position::Bottom
+            ),
+        ]
+        .align_items(iced::Alignment::Center)
+    )
+    .padding(15)
+    .style(container::Appearance {
+        background: Some(Background::Color(palette.toolbar)),
+        border: Border::with_radius(1.0),
+        ..Default::default()
+    });
+
+    let messages_area = create_messages_view(app);
+    let input_area = create_input_area(app);
+
+    let mut content = column![header, messages_area].spacing(0);
+    if app.compare_mode {
+        content = content.push(create_compare_bar(app));
+    }
+
+    container(content.push(input_area))
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
+
+// Wybór porównywanych modeli; przy każdym liczba jego wybranych odpowiedzi
+fn create_compare_bar(app: &ChatApp) -> Element<'_, Message> {
+    let palette = app.palette();
+
+    let models = app.available_models.iter().fold(row![].spacing(15), |models, model| {
+        let votes = app.settings.comparison_votes.get(model).copied().unwrap_or(0);
+        let label = if votes > 0 { format!("{} (👍 {})", model, votes) } else { model.clone() };
+        let name = model.clone();
+        models.push(
+            checkbox(label, app.compare_models.contains(model))
+                .on_toggle(move |selected| Message::ToggleCompareModel(name.clone(), selected))
+                .size(14)
+                .text_size(12)
+        )
+    });
+
+    let hint = if app.compare_models.len() < 2 {
+        t("compare.select_hint")
+    } else {
+        ""
+    };
+
+    container(
+        column![
+            row![
+                text(t("compare.models")).size(12),
+                scrollable(models).direction(scrollable::Direction::Horizontal(
+                    scrollable::Properties::default()
+                )),
+            ]
+            .spacing(10)
+            .align_items(iced::Alignment::Center),
+            row![
+                checkbox(t("compare.sequential"), app.settings.compare_sequential)
+                    .on_toggle(Message::ToggleCompareSequential)
+                    .size(14)
+                    .text_size(12),
+                text(hint).size(12).style(palette.warning),
+            ]
+            .spacing(15),
+        ]
+        .spacing(6)
+    )
+    .padding([8, 15])
+    .width(Length::Fill)
+    .style(container::Appearance {
+        background: Some(Background::Color(palette.toolbar)),
+        ..Default::default()
+    })
+    .into()
+}
+
+// Odpowiedzi porównywanych modeli w kolumnach obok siebie
+fn create_comparison_view<'a>(app: &'a ChatApp, comparison: &'a Comparison) -> Element<'a, Message> {
+    let palette = app.palette();
+
+    let columns = comparison.answers.iter().enumerate().fold(row![].spacing(10), |columns, (index, answer)| {
+        let body: Element<Message> = match &answer.result {
+            Some(Ok((content, stats))) => column![
+                markdown::view(palette, &app.markdown_cache.blocks(content)),
+                text(stats_summary(stats).join(" · "))
+                    .size(10)
+                    .style(palette.faint_text),
+                button(text(t("compare.keep")).size(12))
+                    .padding([4, 10])
+                    .on_press(Message::KeepComparisonAnswer(index)),
+            ]
+            .spacing(8)
+            .into(),
+            Some(Err(error)) => text(error).size(12).style(palette.danger).into(),
+            None if app.generating => text(t("chat.thinking")).size(14).style(palette.muted_text).into(),
+            None => text(t("compare.stopped")).size(12).style(palette.muted_text).into(),
+        };
+
+        columns.push(
+            container(
+                column![
+                    text(&answer.model).size(12).style(palette.muted_text),
+                    body,
+                ]
+                .spacing(6)
+            )
+            .padding(BUBBLE_PADDING)
+            .width(Length::FillPortion(1))
+            .style(container::Appearance {
+                background: Some(Background::Color(palette.bubble)),
+                text_color: Some(palette.on_bubble),
+                border: Border::with_radius(12),
+                ..Default::default()
+            })
+        )
+    });
+
+    columns.into()
+}
+
+// Odstępy listy wiadomości, używane też przy szacowaniu jej wysokości
+const MESSAGE_SPACING: f32 = 10.0;
+const MESSAGE_LIST_PADDING: f32 = 15.0;
+const BUBBLE_PADDING: f32 = 12.0;
+
+// Identyfikator przewijanej listy wiadomości
+pub fn messages_scroll_id() -> scrollable::Id {
+    scrollable::Id::new("messages")
+}
+
+// Szacowana wysokość wiadomości razem z odstępem; dokładna jest znana dopiero po wyrenderowaniu
+fn estimated_message_height(app: &ChatApp, message: &ChatMessage, bubble_width: f32) -> f32 {
+    let content = if message.is_user {
+        markdown::wrapped_lines(&message.content, bubble_width, 14.0) as f32 * 14.0 * 1.3
+    } else {
+        markdown::estimated_height(&app.markdown_cache.blocks(&message.content), bubble_width)
+    };
+    let attachments = message.attachments.len() as f32 * 18.0;
+    let footer = 15.0;
+
+    content + attachments + footer + BUBBLE_PADDING * 2.0 + MESSAGE_SPACING
+}
+
+pub fn create_messages_view(app: &ChatApp) -> Element<'_, Message> {
+    let palette = app.palette();
+
+    // Budowane są tylko wiadomości w widocznym obszarze i w zapasie jego wysokości
+    // nad nim i pod nim; pozostałe zastępują odstępy o szacowanej wysokości
+    let viewport = app.messages_viewport;
+    let bubble_width = (viewport.width - MESSAGE_LIST_PADDING * 2.0) * 0.75 - BUBBLE_PADDING * 2.0;
+    let heights: Vec<f32> = app
+        .messages
+        .iter()
+        .map(|message| estimated_message_height(app, message, bubble_width))
+        .collect();
+    let total: f32 = heights.iter().sum();
+
+    let offset = if app.follow_latest {
+        (total - viewport.height).max(0.0)
+    } else {
+        app.messages_scroll_offset
+    };
+    let render_from = offset - viewport.height;
+    let render_to = offset + viewport.height * 2.0;
+
+    let mut first = heights.len();
+    let mut last = 0;
+    let mut top = 0.0;
+    for (index, height) in heights.iter().enumerate() {
+        if top + height >= render_from && top <= render_to {
+            first = first.min(index);
+            last = index;
+        }
+        top += height;
+    }
+
+    let mut messages_column = column![].padding(MESSAGE_LIST_PADDING);
+
+    if first < heights.len() {
+        messages_column = messages_column.push(Space::with_height(heights[..first].iter().sum::<f32>()));
+
+        for (index, message) in app.messages.iter().enumerate().take(last + 1).skip(first) {
+            messages_column = messages_column.push(
+                container(create_message_item(app, index, message))
+                    .padding([0.0, 0.0, MESSAGE_SPACING, 0.0])
+            );
+        }
+
+        messages_column = messages_column.push(Space::with_height(heights[last + 1..].iter().sum::<f32>()));
+    }
+
+    // Odpowiedź w trakcie generowania: animowane kropki i czas od wysłania
+    if let Some(comparison) = &app.comparison {
+        messages_column = messages_column.push(create_comparison_view(app, comparison));
+    } else if app.generating {
+        let elapsed = app.generation_now.saturating_duration_since(app.generation_started);
+        let dots = ".".repeat(1 + (elapsed.as_millis() / 500 % 3) as usize);
+        let seconds = format!("{:.1}", elapsed.as_secs_f32());
+
+        messages_column = messages_column.push(row![
+            container(
+                column![
+                    text(format!("{}{}", t("chat.thinking"), dots)).size(14),
+                    text(tf("chat.elapsed", &[&seconds]))
+                        .size(10)
+                        .style(palette.faint_text),
+                ]
+                .spacing(2)
+            )
+            .padding(BUBBLE_PADDING)
+            .style(container::Appearance {
+                background: Some(Background::Color(palette.bubble)),
+                text_color: Some(palette.on_bubble),
+                border: Border::with_radius(12),
+                ..Default::default()
+            })
+            .width(Length::FillPortion(3)),
+            Space::with_width(Length::FillPortion(1))
+        ]);
+    }
+
+    let mut view = column![
+        scrollable(messages_column)
+            .id(messages_scroll_id())
+            .on_scroll(Message::MessagesScrolled)
+            .height(Length::Fill)
+    ];
+
+    // Po przewinięciu w górę nowe wiadomości nie przesuwają listy; przycisk wraca na dół
+    if !app.follow_latest {
+        view = view.push(
+            container(
+                button(text(t("chat.jump_to_latest")).size(12))
+                    .padding([4, 12])
+                    .on_press(Message::JumpToLatest)
+            )
+            .width(Length::Fill)
+            .center_x()
+            .padding(5)
+        );
+    }
+
+    container(view)
+        .height(Length::Fill)
+        .into()
+}
+
+fn create_message_item<'a>(app: &'a ChatApp, index: usize, message: &'a ChatMessage) -> Element<'a, Message> {
+    let palette = app.palette();
+
+    let message_content = if message.is_user {
+        // Wiadomość użytkownika - po prawej stronie
+        row![
+            Space::with_width(Length::FillPortion(1)),
+            container(create_message_body(app, message))
+            .padding(BUBBLE_PADDING)
+            .style(container::Appearance {
+                background: Some(Background::Color(palette.accent)),
+                text_color: Some(palette.on_accent),
+                border: Border::with_radius(12),
+                ..Default::default()
+            })
+            .width(Length::FillPortion(3))
+        ]
+    } else {
+        // Wiadomość AI - po lewej stronie
+        row![
+            container(create_message_body(app, message))
+            .padding(BUBBLE_PADDING)
+            .style(container::Appearance {
+                background: Some(Background::Color(palette.bubble)),
+                text_color: Some(palette.on_bubble),
+                border: Border::with_radius(12),
+                ..Default::default()
+            })
+            .width(Length::FillPortion(3)),
+            Space::with_width(Length::FillPortion(1))
+        ]
+    };
+
+    // Przyciski akcji widać po najechaniu na wiadomość
+    let mut item = column![message_content].spacing(4);
+    if app.hovered_message == Some(index) {
+        let actions = create_message_actions(index, message, app.generating);
+        item = item.push(if message.is_user {
+            row![Space::with_width(Length::Fill), actions]
+        } else {
+            row![actions, Space::with_width(Length::Fill)]
+        });
+    }
+
+    mouse_area(item)
+        .on_enter(Message::MessageHovered(index, true))
+        .on_exit(Message::MessageHovered(index, false))
+        .into()
+}
+
+fn create_message_actions(index: usize, message: &ChatMessage, generating: bool) -> Element<'static, Message> {
+    let action = |label: &str, message: Message| {
+        button(text(label.to_string()).size(12))
+            .padding([2, 6])
+            .style(theme::Button::Text)
+            .on_press(message)
+    };
+    // Usunięcie wiadomości w trakcie odpowiedzi zmieniłoby historię, na którą model odpowiada
+    let delete = button(text(t("common.delete")).size(12))
+        .padding([2, 6])
+        .style(theme::Button::Text)
+        .on_press_maybe((!generating).then(|| {
+            Confirmation::request(t("message.delete_confirm"), t("common.delete"), Message::DeleteMessage(index))
+        }));
+
+    row![
+        action(t("message.copy"), Message::CopyMessage(index, false)),
+        action(t("message.copy_text"), Message::CopyMessage(index, true)),
+        action(t("message.quote"), Message::QuoteMessage(index)),
+        action(
+            if message.pinned { t("message.unpin") } else { t("message.pin") },
+            Message::TogglePinMessage(index)
+        ),
+        delete,
+    ]
+    .spacing(2)
+    .into()
+}
+
+// Stopka wiadomości: czas, oznaczenie przypięcia i statystyki generowania
+fn message_footer(message: &ChatMessage) -> String {
+    let mut parts = Vec::new();
+    if message.pinned {
+        parts.push("📌".to_string());
+    }
+    parts.push(format_timestamp(&message.timestamp));
+    if let Some(stats) = &message.stats {
+        parts.extend(stats_summary(stats));
+    }
+
+    parts.join(" · ")
+}
+
+// Szybkość, liczby tokenów, czas do pierwszego tokenu i czas całkowity
+fn stats_summary(stats: &CompletionStats) -> Vec<String> {
+    let mut parts = Vec::new();
+    if let Some(speed) = stats.tokens_per_second() {
+        parts.push(tf("stats.speed", &[&format!("{:.1}", speed)]));
+    }
+    if let (Some(prompt), Some(completion)) = (stats.prompt_tokens, stats.completion_tokens) {
+        parts.push(tf("stats.tokens", &[&prompt, &completion]));
+    }
+    if let Some(first_token) = stats.time_to_first_token_ms {
+        parts.push(tf("stats.first_token", &[&format!("{:.2}", first_token as f64 / 1000.0)]));
+    }
+    parts.push(tf("stats.duration", &[&format!("{:.1}", stats.duration_ms as f64 / 1000.0)]));
+    parts
+}
+
+fn create_message_body<'a>(app: &'a ChatApp, message: &'a ChatMessage) -> Element<'a, Message> {
+    let palette = app.palette();
+    let mut body = column![].spacing(2);
+
+    for attachment in &message.attachments {
+        body = body.push(
+            text(format!("📎 {}", attachment.name))
+                .size(12)
+        );
+    }
+
+    // Odpowiedzi modelu są renderowane z Markdown, wiadomości użytkownika dosłownie
+    let content: Element<Message> = if message.is_user {
+        text(&message.content).size(14).into()
+    } else {
+        markdown::view(palette, &app.markdown_cache.blocks(&message.content))
+    };
+
+    body
+        .push(content)
+        .push(
+            text(message_footer(message))
+                .size(10)
+                .style(palette.faint_text)
+        )
+        .into()
+}
+
+// Maksymalna wysokość edytora wiadomości
+const INPUT_MAX_HEIGHT: f32 = 200.0;
+
+pub fn create_input_area(app: &ChatApp) -> Element<'_, Message> {
+    let palette = app.palette();
+
+    let mut attachments_row = row![].spacing(5);
+
+    // Chipy z załącznikami oczekującymi na wysłanie
+    for (index, pending) in app.pending_attachments.iter().enumerate() {
+        attachments_row = attachments_row.push(input_chip(
+            palette,
+            format!("📎 {}", pending.attachment.name),
+            Message::RemoveAttachment(index),
+        ));
+    }
+
+    // Instrukcja systemowa i temperatura rozmowy ustawione poleceniami
+    if let Some(prompt) = &app.system_prompt {
+        let preview: String = prompt.chars().take(40).collect();
+        let ellipsis = if prompt.chars().count() > 40 { "…" } else { "" };
+        attachments_row = attachments_row.push(input_chip(
+            palette,
+            format!("⚙ {}{}", preview, ellipsis),
+            Message::SetSystemPrompt(None),
+        ));
+    }
+    if let Some(temperature) = app.temperature {
+        attachments_row = attachments_row.push(input_chip(
+            palette,
+            tf("commands.temperature_chip", &[&temperature]),
+            Message::SetTemperature(None),
+        ));
+    }
+
+    if let Some(error) = &app.attachment_error {
+        attachments_row = attachments_row.push(
+            text(error)
+                .size(12)
+                .style(palette.danger)
+        );
+    }
+
+    let input_row = row![
+        Tooltip::new(
+            button("📎")
+                .on_press(Message::AttachFile)
+                .padding([12, 12]),
+            t("chat.attach"),
+            iced::widget::tooltip::Position::Top
+        ),
+        Tooltip::new(
+            button("📝")
+                .on_press(Message::ShowTemplates)
+                .padding([12, 12]),
+            t("templates.tooltip"),
+            iced::widget::tooltip::Position::Top
+        ),
+
+        // Edytor rośnie z liczbą linii do stałej wysokości, potem przewija
+        container(
+            text_editor(&app.input_editor)
+                .on_action(Message::EditorAction)
+                .padding(12)
+        )
+        .max_height(INPUT_MAX_HEIGHT)
+        .width(Length::Fill),
+        
+        if app.generating {
+            button(t("chat.stop"))
+                .on_press(Message::StopGeneration)
+                .style(theme::Button::Destructive)
+                .padding([12, 20])
+        } else {
+            button(t("chat.send"))
+                .on_press(Message::SendMessage)
+                .padding([12, 20])
+        }
+    ]
+    .spacing(10)
+    .align_items(iced::Alignment::Center);
+
+    // Podpowiedzi poleceń z ukośnikiem nad polem wiadomości
+    let mut suggestions = column![].spacing(2);
+    for suggestion in commands::suggestions(&app.input_editor.text(), &app.available_models) {
+        suggestions = suggestions.push(
+            button(
+                row![
+                    text(suggestion.label).size(13).font(iced::Font::MONOSPACE),
+                    text(suggestion.description).size(12).style(palette.muted_text),
+                ]
+                .spacing(12)
+                .align_items(iced::Alignment::Center)
+            )
+            .width(Length::Fill)
+            .style(theme::Button::Text)
+            .on_press(Message::ApplySuggestion(suggestion.completion))
+        );
+    }
+
+    let mut content = column![attachments_row, suggestions].spacing(8);
+    if let Some(status) = &app.command_status {
+        content = content.push(text(status).size(12).style(palette.muted_text));
+    }
+
+    container(content.push(input_row))
+    .padding(15)
+    .style(container::Appearance {
+        background: Some(Background::Color(palette.toolbar)),
+        border: Border::with_radius(1.0),
+        ..Default::default()
+    })
+    .into()
+}
+
+// Chip nad polem wiadomości z przyciskiem usunięcia
+fn input_chip<'a>(palette: Palette, label: String, on_remove: Message) -> Element<'a, Message> {
+    container(
+        row![
+            text(label).size(12),
+            button(text("✕").size(10))
+                .padding([0, 4])
+                .on_press(on_remove),
+        ]
+        .spacing(5)
+        .align_items(iced::Alignment::Center)
+    )
+    .padding([4, 8])
+    .style(container::Appearance {
+        background: Some(Background::Color(palette.bubble)),
+        border: Border::with_radius(10),
+        ..Default::default()
+    })
+    .into()
+}
+
+// Identyfikator pola nazwy, które dostaje fokus po otwarciu okna zapisu
+pub fn save_name_input_id() -> text_input::Id {
+    text_input::Id::new("save-name")
+}
+
+// Identyfikator pola wyszukiwania rozmów (Ctrl+F)
+pub fn conversation_search_id() -> text_input::Id {
+    text_input::Id::new("conversation-search")
+}
+
+/// Treść okna dialogowego; tło i zamykanie zapewnia warstwa `Modal`
+pub fn create_dialog<'a>(app: &'a ChatApp, dialog: &'a Dialog) -> Element<'a, Message> {
+    match dialog {
+        Dialog::Save => create_save_dialog(app),
+        Dialog::Confirm(confirmation) => create_confirmation_dialog(app, confirmation),
+        Dialog::Settings => create_settings_dialog(app),
+        Dialog::Models => create_model_manager(app),
+        Dialog::Templates => create_templates_dialog(app),
+        Dialog::FillTemplate(index) => create_fill_template_dialog(app, *index),
+        Dialog::EditTemplate(_) => create_edit_template_dialog(app),
+    }
+}
+
+// Wspólna karta okna dialogowego
+fn dialog_card<'a>(palette: Palette, content: impl Into<Element<'a, Message>>, width: f32) -> Element<'a, Message> {
+    container(content)
+        .padding(30)
+        .width(Length::Fixed(width))
+        .style(container::Appearance {
+            background: Some(Background::Color(palette.surface)),
+            border: Border::with_radius(10),
+            ..Default::default()
+        })
+        .into()
+}
+
+fn create_save_dialog(app: &ChatApp) -> Element<'_, Message> {
+    dialog_card(
+        app.palette(),
+        column![
+            text(t("chat.save"))
+                .size(18)
+                .horizontal_alignment(Horizontal::Center),
+            
+            Space::with_height(20),
+            
+            text(t("save.name_label")),
+            row![
+                text_input(t("save.name_placeholder"), &app.save_name_input)
+                    .id(save_name_input_id())
+                    .on_input(Message::SaveNameChanged)
+                    .on_submit(Message::ConfirmSave)
+                    .padding(10),
+                Tooltip::new(
+                    button(text(if app.generating_title { "⏳" } else { "↻" }))
+                        .padding(10)
+                        .on_press_maybe(
+                            (!app.messages.is_empty() && !app.generating_title).then_some(Message::RegenerateTitle)
+                        ),
+                    t("save.suggest_title"),
+                    iced::widget::tooltip::Position::Bottom
+                ),
+            ]
+            .spacing(5),
+            
+            Space::with_height(20),
+            
+            row![
+                button(t("common.cancel"))
+                    .on_press(Message::CloseDialog)
+                    ,
+                
+                Space::with_width(10),
+                
+                button(t("common.save"))
+                    .on_press(Message::ConfirmSave)
+            ]
+            .align_items(iced::Alignment::Center)
+        ]
+        .spacing(10)
+        .align_items(iced::Alignment::Center),
+        400.0,
+    )
+}
+
+fn create_confirmation_dialog<'a>(app: &'a ChatApp, confirmation: &'a Confirmation) -> Element<'a, Message> {
+    let palette = app.palette();
+
+    dialog_card(
+        palette,
+        column![
+            text(&confirmation.text).size(16),
+            Space::with_height(10),
+            row![
+                Space::with_width(Length::Fill),
+                button(t("common.cancel")).on_press(Message::CloseDialog),
+                button(text(&confirmation.confirm_label))
+                    .style(theme::Button::Destructive)
+                    .on_press(Message::Confirm),
+            ]
+            .spacing(10)
+            .align_items(iced::Alignment::Center),
+        ]
+        .spacing(10),
+        400.0,
+    )
+}
+
+fn create_settings_dialog(app: &ChatApp) -> Element<'_, Message> {
+    let palette = app.palette();
+
+    let mut api = column![
+        text(t("settings.api_title")).size(16),
+        checkbox(t("settings.api_enabled"), app.settings.api_server_enabled)
+            .on_toggle(Message::ToggleApiServer),
+        row![
+            text(t("settings.port")).size(14),
+            text_input("8765", &app.api_port_input)
+                .on_input(Message::ApiPortChanged)
+                .padding(5)
+                .width(Length::Fixed(80.0)),
+        ]
+        .spacing(10)
+        .align_items(iced::Alignment::Center),
+        text(t("settings.port_hint"))
+            .size(12)
+            .style(palette.faint_text),
+    ]
+    .spacing(8);
+
+    if let Some(status) = &app.api_server_status {
+        api = api.push(
+            text(status)
+                .size(12)
+                .style(palette.muted_text)
+        );
+    }
+
+    dialog_card(
+        palette,
+        column![
+            text(t("settings.title")).size(18),
+            api,
+            text(t("settings.language")).size(16),
+            Language::ALL.into_iter().fold(row![].spacing(5), |languages, language| {
+                languages.push(
+                    button(text(language.native_name()).size(14))
+                        .style(if app.settings.language == language {
+                            theme::Button::Primary
+                        } else {
+                            theme::Button::Secondary
+                        })
+                        .on_press(Message::SetLanguage(language))
+                )
+            }),
+            text(t("settings.theme")).size(16),
+            ThemePreference::ALL.into_iter().fold(row![].spacing(5), |themes, preference| {
+                themes.push(
+                    button(text(preference.label()).size(14))
+                        .style(if app.settings.theme == preference {
+                            theme::Button::Primary
+                        } else {
+                            theme::Button::Secondary
+                        })
+                        .on_press(Message::SetThemePreference(preference))
+                )
+            }),
+            text(t("memory.title")).size(16),
+            checkbox(t("memory.remember_facts"), app.settings.memory_enabled)
+                .on_toggle(Message::ToggleMemory),
+            text(t("trash.title")).size(16),
+            row![
+                text(t("trash.retention")).size(14),
+                text_input("30", &app.trash_retention_input)
+                    .on_input(Message::TrashRetentionChanged)
+                    .padding(5)
+                    .width(Length::Fixed(60.0)),
+            ]
+            .spacing(10)
+            .align_items(iced::Alignment::Center),
+            text(t("inspector.title")).size(16),
+            checkbox(t("settings.log_bodies"), app.settings.log_request_bodies)
+                .on_toggle(Message::ToggleBodyLogging),
+            text(t("settings.log_bodies_hint"))
+                .size(12)
+                .style(palette.faint_text),
+            row![
+                Space::with_width(Length::Fill),
+                button(t("common.close")).on_press(Message::CloseDialog),
+            ],
+        ]
+        .spacing(12),
+        450.0,
+    )
+}
+
+fn create_model_manager(app: &ChatApp) -> Element<'_, Message> {
+    let palette = app.palette();
+
+    let mut models = column![].spacing(5);
+    for model in &app.available_models {
+        let selected = *model == app.model;
+        let loaded = app.loaded_model.as_ref() == Some(model);
+        models = models.push(
+            button(
+                row![
+                    text(if selected { "●" } else { "○" }).size(14),
+                    text(model).size(14).width(Length::Fill),
+                    text(if loaded { t("models.loaded") } else { "" })
+                        .size(12)
+                        .style(palette.success),
+                ]
+                .spacing(8)
+                .align_items(iced::Alignment::Center)
+            )
+            .width(Length::Fill)
+            .style(if selected { theme::Button::Primary } else { theme::Button::Secondary })
+            .on_press(Message::SelectModel(model.clone()))
+        );
+    }
+
+    let mut content = column![
+        text(t("models.title")).size(18),
+        text(tf("models.chat_model", &[&app.model])).size(14),
+        scrollable(models).height(Length::Fixed(300.0)),
+    ]
+    .spacing(12);
+
+    if let Some(status) = &app.models_status {
+        content = content.push(
+            text(status)
+                .size(12)
+                .style(palette.muted_text)
+        );
+    }
+
+    content = content.push(
+        row![
+            button(t("models.refresh")).on_press(Message::ShowModelManager),
+            button(t("models.reload")).on_press(Message::ReloadModel),
+            Space::with_width(Length::Fill),
+            button(t("common.close")).on_press(Message::CloseDialog),
+        ]
+        .spacing(10)
+    );
+
+    dialog_card(palette, content, 500.0)
+}
+
+fn create_templates_dialog(app: &ChatApp) -> Element<'_, Message> {
+    let palette = app.palette();
+
+    let mut list = column![].spacing(8);
+    for (index, template) in app.templates.iter().enumerate() {
+        let variables = template.variables();
+        let mut details = column![text(&template.name).size(15)].spacing(2);
+        if !template.description.is_empty() {
+            details = details.push(text(&template.description).size(12).style(palette.muted_text));
+        }
+        if !variables.is_empty() {
+            let names: Vec<String> = variables.iter().map(|v| format!("{{{{{}}}}}", v)).collect();
+            details = details.push(text(names.join(" ")).size(11).style(palette.faint_text));
+        }
+
+        list = list.push(
+            container(
+                row![
+                    details.width(Length::Fill),
+                    button(text(t("templates.use")).size(13)).on_press(Message::UseTemplate(index)),
+                    button(text(t("common.edit")).size(13))
+                        .style(theme::Button::Secondary)
+                        .on_press(Message::EditTemplate(index)),
+                    button(text(t("common.delete")).size(13))
+                        .style(theme::Button::Destructive)
+                        .on_press(Confirmation::request(
+                            tf("templates.delete_confirm", &[&template.name]),
+                            t("common.delete"),
+                            Message::DeleteTemplate(index),
+                        )),
+                ]
+                .spacing(6)
+                .align_items(iced::Alignment::Center)
+            )
+            .padding(10)
+            .style(container::Appearance {
+                background: Some(Background::Color(palette.bubble)),
+                border: Border::with_radius(6),
+                ..Default::default()
+            })
+        );
+    }
+    if app.templates.is_empty() {
+        list = list.push(text(t("templates.empty")).size(13).style(palette.muted_text));
+    }
+
+    let mut content = column![
+        text(t("templates.title")).size(18),
+        scrollable(list).height(Length::Fixed(320.0)),
+    ]
+    .spacing(12);
+
+    if let Some(status) = &app.templates_status {
+        content = content.push(text(status).size(12).style(palette.muted_text));
+    }
+
+    content = content.push(
+        row![
+            button(t("templates.new")).on_press(Message::NewTemplate),
+            button(t("templates.import")).on_press(Message::ImportTemplates),
+            button(t("templates.export"))
+                .on_press_maybe((!app.templates.is_empty()).then_some(Message::ExportTemplates)),
+            Space::with_width(Length::Fill),
+            button(t("common.close")).on_press(Message::CloseDialog),
+        ]
+        .spacing(10)
+    );
+
+    dialog_card(palette, content, 560.0)
+}
+
+fn create_fill_template_dialog(app: &ChatApp, index: usize) -> Element<'_, Message> {
+    let palette = app.palette();
+    let Some(template) = app.templates.get(index) else {
+        return create_templates_dialog(app);
+    };
+
+    let mut fields = column![].spacing(10);
+    for (variable, value) in &app.template_values {
+        let hint = match variable.as_str() {
+            templates::CLIPBOARD_VARIABLE => t("templates.from_clipboard"),
+            templates::DATE_VARIABLE => t("templates.today"),
+            templates::SELECTED_FILE_VARIABLE => t("templates.from_file"),
+            _ => "",
+        };
+        let label = row![
+            text(variable).size(14),
+            text(hint).size(12).style(palette.faint_text),
+        ]
+        .spacing(8)
+        .align_items(iced::Alignment::Center);
+
+        // Treść pliku bywa długa, więc zamiast pola tekstowego widać tylko jej rozmiar
+        let input: Element<Message> = if variable == templates::SELECTED_FILE_VARIABLE {
+            row![
+                button(text(t("templates.pick_file")).size(13)).on_press(Message::PickTemplateFile),
+                text(if value.is_empty() {
+                    t("templates.no_file").to_string()
+                } else {
+                    tf("templates.file_loaded", &[&value.chars().count()])
+                })
+                .size(12)
+                .style(palette.muted_text),
+            ]
+            .spacing(10)
+            .align_items(iced::Alignment::Center)
+            .into()
+        } else {
+            let variable = variable.clone();
+            text_input("", value)
+                .on_input(move |value| Message::TemplateValueChanged(variable.clone(), value))
+                .on_submit(Message::ApplyTemplate(false))
+                .padding(8)
+                .into()
+        };
+        fields = fields.push(column![label, input].spacing(4));
+    }
+
+    let mut content = column![
+        text(&template.name).size(18),
+        scrollable(fields).height(Length::Shrink),
+    ]
+    .spacing(12);
+
+    if let Some(status) = &app.templates_status {
+        content = content.push(text(status).size(12).style(palette.danger));
+    }
+
+    content = content.push(
+        row![
+            button(t("common.cancel")).on_press(Message::ShowTemplates),
+            Space::with_width(Length::Fill),
+            button(t("templates.insert")).on_press(Message::ApplyTemplate(false)),
+            button(t("chat.send")).on_press_maybe((!app.generating).then_some(Message::ApplyTemplate(true))),
+        ]
+        .spacing(10)
+    );
+
+    dialog_card(palette, content, 500.0)
+}
+
+fn create_edit_template_dialog(app: &ChatApp) -> Element<'_, Message> {
+    let palette = app.palette();
+
+    let mut content = column![
+        text(t("templates.edit_title")).size(18),
+        text(t("templates.name")).size(14),
+        text_input("", &app.template_name_input)
+            .on_input(Message::TemplateNameChanged)
+            .padding(8),
+        text(t("templates.description")).size(14),
+        text_input("", &app.template_description_input)
+            .on_input(Message::TemplateDescriptionChanged)
+            .padding(8),
+        text(t("templates.content")).size(14),
+        text_editor(&app.template_editor)
+            .on_action(Message::TemplateEditorAction)
+            .height(Length::Fixed(200.0))
+            .padding(8),
+        text(t("templates.variables_hint")).size(12).style(palette.faint_text),
+    ]
+    .spacing(8);
+
+    if let Some(status) = &app.templates_status {
+        content = content.push(text(status).size(12).style(palette.danger));
+    }
+
+    content = content.push(
+        row![
+            Space::with_width(Length::Fill),
+            button(t("common.cancel")).on_press(Message::ShowTemplates),
+            button(t("common.save")).on_press(Message::SaveTemplate),
+        ]
+        .spacing(10)
+    );
+
+    dialog_card(palette, content, 560.0)
+}
+
+pub fn create_collections_panel(app: &ChatApp) -> Element<'_, Message> {
+    let palette = app.palette();
+
+    let header = container(
+        row![
+            text(t("collections.title"))
+                .size(16)
+                .width(Length::Fill),
+            button(t("common.close"))
+                .on_press(Message::HideCollectionsPanel),
+        ]
+        .align_items(iced::Alignment::Center)
+    )
+    .padding(15)
+    .style(container::Appearance {
+        background: Some(Background::Color(palette.toolbar)),
+        border: Border::with_radius(1.0),
+        ..Default::default()
+    });
+
+    let create_row = row![
+        text_input(t("collections.new_name"), &app.new_collection_name)
+            .on_input(Message::NewCollectionNameChanged)
+            .on_submit(Message::CreateCollection)
+            .padding(8)
+            .width(Length::FillPortion(2)),
+        text_input(t("collections.embedding_model"), &app.new_collection_model)
+            .on_input(Message::NewCollectionModelChanged)
+            .padding(8)
+            .width(Length::FillPortion(2)),
+        button(t("collections.create"))
+            .on_press(Message::CreateCollection),
+    ]
+    .spacing(10)
+    .align_items(iced::Alignment::Center);
+
+    let mut list = column![
+        text(t("collections.hint"))
+            .size(12)
+            .style(palette.muted_text)
+    ]
+    .spacing(8);
+
+    for collection in &app.settings.collections {
+        let name = collection.name.clone();
+        let in_conversation = app.conversation_collections.contains(&name);
+
+        let title: Element<Message> = if app.renaming_collection.as_ref() == Some(&name) {
+            row![
+                text_input(t("collections.rename_placeholder"), &app.rename_collection_input)
+                    .on_input(Message::RenameCollectionInputChanged)
+                    .on_submit(Message::ConfirmRenameCollection)
+                    .padding(5)
+                    .width(Length::Fill),
+                button("OK").on_press(Message::ConfirmRenameCollection),
+                button(t("common.cancel")).on_press(Message::CancelRenameCollection),
+            ]
+            .spacing(5)
+            .width(Length::Fill)
+            .into()
+        } else {
+            let toggle_name = name.clone();
+            checkbox(name.as_str(), in_conversation)
+                .on_toggle(move |enabled| Message::ToggleConversationCollection(toggle_name.clone(), enabled))
+                .width(Length::Fill)
+                .into()
+        };
+
+        list = list.push(
+            row![
+                title,
+                button(t("collections.details")).on_press(Message::SelectCollection(name.clone())),
+                button(t("sidebar.rename")).on_press(Message::StartRenameCollection(name.clone())),
+                button(t("common.delete")).on_press(Confirmation::request(
+                    tf("collections.delete_confirm", &[&name]),
+                    t("collections.delete"),
+                    Message::DeleteCollection(name.clone()),
+                )),
+            ]
+            .spacing(5)
+            .align_items(iced::Alignment::Center)
+        );
+    }
+
+    let mut content = column![create_row, list].spacing(20).padding(15);
+
+    if let Some(status) = &app.collections_status {
+        content = content.push(
+            text(status)
+                .size(12)
+                .style(palette.danger)
+        );
+    }
+
+    if let Some(stats) = &app.collection_stats {
+        content = content.push(create_collection_details(app, stats));
+    } else if let Some(name) = &app.selected_collection {
+        content = content.push(text(tf("collections.loading", &[&name])).size(12));
+    }
+
+    container(
+        column![
+            header,
+            scrollable(content).height(Length::Fill)
+        ]
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
+
+fn create_collection_details<'a>(app: &'a ChatApp, stats: &'a knowledge::CollectionStats) -> Element<'a, Message> {
+    let palette = app.palette();
+
+    let settings = app.settings.collections.iter().find(|c| c.name == stats.name);
+    let hybrid = settings.map(|c| c.hybrid).unwrap_or(true);
+    let rerank = settings.map(|c| c.rerank).unwrap_or(false);
+    let hybrid_name = stats.name.clone();
+    let rerank_name = stats.name.clone();
+
+    let mut details = column![
+        text(tf("collections.name", &[&stats.name])).size(16),
+        text(tf(
+            "collections.stats",
+            &[
+                &stats.documents.len(),
+                &stats.chunk_count,
+                &stats.embedding_model,
+                &stats.vector_size,
+            ],
+        ))
+        .size(12),
+        checkbox(t("collections.hybrid"), hybrid)
+            .on_toggle(move |enabled| Message::SetCollectionHybrid(hybrid_name.clone(), enabled)),
+        checkbox(t("collections.rerank"), rerank)
+            .on_toggle(move |enabled| Message::SetCollectionRerank(rerank_name.clone(), enabled)),
+        button(t("collections.add_documents"))
+            .on_press(Message::IngestDocuments(stats.name.clone())),
+    ]
+    .spacing(8);
+
+    for document in &stats.documents {
+        details = details.push(
+            row![
+                text(&document.file_name)
+                    .size(13)
+                    .width(Length::Fill),
+                text(tf("collections.chunks", &[&document.chunks]))
+                    .size(12)
+                    .style(palette.muted_text),
+                button(text(t("common.delete")).size(12))
+                    .padding([2, 6])
+                    .on_press(Message::RemoveDocument(stats.name.clone(), document.path.clone())),
+            ]
+            .spacing(10)
+            .align_items(iced::Alignment::Center)
+        );
+    }
+
+    container(details)
+        .padding(15)
+        .style(container::Appearance {
+            background: Some(Background::Color(palette.card)),
+            border: Border::with_radius(8),
+            ..Default::default()
+        })
+        .into()
+}
+
+pub fn create_memory_panel(app: &ChatApp) -> Element<'_, Message> {
+    let palette = app.palette();
+
+    let header = container(
+        row![
+            text(t("memory.panel_title"))
+                .size(16)
+                .width(Length::Fill),
+            button(t("common.close"))
+                .on_press(Message::HideMemoryPanel),
+        ]
+        .align_items(iced::Alignment::Center)
+    )
+    .padding(15)
+    .style(container::Appearance {
+        background: Some(Background::Color(palette.toolbar)),
+        border: Border::with_radius(1.0),
+        ..Default::default()
+    });
+
+    let mut list = column![].spacing(10).padding(15);
+
+    if app.memories.is_empty() {
+        list = list.push(
+            text(t("memory.empty"))
+                .size(14)
+                .style(palette.muted_text)
+        );
+    }
+
+    for entry in &app.memories {
+        let body: Element<Message> = if app.editing_memory.as_ref() == Some(&entry.id) {
+            row![
+                text_input(t("memory.fact_placeholder"), &app.memory_input)
+                    .on_input(Message::MemoryInputChanged)
+                    .on_submit(Message::SaveMemory)
+                    .padding(8)
+                    .width(Length::Fill),
+                button(t("common.save")).on_press(Message::SaveMemory),
+                button(t("common.cancel")).on_press(Message::CancelEditMemory),
+            ]
+            .spacing(5)
+            .align_items(iced::Alignment::Center)
+            .into()
+        } else {
+            row![
+                column![
+                    text(&entry.text).size(14),
+                    text(format!("{} · {}", entry.source, format_timestamp(&entry.updated_at)))
+                        .size(10)
+                        .style(palette.faint_text),
+                ]
+                .spacing(2)
+                .width(Length::Fill),
+                button(t("common.edit")).on_press(Message::EditMemory(entry.id.clone())),
+                button(t("memory.forget")).on_press(Message::ForgetMemory(entry.id.clone())),
+            ]
+            .spacing(5)
+            .align_items(iced::Alignment::Center)
+            .into()
+        };
+
+        list = list.push(
+            container(body)
+                .padding(10)
+                .style(container::Appearance {
+                    background: Some(Background::Color(palette.card)),
+                    border: Border::with_radius(8),
+                    ..Default::default()
+                })
+        );
+    }
+
+    container(
+        column![
+            header,
+            scrollable(list).height(Length::Fill)
+        ]
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
+
+// Podgląd importu: lista odczytanych rozmów z oznaczeniem duplikatów
+pub fn create_import_panel(app: &ChatApp) -> Element<'_, Message> {
+    let palette = app.palette();
+
+    let selected = app.import_candidates.iter().filter(|c| c.selected).count();
+
+    let header = container(
+        row![
+            text(tf("import.title", &[&selected, &app.import_candidates.len()]))
+                .size(16)
+                .width(Length::Fill),
+            button(t("import.selected"))
+                .on_press(Message::ConfirmImport),
+            button(t("common.cancel"))
+                .on_press(Message::CancelImport),
+        ]
+        .spacing(5)
+        .align_items(iced::Alignment::Center)
+    )
+    .padding(15)
+    .style(container::Appearance {
+        background: Some(Background::Color(palette.toolbar)),
+        border: Border::with_radius(1.0),
+        ..Default::default()
+    });
+
+    let mut list = column![].spacing(10).padding(15);
+
+    for (index, candidate) in app.import_candidates.iter().enumerate() {
+        let conversation = &candidate.conversation;
+        let first_message = conversation
+            .messages
+            .first()
+            .map(|m| m.content.chars().take(120).collect::<String>())
+            .unwrap_or_default();
+        let period = match (conversation.messages.first(), conversation.messages.last()) {
+            (Some(first), Some(last)) if first.timestamp != last.timestamp => {
+                format!("{} – {}", format_timestamp(&first.timestamp), format_timestamp(&last.timestamp))
+            }
+            (Some(first), _) => format_timestamp(&first.timestamp),
+            _ => String::new(),
+        };
+
+        let mut details = tf(
+            "import.details",
+            &[&candidate.source.label(), &conversation.messages.len(), &period],
+        );
+        if candidate.duplicate {
+            details.push_str(t("import.duplicate"));
+        }
+
+        list = list.push(
+            container(
+                row![
+                    checkbox("", candidate.selected)
+                        .on_toggle(move |selected| Message::ToggleImportCandidate(index, selected)),
+                    column![
+                        text(&conversation.name).size(14),
+                        text(details)
+                            .size(10)
+                            .style(if candidate.duplicate {
+                                palette.warning
+                            } else {
+                                palette.faint_text
+                            }),
+                        text(first_message)
+                            .size(12)
+                            .style(palette.muted_text),
+                    ]
+                    .spacing(2)
+                    .width(Length::Fill),
+                ]
+                .spacing(5)
+                .align_items(iced::Alignment::Center)
+            )
+            .padding(10)
+            .style(container::Appearance {
+                background: Some(Background::Color(palette.card)),
+                border: Border::with_radius(8),
+                ..Default::default()
+            })
+        );
+    }
+
+    container(
+        column![
+            header,
+            scrollable(list).height(Length::Fill)
+        ]
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
+
+// Kosz: rozmowy usunięte z listy, do przywrócenia do czasu automatycznego opróżnienia
+pub fn create_trash_panel(app: &ChatApp) -> Element<'_, Message> {
+    let palette = app.palette();
+
+    let header = container(
+        row![
+            text(t("trash.title"))
+                .size(16)
+                .width(Length::Fill),
+            button(t("trash.empty_button"))
+                .on_press_maybe((!app.trash.is_empty()).then(|| Confirmation::request(
+                    t("trash.empty_confirm"),
+                    t("trash.empty_button"),
+                    Message::EmptyTrash,
+                ))),
+            button(t("common.close"))
+                .on_press(Message::HideTrashPanel),
+        ]
+        .spacing(5)
+        .align_items(iced::Alignment::Center)
+    )
+    .padding(15)
+    .style(container::Appearance {
+        background: Some(Background::Color(palette.toolbar)),
+        border: Border::with_radius(1.0),
+        ..Default::default()
+    });
+
+    let retention = row![
+        text(t("trash.retention")).size(14),
+        text_input("30", &app.trash_retention_input)
+            .on_input(Message::TrashRetentionChanged)
+            .padding(5)
+            .width(Length::Fixed(60.0)),
+    ]
+    .spacing(10)
+    .align_items(iced::Alignment::Center);
+
+    let mut list = column![retention].spacing(10).padding(15);
+
+    if app.trash.is_empty() {
+        list = list.push(
+            text(t("trash.is_empty"))
+                .size(14)
+                .style(palette.muted_text)
+        );
+    }
+
+    // Najpierw ostatnio usunięte
+    for (index, item) in app.trash.iter().enumerate().rev() {
+        list = list.push(
+            container(
+                row![
+                    column![
+                        text(&item.conversation.name).size(14),
+                        text(tf(
+                            "trash.deleted",
+                            &[&format_timestamp(&item.deleted_at), &item.conversation.messages.len()],
+                        ))
+                        .size(10)
+                        .style(palette.faint_text),
+                    ]
+                    .spacing(2)
+                    .width(Length::Fill),
+                    button(t("trash.restore")).on_press(Message::RestoreConversation(index)),
+                    button(t("trash.purge")).on_press(Confirmation::request(
+                        tf("trash.purge_confirm", &[&item.conversation.name]),
+                        t("trash.purge"),
+                        Message::PurgeConversation(index),
+                    )),
+                ]
+                .spacing(5)
+                .align_items(iced::Alignment::Center)
+            )
+            .padding(10)
+            .style(container::Appearance {
+                background: Some(Background::Color(palette.card)),
+                border: Border::with_radius(8),
+                ..Default::default()
+            })
+        );
+    }
+
+    container(
+        column![
+            header,
+            scrollable(list).height(Length::Fill)
+        ]
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
+
+pub fn create_inspector_panel(app: &ChatApp) -> Element<'_, Message> {
+    let palette = app.palette();
+
+    let header = container(
+        row![
+            column![
+                text(t("inspector.title")).size(16),
+                text(tf("inspector.log_directory", &[&logging::LOG_DIRECTORY]))
+                    .size(11)
+                    .style(palette.faint_text),
+            ]
+            .spacing(2)
+            .width(Length::Fill),
+            button(t("inspector.clear"))
+                .on_press_maybe((!app.inspector_records.is_empty()).then_some(Message::ClearInspector)),
+            button(t("common.close"))
+                .on_press(Message::HideInspectorPanel),
+        ]
+        .spacing(5)
+        .align_items(iced::Alignment::Center)
+    )
+    .padding(15)
+    .style(container::Appearance {
+        background: Some(Background::Color(palette.toolbar)),
+        border: Border::with_radius(1.0),
+        ..Default::default()
+    });
+
+    // Lista żądań, od najnowszego
+    let mut list = column![].spacing(5).padding(10);
+    if app.inspector_records.is_empty() {
+        list = list.push(text(t("inspector.empty")).size(13).style(palette.muted_text));
+    }
+    for record in &app.inspector_records {
+        let status = match record.status {
+            Some(status) => text(status.to_string()),
+            None => text(t("inspector.no_response")),
+        }
+        .size(11)
+        .style(if record.succeeded() { palette.success } else { palette.danger });
+
+        list = list.push(
+            button(
+                column![
+                    row![
+                        text(format!("{} {}", record.method, request_path(&record.url)))
+                            .size(13)
+                            .width(Length::Fill),
+                        status,
+                    ]
+                    .spacing(5),
+                    text(request_summary(record)).size(11).style(palette.faint_text),
+                ]
+                .spacing(2)
+            )
+            .width(Length::Fill)
+            .style(if app.selected_request == Some(record.id) {
+                theme::Button::Primary
+            } else {
+                theme::Button::Secondary
+            })
+            .on_press(Message::SelectRequest(record.id))
+        );
+    }
+
+    let details: Element<Message> = match app
+        .selected_request
+        .and_then(|id| app.inspector_records.iter().find(|r| r.id == id))
+    {
+        Some(record) => create_request_details(app, record),
+        None => container(text(t("inspector.select_hint")).size(13).style(palette.muted_text))
+            .padding(15)
+            .into(),
+    };
+
+    container(
+        column![
+            header,
+            row![
+                scrollable(list).width(Length::Fixed(340.0)).height(Length::Fill),
+                scrollable(details).width(Length::Fill).height(Length::Fill),
+            ]
+        ]
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
+
+fn create_request_details<'a>(app: &'a ChatApp, record: &'a RequestRecord) -> Element<'a, Message> {
+    let palette = app.palette();
+
+    let mut content = column![
+        text(format!("{} {}", record.method, record.url)).size(15),
+        text(request_summary(record)).size(12).style(palette.muted_text),
+        row![
+            button(text(t("inspector.copy_curl")).size(13)).on_press(Message::CopyRequestAsCurl(record.id)),
+            button(text(t("inspector.copy_response")).size(13))
+                .on_press_maybe(record.response_body.is_some().then_some(Message::CopyResponse(record.id))),
+        ]
+        .spacing(5),
+    ]
+    .spacing(8)
+    .padding(15);
+
+    if let Some(error) = &record.error {
+        content = content.push(text(tf("inspector.error", &[error])).size(13).style(palette.danger));
+    }
+
+    let headers = |headers: &[(String, String)]| {
+        headers
+            .iter()
+            .map(|(name, value)| format!("{}: {}", name, value))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let sections = [
+        (t("inspector.request_headers"), Some(headers(&record.request_headers))),
+        (t("inspector.request_body"), record.request_body.clone()),
+        (t("inspector.response_headers"), Some(headers(&record.response_headers))),
+        (t("inspector.response_body"), record.response_body.clone()),
+    ];
+    for (title, body) in sections {
+        let body = body.filter(|b| !b.is_empty());
+        content = content.push(text(title).size(14));
+        content = content.push(match body {
+            Some(body) => code_block(palette, body),
+            None => text(t("inspector.no_body")).size(12).style(palette.faint_text).into(),
+        });
+    }
+
+    content.into()
+}
+
+// Tekst czcionką o stałej szerokości na osobnym tle
+fn code_block<'a>(palette: Palette, content: String) -> Element<'a, Message> {
+    container(text(content).size(12).font(iced::Font::MONOSPACE))
+        .padding(8)
+        .width(Length::Fill)
+        .style(container::Appearance {
+            background: Some(Background::Color(palette.surface)),
+            text_color: Some(palette.text),
+            border: Border::with_radius(6),
+            ..Default::default()
+        })
+        .into()
+}
+
+// Ścieżka adresu bez schematu i hosta, np. /v1/chat/completions
+fn request_path(url: &str) -> &str {
+    url.split_once("://")
+        .and_then(|(_, rest)| rest.find('/').map(|index| &rest[index..]))
+        .unwrap_or(url)
+}
+
+fn request_summary(record: &RequestRecord) -> String {
+    let mut parts = vec![format_timestamp(&record.timestamp), tf("inspector.duration", &[&record.duration_ms])];
+    if let (Some(prompt), Some(completion)) = (record.prompt_tokens, record.completion_tokens) {
+        parts.push(tf("inspector.tokens", &[&prompt, &completion]));
+    }
+    parts.join(" · ")
+}
+
+pub fn create_undo_toast(palette: Palette, name: &str) -> Element<'static, Message> {
+    container(
+        row![
+            text(tf("trash.moved", &[&name]))
+                .size(14)
+                .style(palette.on_header)
+                .width(Length::Fill),
+            button(t("common.undo")).on_press(Message::UndoDelete),
+            button(text("✕")).on_press(Message::DismissUndoToast),
+        ]
+        .spacing(10)
+        .align_items(iced::Alignment::Center)
+    )
+    .padding(10)
+    .width(Length::Fill)
+    .style(container::Appearance {
+        background: Some(Background::Color(palette.header)),
+        ..Default::default()
+    })
+    .into()
+}
@@ -0,0 +1,186 @@
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+use iced::widget::{column, container, horizontal_rule, row, text};
+use iced::{Background, Border, Element, Font, Length};
+
+use knowledge_app::export;
+
+use super::palette::Palette;
+use crate::Message;
+
+// Po przekroczeniu tej liczby wpisów pamięć podręczna jest czyszczona
+const CACHE_LIMIT: usize = 2000;
+
+// Przybliżone wymiary tekstu do szacowania wysokości niewyrenderowanych wiadomości
+const LINE_HEIGHT: f32 = 1.3;
+const CHAR_WIDTH: f32 = 0.55;
+const BLOCK_SPACING: f32 = 6.0;
+const CODE_PADDING: f32 = 8.0;
+
+// Blok odpowiedzi modelu po przetworzeniu Markdown; tekst jest już bez znaczników
+#[derive(Debug, Clone)]
+pub enum Block {
+    Heading(u8, String),
+    Paragraph(String),
+    ListItem(String),
+    Quote(String),
+    Code(String),
+    Rule,
+}
+
+impl Block {
+    fn font_size(&self) -> f32 {
+        match self {
+            Block::Heading(1, _) => 20.0,
+            Block::Heading(2, _) => 18.0,
+            Block::Heading(_, _) => 16.0,
+            Block::Code(_) => 13.0,
+            _ => 14.0,
+        }
+    }
+
+    /// Szacowana wysokość bloku przy danej szerokości
+    fn estimated_height(&self, width: f32) -> f32 {
+        let size = self.font_size();
+        let content = match self {
+            Block::Heading(_, text) | Block::Paragraph(text) | Block::ListItem(text) | Block::Quote(text) => text,
+            Block::Code(code) => code,
+            Block::Rule => return BLOCK_SPACING * 2.0,
+        };
+        let padding = if matches!(self, Block::Code(_)) { CODE_PADDING * 2.0 } else { 0.0 };
+
+        wrapped_lines(content, width - padding, size) as f32 * size * LINE_HEIGHT + padding + BLOCK_SPACING
+    }
+}
+
+/// Przetworzone odpowiedzi, żeby Markdown nie był analizowany przy każdym `view`
+#[derive(Debug, Default)]
+pub struct MarkdownCache {
+    entries: RefCell<HashMap<u64, Rc<Vec<Block>>>>,
+}
+
+impl MarkdownCache {
+    pub fn blocks(&self, content: &str) -> Rc<Vec<Block>> {
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        let key = hasher.finish();
+
+        let mut entries = self.entries.borrow_mut();
+        if entries.len() > CACHE_LIMIT {
+            entries.clear();
+        }
+        entries.entry(key).or_insert_with(|| Rc::new(parse(content))).clone()
+    }
+}
+
+/// Dzieli Markdown na bloki: nagłówki, akapity, elementy list, cytaty, kod i linie poziome
+pub fn parse(content: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut code: Option<Vec<&str>> = None;
+
+    let flush = |paragraph: &mut Vec<&str>, blocks: &mut Vec<Block>| {
+        if !paragraph.is_empty() {
+            blocks.push(Block::Paragraph(export::plain_text(&paragraph.join("\n"))));
+            paragraph.clear();
+        }
+    };
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+
+        if trimmed.starts_with("```") {
+            match code.take() {
+                Some(lines) => blocks.push(Block::Code(lines.join("\n"))),
+                None => {
+                    flush(&mut paragraph, &mut blocks);
+                    code = Some(Vec::new());
+                }
+            }
+            continue;
+        }
+        if let Some(lines) = code.as_mut() {
+            lines.push(line);
+            continue;
+        }
+
+        let heading_level = trimmed.chars().take_while(|c| *c == '#').count();
+        let block = if trimmed.is_empty() {
+            None
+        } else if (1..=6).contains(&heading_level) && trimmed[heading_level..].starts_with(' ') {
+            Some(Block::Heading(heading_level as u8, export::plain_text(trimmed[heading_level..].trim())))
+        } else if matches!(trimmed, "---" | "***" | "___") {
+            Some(Block::Rule)
+        } else if let Some(item) = trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* ")) {
+            Some(Block::ListItem(export::plain_text(item)))
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            Some(Block::Quote(export::plain_text(quote.trim_start())))
+        } else {
+            paragraph.push(trimmed);
+            continue;
+        };
+
+        flush(&mut paragraph, &mut blocks);
+        blocks.extend(block);
+    }
+
+    flush(&mut paragraph, &mut blocks);
+    // Niezamknięty blok kodu, np. w odpowiedzi przerwanej w połowie
+    if let Some(lines) = code {
+        blocks.push(Block::Code(lines.join("\n")));
+    }
+
+    blocks
+}
+
+/// Widżety bloków; kod jest wyświetlany czcionką o stałej szerokości na osobnym tle
+pub fn view<'a>(palette: Palette, blocks: &[Block]) -> Element<'a, Message> {
+    blocks
+        .iter()
+        .fold(column![].spacing(BLOCK_SPACING), |content, block| {
+            let size = block.font_size();
+            let element: Element<'a, Message> = match block {
+                Block::Heading(_, heading) => text(heading.clone()).size(size).into(),
+                Block::Paragraph(paragraph) => text(paragraph.clone()).size(size).into(),
+                Block::ListItem(item) => row![text("•").size(size), text(item.clone()).size(size)]
+                    .spacing(6)
+                    .into(),
+                Block::Quote(quote) => text(format!("│ {}", quote))
+                    .size(size)
+                    .style(palette.muted_text)
+                    .into(),
+                Block::Code(code) => container(text(code.clone()).size(size).font(Font::MONOSPACE))
+                    .padding(CODE_PADDING)
+                    .width(Length::Fill)
+                    .style(container::Appearance {
+                        background: Some(Background::Color(palette.surface)),
+                        text_color: Some(palette.text),
+                        border: Border::with_radius(6),
+                        ..Default::default()
+                    })
+                    .into(),
+                Block::Rule => horizontal_rule(1).into(),
+            };
+            content.push(element)
+        })
+        .into()
+}
+
+/// Szacowana wysokość treści złożonej z bloków
+pub fn estimated_height(blocks: &[Block], width: f32) -> f32 {
+    blocks.iter().map(|block| block.estimated_height(width)).sum()
+}
+
+/// Liczba linii tekstu po zawinięciu do podanej szerokości
+pub fn wrapped_lines(content: &str, width: f32, size: f32) -> usize {
+    let per_line = (width / (size * CHAR_WIDTH)).max(1.0) as usize;
+    content
+        .lines()
+        .map(|line| line.chars().count().div_ceil(per_line).max(1))
+        .sum::<usize>()
+        .max(1)
+}
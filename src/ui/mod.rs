@@ -0,0 +1,12 @@
+// Deklaracja pliku zawierającego wszystkie funkcje UI dla tego modułu
+pub mod chat_application_ui;
+// Warstwa okien dialogowych nad główną zawartością
+pub mod modal;
+// Bloki Markdown odpowiedzi modelu i ich pamięć podręczna
+pub mod markdown;
+// Kolory motywów jasnego, ciemnego i o wysokim kontraście
+pub mod palette;
+
+// Reeksportowanie funkcji publicznych z ChatApplicationUI dla łatwiejszego dostępu
+pub use chat_application_ui::{create_sidebar, create_chat_area, create_dialog, save_name_input_id, conversation_search_id, messages_scroll_id, create_collections_panel, create_memory_panel, create_import_panel, create_trash_panel, create_inspector_panel, create_undo_toast, TAG_COLOR_COUNT};
+
@@ -0,0 +1,278 @@
+use iced::advanced::layout::{self, Layout};
+use iced::advanced::overlay;
+use iced::advanced::renderer;
+use iced::advanced::widget::{self, Widget};
+use iced::advanced::{Clipboard, Shell};
+use iced::keyboard::{self, key};
+use iced::{
+    event, mouse, Alignment, Border, Color, Element, Event, Length, Point, Rectangle, Shadow, Size, Vector,
+};
+
+// Przyciemnienie zawartości pod otwartym oknem dialogowym
+const BACKDROP: Color = Color { r: 0.0, g: 0.0, b: 0.0, a: 0.5 };
+
+/// Okno dialogowe wyświetlane nad zawartością, z przyciemnionym tłem.
+///
+/// Dopóki jest otwarte, zawartość pod spodem nie dostaje zdarzeń ani operacji
+/// (np. przełączania fokusu), więc fokus pozostaje w oknie. Esc i kliknięcie
+/// w tło wysyłają komunikat z `on_close`.
+pub struct Modal<'a, Message, Theme, Renderer> {
+    base: Element<'a, Message, Theme, Renderer>,
+    content: Element<'a, Message, Theme, Renderer>,
+    on_close: Option<Message>,
+}
+
+impl<'a, Message, Theme, Renderer> Modal<'a, Message, Theme, Renderer> {
+    pub fn new(
+        base: impl Into<Element<'a, Message, Theme, Renderer>>,
+        content: impl Into<Element<'a, Message, Theme, Renderer>>,
+    ) -> Self {
+        Modal {
+            base: base.into(),
+            content: content.into(),
+            on_close: None,
+        }
+    }
+
+    pub fn on_close(mut self, message: Message) -> Self {
+        self.on_close = Some(message);
+        self
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer> for Modal<'a, Message, Theme, Renderer>
+where
+    Message: Clone,
+    Renderer: renderer::Renderer,
+{
+    fn children(&self) -> Vec<widget::Tree> {
+        vec![widget::Tree::new(&self.base), widget::Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut widget::Tree) {
+        tree.diff_children(&[&self.base, &self.content]);
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.base.as_widget().size()
+    }
+
+    fn layout(&self, tree: &mut widget::Tree, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.base.as_widget().layout(&mut tree.children[0], renderer, limits)
+    }
+
+    // Zawartość pod oknem jest nieaktywna
+    fn on_event(
+        &mut self,
+        _state: &mut widget::Tree,
+        _event: Event,
+        _layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        _shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) -> event::Status {
+        event::Status::Ignored
+    }
+
+    fn draw(
+        &self,
+        state: &widget::Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.base.as_widget().draw(&state.children[0], renderer, theme, style, layout, cursor, viewport);
+    }
+
+    // Operacje (fokus, przewijanie) trafiają tylko do okna, przez jego warstwę
+    fn operate(
+        &self,
+        _state: &mut widget::Tree,
+        _layout: Layout<'_>,
+        _renderer: &Renderer,
+        _operation: &mut dyn widget::Operation<Message>,
+    ) {
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        state: &'b mut widget::Tree,
+        layout: Layout<'_>,
+        _renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        Some(overlay::Element::new(Box::new(Overlay {
+            position: layout.position() + translation,
+            content: &mut self.content,
+            tree: &mut state.children[1],
+            size: layout.bounds().size(),
+            on_close: self.on_close.clone(),
+        })))
+    }
+}
+
+struct Overlay<'a, 'b, Message, Theme, Renderer> {
+    position: Point,
+    content: &'b mut Element<'a, Message, Theme, Renderer>,
+    tree: &'b mut widget::Tree,
+    size: Size,
+    on_close: Option<Message>,
+}
+
+impl<'a, 'b, Message, Theme, Renderer> overlay::Overlay<Message, Theme, Renderer>
+    for Overlay<'a, 'b, Message, Theme, Renderer>
+where
+    Message: Clone,
+    Renderer: renderer::Renderer,
+{
+    fn layout(&mut self, renderer: &Renderer, _bounds: Size) -> layout::Node {
+        let limits = layout::Limits::new(Size::ZERO, self.size)
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        let child = self
+            .content
+            .as_widget()
+            .layout(self.tree, renderer, &limits)
+            .align(Alignment::Center, Alignment::Center, limits.max());
+
+        layout::Node::with_children(self.size, vec![child]).move_to(self.position)
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        let content_bounds = layout.children().next().unwrap().bounds();
+
+        if let Some(message) = &self.on_close {
+            match &event {
+                Event::Keyboard(keyboard::Event::KeyPressed {
+                    key: keyboard::Key::Named(key::Named::Escape),
+                    ..
+                }) => {
+                    shell.publish(message.clone());
+                    return event::Status::Captured;
+                }
+                Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+                    if !cursor.is_over(content_bounds) =>
+                {
+                    shell.publish(message.clone());
+                    return event::Status::Captured;
+                }
+                _ => {}
+            }
+        }
+
+        self.content.as_widget_mut().on_event(
+            self.tree,
+            event,
+            layout.children().next().unwrap(),
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            &layout.bounds(),
+        );
+
+        // Żadne zdarzenie nie przecieka do zawartości pod oknem
+        event::Status::Captured
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+    ) {
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: layout.bounds(),
+                border: Border::default(),
+                shadow: Shadow::default(),
+            },
+            BACKDROP,
+        );
+
+        self.content.as_widget().draw(
+            self.tree,
+            renderer,
+            theme,
+            style,
+            layout.children().next().unwrap(),
+            cursor,
+            &layout.bounds(),
+        );
+    }
+
+    fn operate(
+        &mut self,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn widget::Operation<Message>,
+    ) {
+        self.content.as_widget().operate(
+            self.tree,
+            layout.children().next().unwrap(),
+            renderer,
+            operation,
+        );
+    }
+
+    fn mouse_interaction(
+        &self,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            self.tree,
+            layout.children().next().unwrap(),
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    // Okno przykrywa cały ekran razem z tłem
+    fn is_over(&self, _layout: Layout<'_>, _renderer: &Renderer, _cursor_position: Point) -> bool {
+        true
+    }
+
+    fn overlay<'c>(
+        &'c mut self,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'c, Message, Theme, Renderer>> {
+        self.content.as_widget_mut().overlay(
+            self.tree,
+            layout.children().next().unwrap(),
+            renderer,
+            Vector::ZERO,
+        )
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<Modal<'a, Message, Theme, Renderer>> for Element<'a, Message, Theme, Renderer>
+where
+    Theme: 'a,
+    Message: 'a + Clone,
+    Renderer: 'a + renderer::Renderer,
+{
+    fn from(modal: Modal<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(modal)
+    }
+}
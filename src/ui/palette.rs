@@ -0,0 +1,138 @@
+use iced::{theme, Color, Theme};
+
+use knowledge_app::settings::ThemePreference;
+
+// Kolory interfejsu nazwane według roli; każdy motyw dostarcza własny zestaw
+#[derive(Debug, Clone, Copy)]
+pub struct Palette {
+    // Pasek tytułu panelu bocznego i komunikaty typu toast
+    pub header: Color,
+    pub on_header: Color,
+    pub sidebar: Color,
+    // Pasek nagłówka paneli i obszaru czatu
+    pub toolbar: Color,
+    // Okna dialogowe i menu
+    pub surface: Color,
+    // Elementy list w panelach
+    pub card: Color,
+    pub border: Color,
+    pub text: Color,
+    pub muted_text: Color,
+    pub faint_text: Color,
+    // Dymek wiadomości użytkownika i główny przycisk
+    pub accent: Color,
+    pub on_accent: Color,
+    // Dymek odpowiedzi modelu i chipy załączników
+    pub bubble: Color,
+    pub on_bubble: Color,
+    pub secondary_accent: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub danger: Color,
+}
+
+impl Palette {
+    pub const LIGHT: Palette = Palette {
+        header: rgb(0.2, 0.2, 0.2),
+        on_header: Color::WHITE,
+        sidebar: rgb(0.95, 0.95, 0.95),
+        toolbar: rgb(0.98, 0.98, 0.98),
+        surface: Color::WHITE,
+        card: rgb(0.95, 0.95, 0.95),
+        border: rgb(0.8, 0.8, 0.8),
+        text: Color::BLACK,
+        muted_text: rgb(0.4, 0.4, 0.4),
+        faint_text: rgb(0.6, 0.6, 0.6),
+        accent: rgb(0.0, 0.5, 1.0),
+        on_accent: Color::WHITE,
+        bubble: rgb(0.9, 0.9, 0.9),
+        on_bubble: Color::BLACK,
+        secondary_accent: rgb(1.0, 0.5, 0.0),
+        success: rgb(0.2, 0.6, 0.2),
+        warning: rgb(0.8, 0.4, 0.0),
+        danger: rgb(0.8, 0.1, 0.1),
+    };
+
+    pub const DARK: Palette = Palette {
+        header: rgb(0.08, 0.08, 0.1),
+        on_header: rgb(0.92, 0.92, 0.92),
+        sidebar: rgb(0.14, 0.14, 0.16),
+        toolbar: rgb(0.17, 0.17, 0.19),
+        surface: rgb(0.2, 0.2, 0.23),
+        card: rgb(0.22, 0.22, 0.25),
+        border: rgb(0.35, 0.35, 0.38),
+        text: rgb(0.92, 0.92, 0.92),
+        muted_text: rgb(0.65, 0.65, 0.68),
+        faint_text: rgb(0.5, 0.5, 0.53),
+        accent: rgb(0.2, 0.45, 0.85),
+        on_accent: Color::WHITE,
+        bubble: rgb(0.25, 0.25, 0.28),
+        on_bubble: rgb(0.92, 0.92, 0.92),
+        secondary_accent: rgb(0.85, 0.45, 0.1),
+        success: rgb(0.4, 0.8, 0.4),
+        warning: rgb(0.95, 0.6, 0.2),
+        danger: rgb(0.95, 0.35, 0.35),
+    };
+
+    pub const HIGH_CONTRAST: Palette = Palette {
+        header: Color::BLACK,
+        on_header: Color::WHITE,
+        sidebar: Color::BLACK,
+        toolbar: Color::BLACK,
+        surface: Color::BLACK,
+        card: rgb(0.1, 0.1, 0.1),
+        border: Color::WHITE,
+        text: Color::WHITE,
+        muted_text: Color::WHITE,
+        faint_text: rgb(0.85, 0.85, 0.85),
+        accent: rgb(1.0, 1.0, 0.0),
+        on_accent: Color::BLACK,
+        bubble: rgb(0.15, 0.15, 0.15),
+        on_bubble: Color::WHITE,
+        secondary_accent: rgb(0.0, 1.0, 1.0),
+        success: rgb(0.3, 1.0, 0.3),
+        warning: rgb(1.0, 0.75, 0.0),
+        danger: rgb(1.0, 0.4, 0.4),
+    };
+
+    /// Paleta dla wybranego motywu; `System` rozstrzyga wykryty tryb systemu
+    pub fn for_preference(preference: ThemePreference, system_dark: bool) -> Palette {
+        match preference {
+            ThemePreference::System if system_dark => Palette::DARK,
+            ThemePreference::System | ThemePreference::Light => Palette::LIGHT,
+            ThemePreference::Dark => Palette::DARK,
+            ThemePreference::HighContrast => Palette::HIGH_CONTRAST,
+        }
+    }
+}
+
+/// Motyw iced dla wbudowanych widżetów (przyciski, pola tekstowe, pola wyboru)
+pub fn iced_theme(preference: ThemePreference, system_dark: bool) -> Theme {
+    match preference {
+        ThemePreference::System if system_dark => Theme::Dark,
+        ThemePreference::System | ThemePreference::Light => Theme::Light,
+        ThemePreference::Dark => Theme::Dark,
+        ThemePreference::HighContrast => {
+            let palette = Palette::HIGH_CONTRAST;
+            Theme::custom(
+                "Wysoki kontrast".to_string(),
+                theme::Palette {
+                    background: palette.surface,
+                    text: palette.text,
+                    primary: palette.accent,
+                    success: palette.success,
+                    danger: palette.danger,
+                },
+            )
+        }
+    }
+}
+
+const fn rgb(r: f32, g: f32, b: f32) -> Color {
+    Color { r, g, b, a: 1.0 }
+}
+
+/// Czy system używa ciemnego motywu
+pub fn system_prefers_dark() -> bool {
+    matches!(dark_light::detect(), dark_light::Mode::Dark)
+}
@@ -0,0 +1,54 @@
+use std::hash::Hash;
+use std::path::Path;
+use std::time::Duration;
+
+use iced::futures::channel::mpsc;
+use iced::futures::{SinkExt, StreamExt};
+use iced::subscription::{self, Subscription};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+// Czas ciszy po ostatniej zmianie, zanim zgłosimy potrzebę synchronizacji
+const DEBOUNCE: Duration = Duration::from_secs(2);
+
+/// Subskrypcja wysyłająca `on_change` po zmianach plików w podanych folderach
+pub fn watch<M>(directories: Vec<String>, on_change: M) -> Subscription<M>
+where
+    M: Clone + Send + 'static,
+{
+    #[derive(Hash)]
+    struct WatchId(Vec<String>);
+
+    let id = WatchId(directories.clone());
+
+    subscription::channel(id, 10, move |mut output| async move {
+        let (sender, mut events) = mpsc::unbounded();
+
+        let watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+            if let Ok(event) = result
+                && !event.kind.is_access()
+            {
+                let _ = sender.unbounded_send(());
+            }
+        });
+
+        // Watcher musi żyć tak długo jak subskrypcja
+        let _watcher: Option<RecommendedWatcher> = watcher.ok().map(|mut watcher| {
+            for directory in &directories {
+                let _ = watcher.watch(Path::new(directory), RecursiveMode::Recursive);
+            }
+            watcher
+        });
+
+        loop {
+            if events.next().await.is_none() {
+                // Bez działającego watchera subskrypcja tylko czeka
+                iced::futures::future::pending::<()>().await;
+            }
+
+            // Zbierz serię zmian (np. zapis pliku przez edytor) w jedno zgłoszenie
+            while let Ok(Some(())) = tokio::time::timeout(DEBOUNCE, events.next()).await {}
+
+            let _ = output.send(on_change.clone()).await;
+        }
+    })
+}